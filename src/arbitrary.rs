@@ -0,0 +1,39 @@
+//! arbitrary support.
+//!
+//! Generated tags are always valid, with boundary lengths (empty and maximum) being preferred.
+
+use core::str;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{EntityTag, MAX_TAG_LEN};
+
+impl<'a> Arbitrary<'a> for EntityTag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let weak = bool::arbitrary(u)?;
+        let len = match u.int_in_range(0u8..=3)? {
+            0 => 0,
+            1 => MAX_TAG_LEN,
+            _ => u.int_in_range(0..=MAX_TAG_LEN)?,
+        };
+
+        let mut storage = [0u8; MAX_TAG_LEN];
+        for byte in storage[..len].iter_mut() {
+            //etagc is %x21 / %x23-7E
+            *byte = match u.int_in_range(0x21u8..=0x7D)? {
+                0x21 => 0x21,
+                byte => byte + 1,
+            };
+        }
+
+        match str::from_utf8(&storage[..len]) {
+            Ok(tag) => Ok(EntityTag::new_unchecked(weak, tag)),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(_: usize) -> (usize, Option<usize>) {
+        (2, Some(2 + MAX_TAG_LEN))
+    }
+}
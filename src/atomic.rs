@@ -0,0 +1,137 @@
+//! Lock-free shared tag.
+
+use core::{hint, str};
+
+#[cfg(not(feature = "portable-atomic"))]
+use core::sync::atomic::{fence, AtomicU32, Ordering};
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{fence, AtomicU32, Ordering};
+
+use crate::{EntityTag, MAX_TAG_LEN};
+
+//Tag is stored as `[kind, len, tag..]`, where kind is `0` for strong, `1` for weak and `2` for `EntityTag::ANY`
+const BYTES: usize = MAX_TAG_LEN + 2;
+const WORDS: usize = BYTES.div_ceil(4);
+
+type Words = [u32; WORDS];
+
+fn encode(tag: &EntityTag) -> Words {
+    let value = tag.tag().as_bytes();
+    let mut bytes = [0u8; WORDS * 4];
+    bytes[0] = match tag.is_any() {
+        true => 2,
+        false => tag.is_weak() as u8,
+    };
+    bytes[1] = value.len() as u8;
+    bytes[2..2 + value.len()].copy_from_slice(value);
+
+    let mut words = [0u32; WORDS];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    words
+}
+
+fn decode(words: &Words) -> Option<EntityTag> {
+    let mut bytes = [0u8; WORDS * 4];
+    for (word, chunk) in words.iter().zip(bytes.chunks_exact_mut(4)) {
+        chunk.copy_from_slice(&word.to_ne_bytes());
+    }
+
+    if bytes[0] == 2 {
+        return Some(EntityTag::ANY);
+    }
+
+    let len = bytes[1] as usize;
+    if len > MAX_TAG_LEN {
+        return None;
+    }
+
+    match str::from_utf8(&bytes[2..2 + len]) {
+        Ok(tag) => Some(EntityTag::new_unchecked(bytes[0] != 0, tag)),
+        Err(_) => None,
+    }
+}
+
+/// `EntityTag` cell, which can be shared between threads without locking.
+///
+/// Uses sequence lock, making readers never block the writer.
+/// Readers retry only when the tag is modified during read.
+///
+/// Concurrent writers are serialized by spinning.
+pub struct AtomicEntityTag {
+    seq: AtomicU32,
+    words: [AtomicU32; WORDS],
+}
+
+impl AtomicEntityTag {
+    /// Creates new cell with initial tag.
+    pub fn new(tag: &EntityTag) -> Self {
+        let words = encode(tag);
+        Self {
+            seq: AtomicU32::new(0),
+            words: words.map(AtomicU32::new),
+        }
+    }
+
+    /// Loads current tag.
+    pub fn load(&self) -> EntityTag {
+        loop {
+            let seq = self.seq.load(Ordering::Acquire);
+            if seq & 1 == 0 {
+                let mut words = [0u32; WORDS];
+                for (word, atomic) in words.iter_mut().zip(self.words.iter()) {
+                    *word = atomic.load(Ordering::Relaxed);
+                }
+
+                fence(Ordering::Acquire);
+                if self.seq.load(Ordering::Relaxed) == seq {
+                    if let Some(tag) = decode(&words) {
+                        break tag;
+                    }
+                }
+            }
+
+            hint::spin_loop();
+        }
+    }
+
+    /// Stores new tag.
+    pub fn store(&self, tag: &EntityTag) {
+        let words = encode(tag);
+
+        let mut seq = self.seq.load(Ordering::Relaxed);
+        loop {
+            if seq & 1 == 0 {
+                match self.seq.compare_exchange_weak(seq, seq.wrapping_add(1), Ordering::Acquire, Ordering::Relaxed) {
+                    Ok(_) => break,
+                    Err(actual) => seq = actual,
+                }
+            } else {
+                hint::spin_loop();
+                seq = self.seq.load(Ordering::Relaxed);
+            }
+        }
+        fence(Ordering::Release);
+
+        for (word, atomic) in words.iter().zip(self.words.iter()) {
+            atomic.store(*word, Ordering::Relaxed);
+        }
+
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+}
+
+impl From<EntityTag> for AtomicEntityTag {
+    #[inline]
+    fn from(tag: EntityTag) -> Self {
+        Self::new(&tag)
+    }
+}
+
+impl core::fmt::Debug for AtomicEntityTag {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.load(), f)
+    }
+}
@@ -0,0 +1,53 @@
+//! borsh support.
+//!
+//! Tag is represented as `(weak, tag)` tuple, while `EntityTag::ANY` is represented by single byte `2`.
+
+use core::str;
+
+use ::borsh::{BorshSerialize, BorshDeserialize};
+use ::borsh::io::{self, Read, Write, ErrorKind};
+
+use crate::{EntityTag, ParseError, MAX_TAG_LEN};
+
+///Tag of `EntityTag::ANY`, following `bool` values.
+const ANY: u8 = 2;
+
+#[cold]
+#[inline(never)]
+fn invalid_data(error: ParseError) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, error.as_str())
+}
+
+impl BorshSerialize for EntityTag {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.is_any() {
+            return ANY.serialize(writer);
+        }
+
+        self.is_weak().serialize(writer)?;
+        self.tag().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for EntityTag {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let weak = match u8::deserialize_reader(reader)? {
+            0 => false,
+            1 => true,
+            ANY => return Ok(EntityTag::ANY),
+            _ => return Err(invalid_data(ParseError::InvalidFormat)),
+        };
+        let len = u32::deserialize_reader(reader)? as usize;
+        if len > MAX_TAG_LEN {
+            return Err(invalid_data(ParseError::Overflow));
+        }
+
+        let mut storage = [0u8; MAX_TAG_LEN];
+        reader.read_exact(&mut storage[..len])?;
+        match str::from_utf8(&storage[..len]) {
+            Ok(tag) => EntityTag::checked_new(weak, tag).map_err(invalid_data),
+            Err(_) => Err(invalid_data(ParseError::NotAscii)),
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Fixed size string buffer.
+
+use core::{fmt, str};
+#[cfg(not(feature = "safe-buffer"))]
+use core::{mem, slice};
+
+#[cfg(not(feature = "safe-buffer"))]
+///Byte of storage, initialized only within written part.
+pub(crate) type Byte = mem::MaybeUninit<u8>;
+#[cfg(feature = "safe-buffer")]
+///Byte of storage, zeroed outside of written part.
+pub(crate) type Byte = u8;
+
+#[cfg(not(feature = "safe-buffer"))]
+///Unwritten byte of storage.
+pub(crate) const UNINIT: Byte = mem::MaybeUninit::uninit();
+#[cfg(feature = "safe-buffer")]
+///Unwritten byte of storage.
+pub(crate) const UNINIT: Byte = 0;
+
+#[inline(always)]
+///Creates written byte of storage.
+pub(crate) const fn byte(value: u8) -> Byte {
+    #[cfg(not(feature = "safe-buffer"))]
+    {
+        mem::MaybeUninit::new(value)
+    }
+    #[cfg(feature = "safe-buffer")]
+    {
+        value
+    }
+}
+
+///Stack based string buffer, capable of holding up to `N` bytes.
+///
+///Capacity is limited to `u8::MAX`.
+//Invariant: first `cursor` bytes of `inner` are initialized and form valid UTF-8.
+//Copying the rest is sound, as it is never read as `u8`.
+#[derive(Clone, Copy)]
+pub struct StrBuf<const N: usize> {
+    inner: [Byte; N],
+    cursor: u8,
+}
+
+impl<const N: usize> StrBuf<N> {
+    #[inline]
+    ///Creates new empty buffer.
+    pub const fn new() -> Self {
+        assert!(N <= u8::MAX as usize, "Capacity cannot exceed u8::MAX");
+        Self {
+            inner: [UNINIT; N],
+            cursor: 0,
+        }
+    }
+
+    #[inline]
+    ///Creates buffer with content of `text`, returning `None` if it doesn't fit.
+    pub fn from_str_checked(text: &str) -> Option<Self> {
+        let mut result = Self::new();
+        match result.push_str(text) == text.len() {
+            true => Some(result),
+            false => None,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns buffer's capacity.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    ///Returns number of written bytes.
+    pub const fn len(&self) -> usize {
+        self.cursor as usize
+    }
+
+    #[inline(always)]
+    ///Returns whether buffer is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.cursor == 0
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes that can still be written.
+    pub const fn remaining(&self) -> usize {
+        N - self.cursor as usize
+    }
+
+    #[inline(always)]
+    ///Resets buffer to empty state.
+    pub fn clear(&mut self) {
+        self.cursor = 0;
+    }
+
+    #[inline]
+    ///Shortens buffer to `len` bytes, doing nothing if `len` is greater than current length.
+    ///
+    ///# Panics
+    ///
+    ///If `len` is not on char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            assert!(self.as_str().is_char_boundary(len), "Truncation must be on char boundary");
+            self.cursor = len as u8;
+        }
+    }
+
+    #[inline(always)]
+    ///Returns written bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let written = &self.inner[..self.len()];
+        #[cfg(not(feature = "safe-buffer"))]
+        //Written part of storage is always initialized.
+        unsafe {
+            slice::from_raw_parts(written.as_ptr().cast::<u8>(), written.len())
+        }
+        #[cfg(feature = "safe-buffer")]
+        written
+    }
+
+    #[inline(always)]
+    ///Returns written content as string.
+    pub fn as_str(&self) -> &str {
+        #[cfg(not(feature = "safe-buffer"))]
+        //Only whole UTF-8 sequences are ever written.
+        unsafe {
+            str::from_utf8_unchecked(self.as_bytes())
+        }
+        #[cfg(feature = "safe-buffer")]
+        str::from_utf8(self.as_bytes()).expect("Only whole UTF-8 sequences are written")
+    }
+
+    ///Appends `text`, truncating it at char boundary if it doesn't fit.
+    ///
+    ///Returns number of written bytes.
+    pub fn push_str(&mut self, text: &str) -> usize {
+        let mut size = core::cmp::min(self.remaining(), text.len());
+        while !text.is_char_boundary(size) {
+            size -= 1;
+        }
+
+        let len = self.len();
+        for (dest, byte) in self.inner[len..len + size].iter_mut().zip(text.as_bytes()) {
+            *dest = self::byte(*byte);
+        }
+        self.cursor += size as u8;
+        size
+    }
+
+    #[inline]
+    ///Appends `text` if it fits as whole, leaving buffer unchanged otherwise.
+    ///
+    ///Returns whether `text` is written.
+    pub fn try_push_str(&mut self, text: &str) -> bool {
+        match text.len() <= self.remaining() {
+            true => {
+                self.push_str(text);
+                true
+            },
+            false => false,
+        }
+    }
+}
+
+impl<const N: usize> Default for StrBuf<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::ops::Deref for StrBuf<N> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for StrBuf<N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for StrBuf<N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Write for StrBuf<N> {
+    #[inline]
+    ///Writes nothing if `text` doesn't fit, reporting overflow as error.
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        match self.try_push_str(text) {
+            true => Ok(()),
+            false => Err(fmt::Error),
+        }
+    }
+}
+
+impl<const N: usize> PartialEq for StrBuf<N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> Eq for StrBuf<N> {}
+
+impl<const N: usize> PartialEq<str> for StrBuf<N> {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for StrBuf<N> {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> core::hash::Hash for StrBuf<N> {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const N: usize> fmt::Debug for StrBuf<N> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), fmt)
+    }
+}
+
+impl<const N: usize> fmt::Display for StrBuf<N> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), fmt)
+    }
+}
+
+///String buffer, capable of holding up to `N` bytes, padded with zeroes instead of storing its length.
+///
+///It takes exactly `N` bytes, but content must not contain `\0`, which terminates it.
+//Invariant: bytes before the first zero form valid UTF-8, while all bytes after it are zero.
+#[derive(Clone, Copy)]
+pub(crate) struct PaddedStrBuf<const N: usize> {
+    inner: [u8; N],
+}
+
+impl<const N: usize> PaddedStrBuf<N> {
+    #[inline]
+    ///Creates buffer from zero padded storage.
+    ///
+    ///Bytes before the first zero must be valid UTF-8 and all bytes after it must be zero.
+    pub(crate) const fn from_storage(inner: [u8; N]) -> Self {
+        Self {
+            inner,
+        }
+    }
+
+    #[inline]
+    ///Returns number of written bytes, i.e. position of the first zero.
+    pub(crate) const fn len(&self) -> usize {
+        let mut len = 0;
+        while len < N && self.inner[len] != 0 {
+            len += 1;
+        }
+        len
+    }
+
+    #[inline(always)]
+    ///Returns written bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.inner[..self.len()]
+    }
+
+    #[inline(always)]
+    ///Returns written content as string.
+    pub(crate) fn as_str(&self) -> &str {
+        #[cfg(not(feature = "safe-buffer"))]
+        //Only whole UTF-8 sequences are ever written, and zero is always on char boundary.
+        unsafe {
+            str::from_utf8_unchecked(self.as_bytes())
+        }
+        #[cfg(feature = "safe-buffer")]
+        str::from_utf8(self.as_bytes()).expect("Only whole UTF-8 sequences are written")
+    }
+
+    #[inline]
+    ///Shortens buffer to `len` bytes, zeroing the rest.
+    ///
+    ///# Panics
+    ///
+    ///If `len` is not on char boundary.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        assert!(self.as_str().is_char_boundary(len), "Truncation must be on char boundary");
+        for byte in self.inner[len..].iter_mut() {
+            *byte = 0;
+        }
+    }
+
+    ///Appends `text`, truncating it at char boundary if it doesn't fit.
+    ///
+    ///Content after `\0` in `text` is not readable.
+    ///Returns number of written bytes.
+    pub(crate) fn push_str(&mut self, text: &str) -> usize {
+        let len = self.len();
+        let mut size = core::cmp::min(N - len, text.len());
+        while !text.is_char_boundary(size) {
+            size -= 1;
+        }
+
+        self.inner[len..len + size].copy_from_slice(&text.as_bytes()[..size]);
+        size
+    }
+}
+
+impl<const N: usize> PartialEq for PaddedStrBuf<N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<const N: usize> Eq for PaddedStrBuf<N> {}
+
+impl<const N: usize> core::hash::Hash for PaddedStrBuf<N> {
+    #[inline(always)]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
@@ -0,0 +1,479 @@
+//! Caches of computed tags.
+
+use core::hash::{Hash, Hasher};
+
+use crate::EntityTag;
+
+const NONE: usize = usize::MAX;
+//Number of index buckets per slot, so that index is never more than half full.
+const BUCKETS: usize = 2;
+
+struct Slot<K> {
+    key: K,
+    tag: EntityTag,
+    prev: usize,
+    next: usize,
+}
+
+#[inline(always)]
+fn home<K: Hash>(key: &K, buckets_len: usize) -> usize {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % buckets_len as u64) as usize
+}
+
+/// Fixed capacity LRU cache, mapping resource keys to their tags.
+///
+/// Keys are looked up through open addressing index over XXH3 hash of key, which has twice as many
+/// buckets as cache capacity, hence lookup, updating recency and eviction are expected to be constant time.
+/// Requires no allocation.
+pub struct EtagCache<K, const N: usize> {
+    //Occupied slots are always `slots[..len]`
+    slots: [Option<Slot<K>>; N],
+    //Buckets with index of slot, or `NONE` if empty
+    index: [[usize; BUCKETS]; N],
+    //Most recently used
+    head: usize,
+    //Least recently used
+    tail: usize,
+    len: usize,
+}
+
+impl<K: Eq + Hash, const N: usize> EtagCache<K, N> {
+    const EMPTY: Option<Slot<K>> = None;
+
+    /// Creates new empty cache.
+    pub const fn new() -> Self {
+        Self {
+            slots: [Self::EMPTY; N],
+            index: [[NONE; BUCKETS]; N],
+            head: NONE,
+            tail: NONE,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    /// Returns number of cached tags.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    /// Returns whether cache is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Returns maximum number of cached tags.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    fn bucket(&self, pos: usize) -> usize {
+        self.index[pos / BUCKETS][pos % BUCKETS]
+    }
+
+    #[inline(always)]
+    fn set_bucket(&mut self, pos: usize, idx: usize) {
+        self.index[pos / BUCKETS][pos % BUCKETS] = idx;
+    }
+
+    fn key(&self, idx: usize) -> &K {
+        match self.slots[idx].as_ref() {
+            Some(slot) => &slot.key,
+            None => unreachable!(),
+        }
+    }
+
+    ///Returns bucket of the key, or empty bucket to insert it into.
+    ///
+    ///Requires non-zero capacity.
+    fn locate(&self, key: &K) -> Result<usize, usize> {
+        let len = N * BUCKETS;
+        let mut pos = home(key, len);
+        //Index is at most half full, so empty bucket is always reached.
+        loop {
+            match self.bucket(pos) {
+                NONE => return Err(pos),
+                idx => if self.key(idx) == key {
+                    return Ok(pos);
+                },
+            }
+            pos = (pos + 1) % len;
+        }
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        match N {
+            0 => None,
+            _ => self.locate(key).ok().map(|pos| self.bucket(pos)),
+        }
+    }
+
+    ///Empties bucket, shifting following buckets of the probe sequence back, so no tombstones are needed.
+    fn unindex(&mut self, mut hole: usize) {
+        let len = N * BUCKETS;
+        let mut next = (hole + 1) % len;
+        loop {
+            let idx = self.bucket(next);
+            if idx == NONE {
+                break;
+            }
+
+            //Bucket can fill the hole, unless home of its key lies cyclically after the hole
+            let home = home(self.key(idx), len);
+            if (next + len - home) % len >= (next + len - hole) % len {
+                self.set_bucket(hole, idx);
+                hole = next;
+            }
+            next = (next + 1) % len;
+        }
+
+        self.set_bucket(hole, NONE);
+    }
+
+    fn slot_mut(&mut self, idx: usize) -> &mut Slot<K> {
+        match self.slots[idx].as_mut() {
+            Some(slot) => slot,
+            None => unreachable!(),
+        }
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = {
+            let slot = self.slot_mut(idx);
+            (slot.prev, slot.next)
+        };
+
+        match prev {
+            NONE => self.head = next,
+            prev => self.slot_mut(prev).next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            next => self.slot_mut(next).prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, idx: usize) {
+        let head = self.head;
+        {
+            let slot = self.slot_mut(idx);
+            slot.prev = NONE;
+            slot.next = head;
+        }
+
+        match head {
+            NONE => self.tail = idx,
+            head => self.slot_mut(head).prev = idx,
+        }
+        self.head = idx;
+    }
+
+    ///Removes slot, found in bucket `pos`, moving the last slot into its place.
+    fn take(&mut self, pos: usize) -> Option<Slot<K>> {
+        let idx = self.bucket(pos);
+        self.unlink(idx);
+        self.unindex(pos);
+
+        self.len -= 1;
+        let last = self.len;
+        let slot = self.slots[idx].take();
+        if idx != last {
+            let pos = match self.locate(self.key(last)) {
+                Ok(pos) => pos,
+                Err(_) => unreachable!(),
+            };
+            self.set_bucket(pos, idx);
+            self.slots.swap(idx, last);
+
+            let (prev, next) = {
+                let slot = self.slot_mut(idx);
+                (slot.prev, slot.next)
+            };
+            match prev {
+                NONE => self.head = idx,
+                prev => self.slot_mut(prev).next = idx,
+            }
+            match next {
+                NONE => self.tail = idx,
+                next => self.slot_mut(next).prev = idx,
+            }
+        }
+
+        slot
+    }
+
+    /// Returns tag of the resource without affecting its recency.
+    pub fn peek(&self, key: &K) -> Option<&EntityTag> {
+        match self.find(key) {
+            Some(idx) => self.slots[idx].as_ref().map(|slot| &slot.tag),
+            None => None,
+        }
+    }
+
+    /// Returns tag of the resource, marking it as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&EntityTag> {
+        match self.find(key) {
+            Some(idx) => {
+                if self.head != idx {
+                    self.unlink(idx);
+                    self.push_front(idx);
+                }
+                self.slots[idx].as_ref().map(|slot| &slot.tag)
+            },
+            None => None,
+        }
+    }
+
+    /// Inserts tag of the resource, marking it as most recently used.
+    ///
+    /// Returns previous tag of the resource or, if cache is full, the least recently used entry,
+    /// which got evicted.
+    pub fn insert(&mut self, key: K, tag: EntityTag) -> Option<(K, EntityTag)> {
+        if N == 0 {
+            return Some((key, tag));
+        }
+
+        if let Ok(pos) = self.locate(&key) {
+            let idx = self.bucket(pos);
+            self.unlink(idx);
+            let slot = self.slots[idx].replace(Slot {
+                key,
+                tag,
+                prev: NONE,
+                next: NONE,
+            });
+            self.push_front(idx);
+            return slot.map(|slot| (slot.key, slot.tag));
+        }
+
+        let evicted = match self.len == N {
+            true => {
+                let pos = match self.locate(self.key(self.tail)) {
+                    Ok(pos) => pos,
+                    Err(_) => unreachable!(),
+                };
+                self.take(pos).map(|slot| (slot.key, slot.tag))
+            },
+            false => None,
+        };
+
+        //Eviction shifts buckets, so empty one is located after it.
+        let pos = match self.locate(&key) {
+            Ok(_) => unreachable!(),
+            Err(pos) => pos,
+        };
+        let idx = self.len;
+        self.len += 1;
+        self.slots[idx] = Some(Slot {
+            key,
+            tag,
+            prev: NONE,
+            next: NONE,
+        });
+        self.set_bucket(pos, idx);
+        self.push_front(idx);
+        evicted
+    }
+
+    /// Removes tag of the resource, returning it.
+    pub fn remove(&mut self, key: &K) -> Option<EntityTag> {
+        match N {
+            0 => None,
+            _ => match self.locate(key) {
+                Ok(pos) => self.take(pos).map(|slot| slot.tag),
+                Err(_) => None,
+            },
+        }
+    }
+
+    /// Removes all tags.
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = None;
+        }
+        for buckets in self.index.iter_mut() {
+            *buckets = [NONE; BUCKETS];
+        }
+        self.head = NONE;
+        self.tail = NONE;
+        self.len = 0;
+    }
+}
+
+impl<K: Eq + Hash, const N: usize> Default for EtagCache<K, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+mod validator {
+    use std::collections::{HashMap, VecDeque};
+    use std::hash::Hash;
+    use std::sync::{Mutex, MutexGuard};
+    use std::time::{Duration, Instant};
+
+    use crate::EntityTag;
+
+    struct Entry {
+        tag: EntityTag,
+        inserted: Instant,
+        //Stamp of the latest access
+        accessed: u64,
+    }
+
+    struct Inner<K> {
+        entries: HashMap<K, Entry>,
+        //Access order, entries can be outdated
+        order: VecDeque<(K, u64)>,
+        clock: u64,
+    }
+
+    impl<K: Hash + Eq + Clone> Inner<K> {
+        fn touch(&mut self, key: &K, capacity: usize) {
+            self.clock += 1;
+            if let Some(entry) = self.entries.get_mut(key) {
+                entry.accessed = self.clock;
+            }
+            self.order.push_back((key.clone(), self.clock));
+
+            //Drop outdated entries, if repeated accesses accumulate behind current ones.
+            if self.order.len() > capacity.saturating_mul(2) {
+                let entries = &self.entries;
+                self.order.retain(|(key, accessed)| match entries.get(key) {
+                    Some(entry) => entry.accessed == *accessed,
+                    None => false,
+                });
+            }
+        }
+
+        fn evict(&mut self, capacity: usize, expired: Option<Instant>) {
+            while let Some((key, accessed)) = self.order.front() {
+                let (is_current, is_expired) = match self.entries.get(key) {
+                    Some(entry) => (entry.accessed == *accessed, matches!(expired, Some(expired) if entry.inserted <= expired)),
+                    None => (false, false),
+                };
+
+                if !is_current {
+                    self.order.pop_front();
+                } else if self.entries.len() >= capacity || is_expired {
+                    self.entries.remove(key);
+                    self.order.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Thread-safe cache, mapping keys to tags with limited capacity and time to live.
+    ///
+    /// When cache is full, the least recently used tag is evicted.
+    pub struct ValidatorCache<K> {
+        capacity: usize,
+        ttl: Duration,
+        inner: Mutex<Inner<K>>,
+    }
+
+    impl<K: Hash + Eq + Clone> ValidatorCache<K> {
+        /// Creates new cache, holding at most `capacity` tags for `ttl` duration.
+        pub fn new(capacity: usize, ttl: Duration) -> Self {
+            Self {
+                capacity,
+                ttl,
+                inner: Mutex::new(Inner {
+                    entries: HashMap::with_capacity(capacity),
+                    order: VecDeque::with_capacity(capacity),
+                    clock: 0,
+                }),
+            }
+        }
+
+        fn lock(&self) -> MutexGuard<'_, Inner<K>> {
+            match self.inner.lock() {
+                Ok(inner) => inner,
+                Err(error) => error.into_inner(),
+            }
+        }
+
+        #[inline]
+        /// Returns number of cached tags, including expired tags that are not yet evicted.
+        pub fn len(&self) -> usize {
+            self.lock().entries.len()
+        }
+
+        #[inline]
+        /// Returns whether cache is empty.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns tag, unless it is expired, marking it as recently used.
+        pub fn get(&self, key: &K) -> Option<EntityTag> {
+            let mut inner = self.lock();
+            let tag = match inner.entries.get(key) {
+                Some(entry) if entry.inserted.elapsed() < self.ttl => entry.tag.clone(),
+                _ => return None,
+            };
+            inner.touch(key, self.capacity);
+            Some(tag)
+        }
+
+        /// Inserts tag, replacing previous one.
+        pub fn insert(&self, key: K, tag: EntityTag) {
+            if self.capacity == 0 {
+                return;
+            }
+
+            let now = Instant::now();
+            let mut inner = self.lock();
+            inner.entries.remove(&key);
+            //Nothing can be expired yet, if `ttl` reaches before the earliest representable instant.
+            inner.evict(self.capacity, now.checked_sub(self.ttl));
+            inner.entries.insert(key.clone(), Entry {
+                tag,
+                inserted: now,
+                accessed: 0,
+            });
+            inner.touch(&key, self.capacity);
+        }
+
+        /// Removes tag, returning it, unless it is expired.
+        pub fn remove(&self, key: &K) -> Option<EntityTag> {
+            match self.lock().entries.remove(key) {
+                Some(entry) if entry.inserted.elapsed() < self.ttl => Some(entry.tag),
+                _ => None,
+            }
+        }
+
+        /// Removes all tags.
+        pub fn clear(&self) {
+            let mut inner = self.lock();
+            inner.entries.clear();
+            inner.order.clear();
+        }
+
+        /// Returns tag, computing and inserting it if it is missing or expired.
+        ///
+        /// Lock is not held during computation, so concurrent callers may compute tag for the same key.
+        pub fn get_or_compute<F: FnOnce() -> EntityTag>(&self, key: K, compute: F) -> EntityTag {
+            match self.get(&key) {
+                Some(tag) => tag,
+                None => {
+                    let tag = compute();
+                    self.insert(key, tag.clone());
+                    tag
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use validator::ValidatorCache;
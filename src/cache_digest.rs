@@ -0,0 +1,213 @@
+//! Cache digests, as defined in [draft-ietf-httpbis-cache-digest](https://datatracker.ietf.org/doc/html/draft-ietf-httpbis-cache-digest-05).
+//!
+//! Digest is Golomb-coded set of SHA-256 hashes over URLs of cached responses, combined with their tags.
+//! Client sends digest of its cache, so server can skip pushing or regenerating responses, that are already held.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::cache_digest::CacheDigest;
+//!
+//! let tag = EntityTag::strong_unchecked("v1");
+//! let digest = CacheDigest::new([("https://example.com/app.js", Some(&tag))], 7);
+//!
+//! let received = CacheDigest::parse(&digest.encode()).unwrap();
+//! assert!(received.contains("https://example.com/app.js", Some(&tag)));
+//! assert!(!received.contains("https://example.com/app.js", Some(&EntityTag::strong_unchecked("v2"))));
+//! ```
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+use crate::{EntityTag, ParseError};
+
+const FIELD_BITS: u32 = 5;
+const MAX_LOG2: u8 = (1 << FIELD_BITS) - 1;
+
+///Returns first `bits` of SHA-256 digest of the `url` and optional `etag`.
+fn hash_key(url: &str, etag: Option<&EntityTag>, bits: u32) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    if let Some(etag) = etag {
+        hasher.update(etag.as_header_str().as_bytes());
+    }
+
+    let digest = hasher.finalize();
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(prefix) >> (64 - bits)
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.len / 8 == self.bytes.len() {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    fn push_bits(&mut self, value: u64, bits: u32) {
+        for idx in (0..bits).rev() {
+            self.push(value >> idx & 1 == 1);
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn next(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.pos / 8)?;
+        let bit = byte & (0x80 >> (self.pos % 8)) != 0;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    fn next_bits(&mut self, bits: u32) -> Option<u64> {
+        let mut result = 0;
+        for _ in 0..bits {
+            result = result << 1 | self.next()? as u64;
+        }
+        Some(result)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Set of cached responses.
+pub struct CacheDigest {
+    n_log2: u8,
+    p_log2: u8,
+    hashes: Vec<u64>,
+}
+
+impl CacheDigest {
+    /// Creates digest of cached responses, identified by URL and optionally their tag, with
+    /// probability of false positive being `1/2^p_log2`.
+    ///
+    /// `p_log2` is limited to range `1..=31`.
+    pub fn new<'a, I: IntoIterator<Item = (&'a str, Option<&'a EntityTag>)>>(entries: I, p_log2: u8) -> Self {
+        let p_log2 = p_log2.clamp(1, MAX_LOG2);
+        let entries = entries.into_iter();
+
+        //Hashes are truncated to `N * P` bits, where number of entries `N` is rounded up to power of two.
+        let mut hashes: Vec<u64> = entries.map(|(url, etag)| hash_key(url, etag, 64)).collect();
+        let n_log2 = core::cmp::min(hashes.len().next_power_of_two().trailing_zeros(), MAX_LOG2 as u32) as u8;
+        let shift = 64 - (n_log2 + p_log2) as u32;
+        for hash in hashes.iter_mut() {
+            *hash >>= shift;
+        }
+        hashes.sort_unstable();
+        hashes.dedup();
+
+        Self {
+            n_log2,
+            p_log2,
+            hashes,
+        }
+    }
+
+    /// Parses encoded digest, as sent by client.
+    ///
+    /// Returns `ParseError::InvalidFormat` if digest is truncated, or its parameters are invalid.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = BitReader {
+            bytes,
+            pos: 0,
+        };
+        let (n_log2, p_log2) = match (reader.next_bits(FIELD_BITS), reader.next_bits(FIELD_BITS)) {
+            (Some(n_log2), Some(p_log2)) if p_log2 > 0 => (n_log2 as u8, p_log2 as u8),
+            _ => return Err(ParseError::InvalidFormat),
+        };
+
+        let mut hashes = Vec::new();
+        let mut last = None;
+        //Trailing padding consists of less than 8 zero bits, hence it is never followed by complete value.
+        'values: loop {
+            let mut quotient = 0u64;
+            loop {
+                match reader.next() {
+                    Some(true) => break,
+                    Some(false) => quotient += 1,
+                    None => break 'values,
+                }
+            }
+            let remainder = match reader.next_bits(p_log2 as u32) {
+                Some(remainder) => remainder,
+                None => break,
+            };
+
+            let delta = quotient.checked_shl(p_log2 as u32).filter(|delta| delta >> p_log2 == quotient).ok_or(ParseError::InvalidFormat)?;
+            let value = match last {
+                Some(last) => delta.checked_add(remainder).and_then(|delta| delta.checked_add(1)).and_then(|delta| delta.checked_add(last)),
+                None => delta.checked_add(remainder),
+            };
+            let value = value.ok_or(ParseError::InvalidFormat)?;
+            hashes.push(value);
+            last = Some(value);
+        }
+
+        Ok(Self {
+            n_log2,
+            p_log2,
+            hashes,
+        })
+    }
+
+    /// Encodes digest to send it to server.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = BitWriter {
+            bytes: Vec::new(),
+            len: 0,
+        };
+        writer.push_bits(self.n_log2 as u64, FIELD_BITS);
+        writer.push_bits(self.p_log2 as u64, FIELD_BITS);
+
+        let mut last = None;
+        for hash in self.hashes.iter() {
+            let delta = match last {
+                Some(last) => hash - last - 1,
+                None => *hash,
+            };
+            for _ in 0..delta >> self.p_log2 {
+                writer.push(false);
+            }
+            writer.push(true);
+            writer.push_bits(delta & ((1 << self.p_log2) - 1), self.p_log2 as u32);
+            last = Some(*hash);
+        }
+
+        writer.bytes
+    }
+
+    #[inline]
+    /// Returns number of distinct hashes in the digest.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    #[inline]
+    /// Returns whether digest is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    #[inline]
+    /// Returns whether response, identified by `url` and optionally its tag, might be cached.
+    ///
+    /// Tag must be provided if, and only if, digest was created with tags.
+    pub fn contains(&self, url: &str, etag: Option<&EntityTag>) -> bool {
+        let bits = (self.n_log2 + self.p_log2) as u32;
+        self.hashes.binary_search(&hash_key(url, etag, bits)).is_ok()
+    }
+}
@@ -0,0 +1,134 @@
+//! Content-addressable storage of blobs, keyed by their strong tags.
+//!
+//! Blobs are stored as files, named after opaque tag `<len>-<hash>`, so the same content is stored once,
+//! and its integrity is verified on retrieval.
+//!
+//! Hash is always 128-bit `xxh3`, regardless of `hash-*` features, as 64-bit hashes are too prone to collisions
+//! for addressing content. Hence tags are the same as produced by `EntityTag::from_data` only with default algorithm.
+//!
+//! ```rust
+//! use etag::cas::BlobStore;
+//!
+//! let store = BlobStore::open(std::env::temp_dir().join("etag-cas-doc")).unwrap();
+//! let tag = store.put(b"{\"id\":1}").unwrap();
+//! assert!(store.contains(&tag));
+//! assert_eq!(store.get(&tag).unwrap().as_deref(), Some(&b"{\"id\":1}"[..]));
+//! # store.remove(&tag).unwrap();
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::vec::Vec;
+
+use crate::EntityTag;
+
+///Distinguishes temporary files of concurrent writers within the process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[inline]
+///Creates tag of the blob, using `xxh3` independently of `hash-*` features.
+fn key(data: &[u8]) -> EntityTag {
+    EntityTag::from_len_hash(data.len() as u64, xxhash_rust::xxh3::xxh3_128(data))
+}
+
+#[derive(Clone, Debug)]
+/// Directory, storing blobs under their strong tags.
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    /// Opens store in directory `root`, creating it if necessary.
+    pub fn open<P: Into<PathBuf>>(root: P) -> io::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+        })
+    }
+
+    #[inline]
+    /// Returns directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns path of the blob with tag `tag`.
+    ///
+    /// Returns `None` if tag is weak or is not in format `<len>-<hash>`.
+    pub fn path(&self, tag: &EntityTag) -> Option<PathBuf> {
+        match !tag.is_weak() && tag.content_components().is_some() {
+            true => Some(self.root.join(tag.tag())),
+            false => None,
+        }
+    }
+
+    /// Stores `data`, returning its strong tag.
+    ///
+    /// Content, which is already stored, is not written again.
+    /// Blob is written into temporary file first, and then renamed, so it never appears partially written.
+    pub fn put(&self, data: &[u8]) -> io::Result<EntityTag> {
+        let tag = key(data);
+        let path = self.root.join(tag.tag());
+        if path.is_file() {
+            return Ok(tag);
+        }
+
+        let temp = self.root.join(std::format!(".{}.{}-{}.tmp", tag.tag(), std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+        let result = fs::File::create(&temp).and_then(|mut file| {
+            file.write_all(data)?;
+            file.sync_all()
+        }).and_then(|_| fs::rename(&temp, &path));
+
+        match result {
+            Ok(()) => Ok(tag),
+            Err(error) => {
+                let _ = fs::remove_file(&temp);
+                Err(error)
+            }
+        }
+    }
+
+    #[inline]
+    /// Returns whether blob with tag `tag` is stored, without verifying its content.
+    pub fn contains(&self, tag: &EntityTag) -> bool {
+        self.path(tag).is_some_and(|path| path.is_file())
+    }
+
+    /// Reads blob with tag `tag`, returning `None` if it is not stored.
+    ///
+    /// Content is verified against the tag, failing with `io::ErrorKind::InvalidData` if it is corrupted.
+    pub fn get(&self, tag: &EntityTag) -> io::Result<Option<Vec<u8>>> {
+        let path = match self.path(tag) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        match key(&data).strong_eq(tag) {
+            true => Ok(Some(data)),
+            false => Err(io::Error::new(io::ErrorKind::InvalidData, "Blob content doesn't match its tag")),
+        }
+    }
+
+    /// Removes blob with tag `tag`, returning whether it was stored.
+    pub fn remove(&self, tag: &EntityTag) -> io::Result<bool> {
+        let path = match self.path(tag) {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+
+        match fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Delta encoding, as defined in [RFC3229](https://www.rfc-editor.org/rfc/rfc3229).
+//!
+//! Client lists tags of instances it holds in `If-None-Match`, and acceptable instance manipulations
+//! (e.g. `vcdiff`) in `A-IM`. Server may respond with `226 IM Used`, carrying difference between
+//! one of these instances, named by `Delta-Base`, and current one.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::delta::{negotiate, DeltaDecision};
+//!
+//! let current = EntityTag::strong_unchecked("v2");
+//! let decision = negotiate(&current, Some("\"v1\""), Some("vcdiff, gzip"), &["vcdiff"], |base| base.tag() == "v1");
+//! match decision {
+//!     DeltaDecision::Delta(response) => {
+//!         assert_eq!(response.status(), 226);
+//!         assert_eq!(response.headers(), [("IM", "vcdiff"), ("Delta-Base", "\"v1\""), ("ETag", "\"v2\"")]);
+//!     },
+//!     _ => unreachable!(),
+//! }
+//! ```
+
+use crate::{not_modified, parse_list, EntityTag};
+
+/// Name of `A-IM` header, listing instance manipulations accepted by client.
+pub const A_IM: &str = "A-IM";
+/// Name of `IM` header, naming instance manipulations applied to response.
+pub const IM: &str = "IM";
+/// Name of `Delta-Base` header, naming tag of instance, used as base of delta.
+pub const DELTA_BASE: &str = "Delta-Base";
+/// Status code `226 IM Used`.
+pub const IM_USED: u16 = 226;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Response with delta, applied to base instance.
+pub struct DeltaResponse<'a> {
+    im: &'a str,
+    base: EntityTag,
+    current: &'a EntityTag,
+}
+
+impl<'a> DeltaResponse<'a> {
+    #[inline]
+    /// Returns status code of the response, i.e. `226`.
+    pub const fn status(&self) -> u16 {
+        IM_USED
+    }
+
+    #[inline]
+    /// Returns instance manipulation, that should be applied to produce delta.
+    pub const fn im(&self) -> &'a str {
+        self.im
+    }
+
+    #[inline]
+    /// Returns tag of instance, that should be used as base of delta.
+    pub const fn base(&self) -> &EntityTag {
+        &self.base
+    }
+
+    #[inline]
+    /// Returns headers of the response: `IM`, `Delta-Base` and `ETag` of current instance.
+    pub fn headers(&self) -> [(&'static str, &str); 3] {
+        [(IM, self.im), (DELTA_BASE, self.base.as_header_str()), (crate::embedded::ETAG, self.current.as_header_str())]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+/// Outcome of delta negotiation.
+pub enum DeltaDecision<'a> {
+    /// Client holds current instance, and should be responded with `304 Not Modified`.
+    NotModified,
+    /// Client should be responded with delta.
+    Delta(DeltaResponse<'a>),
+    /// Client should be responded with full instance.
+    Full,
+}
+
+///Returns first instance manipulation of `a_im`, that is `supported` and not rejected with `q=0`.
+fn select_im<'a>(a_im: &str, supported: &[&'a str]) -> Option<&'a str> {
+    for member in a_im.split(',') {
+        let mut params = member.split(';').map(|param| param.trim_matches(|ch| ch == ' ' || ch == '\t'));
+        let name = params.next().unwrap_or_default();
+        let rejected = params.filter_map(|param| param.split_once('='))
+                             .any(|(key, value)| key.eq_ignore_ascii_case("q") && value.parse::<f32>() == Ok(0.0));
+        if rejected {
+            continue;
+        }
+
+        if let Some(im) = supported.iter().find(|im| im.eq_ignore_ascii_case(name)) {
+            return Some(im);
+        }
+    }
+
+    None
+}
+
+/// Decides how to respond to `GET` request for instance identified by `current`.
+///
+/// - `If-None-Match` matching `current` results in `DeltaDecision::NotModified`.
+/// - Otherwise delta is used if `A-IM` lists any of `supported` manipulations, and `If-None-Match`
+///   lists strong tag of instance, for which `has_base` returns `true`. First acceptable manipulation and
+///   instance, in order of client's lists, are used.
+///
+/// Weak tags are never used as base, as delta requires exact content of base instance.
+pub fn negotiate<'a, F: FnMut(&EntityTag) -> bool>(current: &'a EntityTag, if_none_match: Option<&str>, a_im: Option<&str>, supported: &[&'a str], mut has_base: F) -> DeltaDecision<'a> {
+    if not_modified(current, if_none_match) {
+        return DeltaDecision::NotModified;
+    }
+
+    let (if_none_match, im) = match (if_none_match, a_im.and_then(|a_im| select_im(a_im, supported))) {
+        (Some(if_none_match), Some(im)) => (if_none_match, im),
+        _ => return DeltaDecision::Full,
+    };
+
+    let base = parse_list(if_none_match).skip_invalid()
+                                        .find(|base| !base.is_weak() && !base.is_any() && has_base(base));
+    match base {
+        Some(base) => DeltaDecision::Delta(DeltaResponse {
+            im,
+            base,
+            current,
+        }),
+        None => DeltaDecision::Full,
+    }
+}
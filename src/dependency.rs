@@ -0,0 +1,140 @@
+//! Tracking of resources, whose tags are derived from tags of other resources.
+//!
+//! Rendered pages and aggregated API responses change whenever any of their sources change.
+//! Tracker remembers tags of sources, used to produce each derived resource, and determines which
+//! derived resources have to be recomputed once tag of source changes.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::dependency::EtagDependencyTracker;
+//!
+//! let user = EntityTag::strong_unchecked("user-v1");
+//! let posts = EntityTag::strong_unchecked("posts-v7");
+//!
+//! let mut tracker = EtagDependencyTracker::new();
+//! tracker.record("/profile", EntityTag::strong_unchecked("profile-1"), [("user", &user), ("posts", &posts)]);
+//! tracker.record("/feed", EntityTag::strong_unchecked("feed-1"), [("posts", &posts)]);
+//! tracker.record("/index", EntityTag::strong_unchecked("index-1"), [("/feed", &EntityTag::strong_unchecked("feed-1"))]);
+//!
+//! assert!(tracker.invalidated("user", &user).is_empty());
+//! assert_eq!(tracker.invalidated("user", &EntityTag::strong_unchecked("user-v2")), ["/profile"]);
+//! assert_eq!(tracker.invalidated("posts", &EntityTag::strong_unchecked("posts-v8")), ["/feed", "/index", "/profile"]);
+//! ```
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::EntityTag;
+
+#[derive(Clone, Debug)]
+struct Derived {
+    tag: EntityTag,
+    sources: Vec<(String, EntityTag)>,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Graph of resources, whose tags are derived from tags of other resources.
+///
+/// Resources are identified by arbitrary names, such as paths or keys, and derived resource can
+/// be source of another one.
+pub struct EtagDependencyTracker {
+    derived: BTreeMap<String, Derived>,
+    dependents: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl EtagDependencyTracker {
+    #[inline]
+    /// Creates empty tracker.
+    pub const fn new() -> Self {
+        Self {
+            derived: BTreeMap::new(),
+            dependents: BTreeMap::new(),
+        }
+    }
+
+    /// Records that `tag` of `derived` resource is produced from `sources`, with their current tags.
+    ///
+    /// Replaces previous record of `derived`.
+    pub fn record<'a, I: IntoIterator<Item = (&'a str, &'a EntityTag)>>(&mut self, derived: &str, tag: EntityTag, sources: I) {
+        self.remove(derived);
+
+        let sources = sources.into_iter().map(|(name, tag)| (String::from(name), tag.clone())).collect::<Vec<_>>();
+        for (source, _) in sources.iter() {
+            self.dependents.entry(source.clone()).or_default().insert(derived.into());
+        }
+        self.derived.insert(derived.into(), Derived {
+            tag,
+            sources,
+        });
+    }
+
+    /// Removes record of `derived` resource, returning its tag.
+    pub fn remove(&mut self, derived: &str) -> Option<EntityTag> {
+        let removed = self.derived.remove(derived)?;
+        for (source, _) in removed.sources.iter() {
+            if let Some(dependents) = self.dependents.get_mut(source) {
+                dependents.remove(derived);
+                if dependents.is_empty() {
+                    self.dependents.remove(source);
+                }
+            }
+        }
+
+        Some(removed.tag)
+    }
+
+    #[inline]
+    /// Returns recorded tag of `derived` resource.
+    pub fn tag(&self, derived: &str) -> Option<&EntityTag> {
+        self.derived.get(derived).map(|derived| &derived.tag)
+    }
+
+    /// Returns sources of `derived` resource with their tags, as they were recorded.
+    pub fn sources(&self, derived: &str) -> impl Iterator<Item = (&str, &EntityTag)> {
+        self.derived.get(derived).into_iter().flat_map(|derived| derived.sources.iter().map(|(name, tag)| (name.as_str(), tag)))
+    }
+
+    /// Returns resources, which are directly derived from `source`.
+    pub fn dependents(&self, source: &str) -> impl Iterator<Item = &str> {
+        self.dependents.get(source).into_iter().flat_map(|dependents| dependents.iter().map(String::as_str))
+    }
+
+    /// Returns derived resources, which have to be recomputed, if tag of `source` is `tag`, sorted by name.
+    ///
+    /// Resource is stale if it was produced from tag of `source`, which is not identical to `tag`,
+    /// i.e. differs in either opaque tag or weakness.
+    /// Resources, derived from stale resources, are stale as well, regardless of their recorded tags.
+    pub fn invalidated(&self, source: &str, tag: &EntityTag) -> Vec<&str> {
+        let mut stale = BTreeSet::new();
+        let mut pending = Vec::new();
+        for name in self.dependents(source) {
+            let is_stale = self.sources(name).any(|(name, recorded)| name == source && recorded != tag);
+            if is_stale && stale.insert(name) {
+                pending.push(name);
+            }
+        }
+
+        while let Some(name) = pending.pop() {
+            for dependent in self.dependents(name) {
+                if stale.insert(dependent) {
+                    pending.push(dependent);
+                }
+            }
+        }
+
+        stale.into_iter().collect()
+    }
+
+    #[inline]
+    /// Returns number of derived resources.
+    pub fn len(&self) -> usize {
+        self.derived.len()
+    }
+
+    #[inline]
+    /// Returns whether tracker has no derived resources.
+    pub fn is_empty(&self) -> bool {
+        self.derived.is_empty()
+    }
+}
@@ -0,0 +1,131 @@
+//! Conditional `GET` for `no_std` HTTP servers.
+//!
+//! Embedded HTTP stacks, such as `embedded-svc` or `picoserve`, expose request headers by name and
+//! build response from status and list of headers. Implementing `RequestHeaders` for request type
+//! is enough to use `conditional_get`, which decides status and headers of the response.
+//!
+//! Features `embedded-svc` and `picoserve` provide implementations for requests of respective stacks,
+//! as well as conversion of `ConditionalResponse` into their responses.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::embedded::conditional_get;
+//!
+//! const TAG: EntityTag = EntityTag::const_from_data(b"<html></html>");
+//!
+//! let request = [("Host", "device.local"), ("If-None-Match", TAG.as_header_str())];
+//! let response = conditional_get(&request[..], &TAG);
+//! assert!(response.is_not_modified());
+//! assert_eq!(response.status(), 304);
+//! assert_eq!(response.headers(), [("ETag", TAG.as_header_str())]);
+//! ```
+
+use crate::{not_modified, EntityTag};
+
+/// Name of `ETag` header.
+pub const ETAG: &str = "ETag";
+/// Name of `If-None-Match` header.
+pub const IF_NONE_MATCH: &str = "If-None-Match";
+
+/// Access to request headers.
+pub trait RequestHeaders {
+    /// Returns value of header `name`, which is case-insensitive.
+    fn header(&self, name: &str) -> Option<&str>;
+}
+
+/// List of `(name, value)` pairs.
+impl RequestHeaders for [(&str, &str)] {
+    #[inline]
+    fn header(&self, name: &str) -> Option<&str> {
+        self.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| *value)
+    }
+}
+
+impl<T: RequestHeaders + ?Sized> RequestHeaders for &T {
+    #[inline]
+    fn header(&self, name: &str) -> Option<&str> {
+        T::header(self, name)
+    }
+}
+
+#[cfg(feature = "embedded-svc")]
+impl<C: embedded_svc::http::server::Connection> RequestHeaders for embedded_svc::http::server::Request<C> {
+    #[inline]
+    fn header(&self, name: &str) -> Option<&str> {
+        embedded_svc::http::server::Request::header(self, name)
+    }
+}
+
+#[cfg(feature = "picoserve")]
+/// Header with invalid UTF-8 value is treated as absent.
+impl RequestHeaders for picoserve::request::Headers<'_> {
+    #[inline]
+    fn header(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|value| core::str::from_utf8(value.as_raw()).ok())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Response to conditional `GET` or `HEAD` request.
+pub struct ConditionalResponse<'a> {
+    not_modified: bool,
+    etag: &'a str,
+}
+
+impl<'a> ConditionalResponse<'a> {
+    #[inline]
+    /// Returns whether response is `304 Not Modified`, and must be sent without body.
+    pub const fn is_not_modified(&self) -> bool {
+        self.not_modified
+    }
+
+    #[inline]
+    /// Returns status code of the response: `304` or `200`.
+    pub const fn status(&self) -> u16 {
+        match self.not_modified {
+            true => 304,
+            false => 200,
+        }
+    }
+
+    #[inline]
+    /// Returns headers of the response, i.e. `ETag`.
+    pub const fn headers(&self) -> [(&'static str, &'a str); 1] {
+        [(ETAG, self.etag)]
+    }
+
+    #[cfg(feature = "embedded-svc")]
+    /// Initiates response to `embedded-svc` request with status and headers of this response.
+    ///
+    /// Body must be written only if response is not `304 Not Modified`.
+    pub fn into_svc_response<C: embedded_svc::http::server::Connection>(&self, request: embedded_svc::http::server::Request<C>) -> Result<embedded_svc::http::server::Response<C>, C::Error> {
+        let message = match self.not_modified {
+            true => "Not Modified",
+            false => "OK",
+        };
+        request.into_response(self.status(), Some(message), &self.headers())
+    }
+
+    #[cfg(feature = "picoserve")]
+    /// Creates `picoserve` response with status and headers of this response.
+    ///
+    /// `content` is sent only if response is not `304 Not Modified`.
+    pub fn into_picoserve_response<C: picoserve::response::Content + 'a>(self, content: C) -> impl picoserve::response::IntoResponse + 'a {
+        use picoserve::response::{Response, StatusCode};
+
+        match self.not_modified {
+            true => Err(Response::empty(StatusCode::NOT_MODIFIED).with_header(ETAG, self.etag)),
+            false => Ok(Response::new(StatusCode::OK, content).with_header(ETAG, self.etag)),
+        }
+    }
+}
+
+/// Evaluates `If-None-Match` of `GET` or `HEAD` request against `current` tag of the resource.
+///
+/// Both outcomes carry `ETag` header, as `304` response must include it.
+pub fn conditional_get<'a, R: RequestHeaders + ?Sized>(request: &R, current: &'a EntityTag) -> ConditionalResponse<'a> {
+    ConditionalResponse {
+        not_modified: not_modified(current, request.header(IF_NONE_MATCH)),
+        etag: current.as_header_str(),
+    }
+}
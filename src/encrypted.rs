@@ -0,0 +1,100 @@
+//! Tags carrying encrypted payload, such as resource id and its version.
+//!
+//! Payload is encrypted with AES-256-GCM-SIV, using fixed nonce, and encoded as `EntityTag::from_binary_tag` does.
+//! Deterministic encryption is required for tags to stay the same while payload is not changed,
+//! which reveals only whether two payloads are equal, without revealing payloads themselves.
+//!
+//! ```rust
+//! use etag::encrypted::TagCipher;
+//!
+//! let cipher = TagCipher::new(&[7; 32]);
+//! let tag = cipher.seal_version(42, 3).unwrap();
+//! assert_eq!(cipher.open_version(&tag), Ok((42, 3)));
+//! assert_eq!(tag, cipher.seal_version(42, 3).unwrap());
+//! assert!(TagCipher::new(&[8; 32]).open_version(&tag).is_err());
+//! ```
+
+use aes_gcm_siv::{AeadInPlace, Aes256GcmSiv, KeyInit, Nonce, Tag};
+
+use crate::{EntityTag, ParseError};
+
+const TAG_LEN: usize = 16;
+const VERSION_LEN: usize = 16;
+
+/// Encrypts payloads into strong tags and decrypts them back.
+pub struct TagCipher {
+    cipher: Aes256GcmSiv,
+}
+
+impl TagCipher {
+    /// Maximum length of payload.
+    pub const MAX_PAYLOAD_LEN: usize = EntityTag::MAX_BINARY_LEN - TAG_LEN;
+
+    #[inline]
+    /// Creates cipher with 256-bit `key`.
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: Aes256GcmSiv::new(key.into()),
+        }
+    }
+
+    /// Encrypts `payload` into strong tag.
+    ///
+    /// Returns `ParseError::Overflow` if payload is longer than `TagCipher::MAX_PAYLOAD_LEN`.
+    pub fn seal(&self, payload: &[u8]) -> Result<EntityTag, ParseError> {
+        let mut buffer = [0u8; EntityTag::MAX_BINARY_LEN];
+        let len = payload.len() + TAG_LEN;
+        let sealed = buffer.get_mut(..len).ok_or(ParseError::Overflow)?;
+        let (text, tag) = sealed.split_at_mut(payload.len());
+        text.copy_from_slice(payload);
+        let auth = self.cipher.encrypt_in_place_detached(&Nonce::default(), b"", text).map_err(|_| ParseError::Overflow)?;
+        tag.copy_from_slice(&auth);
+
+        EntityTag::from_binary_tag(sealed)
+    }
+
+    /// Decrypts payload of tag, created by `seal`, into `out`, returning its length.
+    ///
+    /// Weakness of the tag is ignored. Returns `ParseError::InvalidFormat` if tag is not created with
+    /// the same key or is modified, and `ParseError::Overflow` if `out` is not large enough.
+    pub fn open(&self, tag: &EntityTag, out: &mut [u8]) -> Result<usize, ParseError> {
+        let mut buffer = [0u8; EntityTag::MAX_BINARY_LEN];
+        let len = tag.binary_tag(&mut buffer)?;
+        let text_len = len.checked_sub(TAG_LEN).ok_or(ParseError::InvalidFormat)?;
+        let (text, auth) = buffer[..len].split_at_mut(text_len);
+        self.cipher.decrypt_in_place_detached(&Nonce::default(), b"", text, Tag::from_slice(auth)).map_err(|_| ParseError::InvalidFormat)?;
+
+        match out.get_mut(..text_len) {
+            Some(out) => {
+                out.copy_from_slice(text);
+                Ok(text_len)
+            },
+            None => Err(ParseError::Overflow),
+        }
+    }
+
+    /// Encrypts resource `id` and its `version` into strong tag.
+    ///
+    /// Both are encoded as little-endian integers.
+    pub fn seal_version(&self, id: u64, version: u64) -> Result<EntityTag, ParseError> {
+        let mut payload = [0u8; VERSION_LEN];
+        payload[..8].copy_from_slice(&id.to_le_bytes());
+        payload[8..].copy_from_slice(&version.to_le_bytes());
+        self.seal(&payload)
+    }
+
+    /// Decrypts resource id and its version from tag, created by `seal_version`.
+    pub fn open_version(&self, tag: &EntityTag) -> Result<(u64, u64), ParseError> {
+        let mut payload = [0u8; Self::MAX_PAYLOAD_LEN];
+        match self.open(tag, &mut payload)? {
+            VERSION_LEN => {
+                let mut id = [0u8; 8];
+                let mut version = [0u8; 8];
+                id.copy_from_slice(&payload[..8]);
+                version.copy_from_slice(&payload[8..VERSION_LEN]);
+                Ok((u64::from_le_bytes(id), u64::from_le_bytes(version)))
+            },
+            _ => Err(ParseError::InvalidFormat),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Validation of opaque tag characters.
+//!
+//! As per [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3) opaque tag consists of
+//! `etagc = %x21 / %x23-7E / obs-text`, where `obs-text` is not supported by this crate.
+//!
+//! Long tags are validated and compared word at a time, which is portable across all targets and doesn't
+//! require any `unsafe` platform intrinsics.
+//! Outside of `const` context, tags are validated 16 bytes at a time, using SSE2 or NEON when target enables it,
+//! unless `safe-buffer` is enabled.
+
+const WORD: usize = core::mem::size_of::<u64>();
+const ONES: u64 = u64::MAX / 0xFF;
+const HIGH_BITS: u64 = ONES * 0x80;
+
+#[inline(always)]
+///Returns whether any byte of the word is zero.
+const fn has_zero(word: u64) -> bool {
+    word.wrapping_sub(ONES) & !word & HIGH_BITS != 0
+}
+
+#[inline(always)]
+///Returns whether any byte of the word is less than `limit`.
+///
+///Requires all bytes of the word to be ASCII.
+const fn has_less(word: u64, limit: u8) -> bool {
+    word.wrapping_sub(ONES * limit as u64) & !word & HIGH_BITS != 0
+}
+
+#[inline(always)]
+///Returns whether any byte of the word is equal to `byte`.
+const fn has_byte(word: u64, byte: u8) -> bool {
+    has_zero(word ^ (ONES * byte as u64))
+}
+
+#[inline(always)]
+pub(crate) const fn is_valid_byte(byte: u8) -> bool {
+    byte == 0x21 || (byte >= 0x23 && byte <= 0x7E)
+}
+
+#[inline]
+///Returns whether all characters of ASCII `tag` are `etagc`.
+pub(crate) const fn is_valid(tag: &str) -> bool {
+    is_valid_bytes(tag.as_bytes())
+}
+
+const fn is_valid_bytes(tag: &[u8]) -> bool {
+    let mut idx = 0;
+
+    while idx + WORD <= tag.len() {
+        let word = u64::from_le_bytes([tag[idx], tag[idx + 1], tag[idx + 2], tag[idx + 3], tag[idx + 4], tag[idx + 5], tag[idx + 6], tag[idx + 7]]);
+        if has_less(word, 0x21) || has_byte(word, b'"') || has_byte(word, 0x7F) {
+            return false;
+        }
+        idx += WORD;
+    }
+
+    while idx < tag.len() {
+        if !is_valid_byte(tag[idx]) {
+            return false;
+        }
+        idx += 1;
+    }
+
+    true
+}
+
+#[cfg(all(not(feature = "safe-buffer"), any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod simd {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    pub(super) const LANES: usize = 16;
+
+    #[inline(always)]
+    ///Returns whether all bytes of `chunk`, which has `LANES` bytes, are `etagc`.
+    pub(super) fn is_valid(chunk: &[u8; LANES]) -> bool {
+        //SSE2 is enabled by target and chunk is read unaligned.
+        unsafe {
+            let bytes = _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>());
+            //Comparison is signed, so non-ASCII bytes are less than `0x21` too
+            let invalid = _mm_or_si128(
+                _mm_cmplt_epi8(bytes, _mm_set1_epi8(0x21)),
+                _mm_or_si128(_mm_cmpeq_epi8(bytes, _mm_set1_epi8(b'"' as i8)), _mm_cmpeq_epi8(bytes, _mm_set1_epi8(0x7F)))
+            );
+            _mm_movemask_epi8(invalid) == 0
+        }
+    }
+}
+
+#[cfg(all(not(feature = "safe-buffer"), target_arch = "aarch64", target_feature = "neon"))]
+mod simd {
+    use core::arch::aarch64::*;
+
+    pub(super) const LANES: usize = 16;
+
+    #[inline(always)]
+    ///Returns whether all bytes of `chunk`, which has `LANES` bytes, are `etagc`.
+    pub(super) fn is_valid(chunk: &[u8; LANES]) -> bool {
+        //NEON is enabled by target and chunk has exactly `LANES` bytes to load.
+        unsafe {
+            let bytes = vld1q_u8(chunk.as_ptr());
+            let invalid = vorrq_u8(
+                vorrq_u8(vcltq_u8(bytes, vdupq_n_u8(0x21)), vcgtq_u8(bytes, vdupq_n_u8(0x7E))),
+                vceqq_u8(bytes, vdupq_n_u8(b'"'))
+            );
+            vmaxvq_u8(invalid) == 0
+        }
+    }
+}
+
+#[cfg(all(not(feature = "safe-buffer"), any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"),
+)))]
+#[inline]
+///Returns whether all characters of ASCII `tag` are `etagc`, validating `simd::LANES` bytes at a time.
+pub(crate) fn is_valid_simd(tag: &str) -> bool {
+    use core::convert::TryInto;
+
+    let mut chunks = tag.as_bytes().chunks_exact(simd::LANES);
+    for chunk in &mut chunks {
+        let chunk = match chunk.try_into() {
+            Ok(chunk) => chunk,
+            Err(_) => return false,
+        };
+        if !simd::is_valid(chunk) {
+            return false;
+        }
+    }
+
+    is_valid_bytes(chunks.remainder())
+}
+
+#[cfg(not(all(not(feature = "safe-buffer"), any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"),
+))))]
+#[inline(always)]
+///Returns whether all characters of ASCII `tag` are `etagc`, falling back to `is_valid` without SIMD.
+pub(crate) fn is_valid_simd(tag: &str) -> bool {
+    is_valid(tag)
+}
+
+#[inline]
+///Returns whether `left` and `right` are equal, comparing lengths first and then word at a time.
+pub(crate) fn eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut left_words = left.chunks_exact(WORD);
+    let mut right_words = right.chunks_exact(WORD);
+    for (left, right) in (&mut left_words).zip(&mut right_words) {
+        let left = u64::from_ne_bytes([left[0], left[1], left[2], left[3], left[4], left[5], left[6], left[7]]);
+        let right = u64::from_ne_bytes([right[0], right[1], right[2], right[3], right[4], right[5], right[6], right[7]]);
+        if left != right {
+            return false;
+        }
+    }
+
+    left_words.remainder() == right_words.remainder()
+}
@@ -0,0 +1,154 @@
+//! Tags `<issued>-<hash>`, carrying time when they were issued next to hash of content.
+//!
+//! It allows server to reject tag, which is too old, even if content is not changed, forcing client
+//! to fetch it again instead of revalidating it forever.
+//!
+//! Time is expressed as number of seconds, usually since `UNIX_EPOCH`.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::expiring::ExpiringTag;
+//!
+//! const BODY: &[u8] = b"{\"config\":1}";
+//!
+//! let issued = ExpiringTag::new(1_700_000_000, BODY);
+//! let tag = EntityTag::from(&issued);
+//!
+//! let received = ExpiringTag::from_etag(&tag).unwrap();
+//! assert!(received.same_content(&ExpiringTag::new(1_700_000_030, BODY)));
+//! assert!(!received.is_expired(1_700_000_030, 60));
+//! assert!(received.is_expired(1_700_000_060, 60));
+//! ```
+
+use core::fmt::{self, Write};
+
+use crate::{EntityTag, ParseError};
+
+const HASH_LEN: usize = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Tag of content, issued at particular time.
+pub struct ExpiringTag {
+    issued: u64,
+    tag: EntityTag,
+}
+
+impl ExpiringTag {
+    /// Creates tag of content `body`, issued at `issued`.
+    ///
+    /// Hash is 32 hexadecimal digits of XXH3-128 of content.
+    pub fn new(issued: u64, body: &[u8]) -> Self {
+        let mut tag = EntityTag::strong_unchecked("");
+        let _ = write!(tag, "{}-{:032x}", issued, xxhash_rust::xxh3::xxh3_128(body));
+        Self {
+            issued,
+            tag,
+        }
+    }
+
+    /// Parses tag `<issued>-<hash>`.
+    ///
+    /// Time must be decimal number, and hash must consist of 32 lowercase hexadecimal digits.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let (issued, hash) = text.split_once('-').ok_or(ParseError::InvalidFormat)?;
+        let is_valid = !issued.is_empty() && issued.bytes().all(|byte| byte.is_ascii_digit())
+                       && hash.len() == HASH_LEN && hash.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'));
+        let issued = match is_valid {
+            true => issued.parse().map_err(|_| ParseError::Overflow)?,
+            false => return Err(ParseError::InvalidFormat),
+        };
+
+        Ok(Self {
+            issued,
+            tag: EntityTag::checked_strong(text)?,
+        })
+    }
+
+    /// Parses tag from strong tag.
+    ///
+    /// Weak tag results in `ParseError::InvalidFormat`.
+    pub fn from_etag(tag: &EntityTag) -> Result<Self, ParseError> {
+        match tag.is_weak() || tag.is_any() {
+            true => Err(ParseError::InvalidFormat),
+            false => Self::parse(tag.tag()),
+        }
+    }
+
+    #[inline]
+    /// Returns time, when tag was issued.
+    pub const fn issued(&self) -> u64 {
+        self.issued
+    }
+
+    #[inline]
+    /// Returns hash of content.
+    pub fn hash(&self) -> &str {
+        let tag = self.as_str();
+        &tag[tag.len() - HASH_LEN..]
+    }
+
+    #[inline]
+    /// Returns whether tag is at least `max_age` seconds old at time `now`.
+    ///
+    /// Tag, issued after `now`, is never expired.
+    pub const fn is_expired(&self, now: u64, max_age: u64) -> bool {
+        now.saturating_sub(self.issued) >= max_age
+    }
+
+    #[inline]
+    /// Returns whether both tags describe the same content, regardless of when they were issued.
+    pub fn same_content(&self, other: &ExpiringTag) -> bool {
+        self.hash() == other.hash()
+    }
+
+    #[inline]
+    /// Returns textual representation of the tag.
+    pub fn as_str(&self) -> &str {
+        self.tag.tag()
+    }
+
+    #[inline]
+    /// Returns strong tag, representing the tag.
+    pub const fn as_etag(&self) -> &EntityTag {
+        &self.tag
+    }
+}
+
+impl From<&ExpiringTag> for EntityTag {
+    #[inline(always)]
+    fn from(tag: &ExpiringTag) -> Self {
+        tag.tag.clone()
+    }
+}
+
+impl From<ExpiringTag> for EntityTag {
+    #[inline(always)]
+    fn from(tag: ExpiringTag) -> Self {
+        tag.tag
+    }
+}
+
+impl core::convert::TryFrom<&EntityTag> for ExpiringTag {
+    type Error = ParseError;
+
+    #[inline(always)]
+    fn try_from(tag: &EntityTag) -> Result<Self, Self::Error> {
+        Self::from_etag(tag)
+    }
+}
+
+impl core::str::FromStr for ExpiringTag {
+    type Err = ParseError;
+
+    #[inline(always)]
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for ExpiringTag {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,119 @@
+//! Probabilistic membership filter.
+
+use crate::EntityTag;
+
+/// Bloom filter over tags, backed by `N` bytes.
+///
+/// Filter never reports false negatives, but can report false positives, which rate depends on
+/// number of inserted tags, size of the filter and number of hashes.
+/// Tags are hashed by opaque tag, hence membership is checked using weak comparison.
+///
+/// Bytes of the filter are platform independent, so filter can be sent to other party, that uses
+/// the same number of hashes.
+///
+/// ```rust
+/// use etag::{EntityTag, EtagFilter};
+///
+/// let mut cached = EtagFilter::<128>::new(EtagFilter::<128>::optimal_hashes(100));
+/// cached.insert(&EntityTag::strong_unchecked("v1"));
+///
+/// let received = EtagFilter::from_bytes(*cached.as_bytes(), cached.hashes());
+/// assert!(received.contains(&EntityTag::weak_unchecked("v1")));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct EtagFilter<const N: usize> {
+    bits: [u8; N],
+    hashes: u8,
+}
+
+impl<const N: usize> EtagFilter<N> {
+    #[inline]
+    /// Creates new empty filter, using `hashes` hash functions per tag.
+    ///
+    /// Zero `hashes` is treated as one.
+    pub const fn new(hashes: u8) -> Self {
+        Self::from_bytes([0; N], hashes)
+    }
+
+    #[inline]
+    /// Creates filter from bytes, returned by `as_bytes`, using `hashes` hash functions per tag.
+    ///
+    /// Zero `hashes` is treated as one.
+    pub const fn from_bytes(bits: [u8; N], hashes: u8) -> Self {
+        assert!(N > 0, "Filter cannot be empty");
+        Self {
+            bits,
+            hashes: match hashes {
+                0 => 1,
+                hashes => hashes,
+            }
+        }
+    }
+
+    /// Returns number of hashes, minimizing false positive rate for `expected_len` tags.
+    pub const fn optimal_hashes(expected_len: usize) -> u8 {
+        let expected_len = match expected_len {
+            0 => 1,
+            expected_len => expected_len,
+        };
+        //(bits / expected_len) * ln(2), rounded to nearest
+        let hashes = ((N * 8) as u64 * 693 + expected_len as u64 * 500) / (expected_len as u64 * 1000);
+        match hashes {
+            0 => 1,
+            1..=32 => hashes as u8,
+            _ => 32,
+        }
+    }
+
+    #[inline]
+    /// Returns number of hash functions per tag.
+    pub const fn hashes(&self) -> u8 {
+        self.hashes
+    }
+
+    #[inline]
+    /// Returns bytes of the filter.
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.bits
+    }
+
+    ///Returns indexes of bits, corresponding to `tag`, using double hashing.
+    fn bit_indexes(&self, tag: &EntityTag) -> impl Iterator<Item = usize> {
+        let hash = xxhash_rust::xxh3::xxh3_128(tag.tag().as_bytes());
+        let first = hash as u64;
+        let second = (hash >> 64) as u64 | 1;
+        let bits_len = (N * 8) as u64;
+        (0..self.hashes as u64).map(move |idx| (first.wrapping_add(idx.wrapping_mul(second)) % bits_len) as usize)
+    }
+
+    /// Adds tag to the filter.
+    ///
+    /// `EntityTag::ANY` is never added.
+    pub fn insert(&mut self, tag: &EntityTag) {
+        if tag.is_any() {
+            return;
+        }
+
+        for idx in self.bit_indexes(tag) {
+            self.bits[idx / 8] |= 1 << (idx % 8);
+        }
+    }
+
+    /// Returns whether tag might have been added to the filter.
+    pub fn contains(&self, tag: &EntityTag) -> bool {
+        !tag.is_any() && self.bit_indexes(tag).all(|idx| self.bits[idx / 8] & (1 << (idx % 8)) != 0)
+    }
+
+    #[inline]
+    /// Removes all tags from the filter.
+    pub fn clear(&mut self) {
+        self.bits = [0; N];
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for EtagFilter<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let bits_set: u32 = self.bits.iter().map(|byte| byte.count_ones()).sum();
+        f.debug_struct("EtagFilter").field("bits", &(N * 8)).field("bits_set", &bits_set).field("hashes", &self.hashes).finish()
+    }
+}
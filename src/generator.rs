@@ -0,0 +1,363 @@
+//! Tag generators.
+
+use core::fmt::Write;
+
+#[cfg(all(target_has_atomic = "32", not(feature = "portable-atomic")))]
+use core::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "portable-atomic")]
+use portable_atomic::{AtomicU32, Ordering};
+
+use crate::{hash, EntityTag};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Generates weak tags from content and time window it is served in.
+///
+/// Tag changes when either content or window changes, forcing clients to revalidate periodically
+/// regenerated content at least once per window, even if it is unchanged.
+///
+/// ## Format:
+///
+/// `<len>-<hash>`, where hash, selected by `hash-*` features, is seeded with index of the window.
+///
+/// ```rust
+/// use etag::generator::TimeBucketed;
+///
+/// let generator = TimeBucketed::new(300);
+/// assert_eq!(generator.generate(b"page", 0), generator.generate(b"page", 299));
+/// assert_ne!(generator.generate(b"page", 0), generator.generate(b"page", 300));
+/// ```
+pub struct TimeBucketed {
+    window_secs: u64,
+}
+
+impl TimeBucketed {
+    #[inline]
+    /// Creates new generator with window of `window_secs` seconds.
+    ///
+    /// Zero window is treated as one second.
+    pub const fn new(window_secs: u64) -> Self {
+        Self {
+            window_secs: match window_secs {
+                0 => 1,
+                window_secs => window_secs,
+            }
+        }
+    }
+
+    #[inline]
+    /// Returns window length in seconds.
+    pub const fn window_secs(&self) -> u64 {
+        self.window_secs
+    }
+
+    #[inline]
+    /// Returns index of the window, containing `timestamp_secs`.
+    pub const fn bucket(&self, timestamp_secs: u64) -> u64 {
+        timestamp_secs / self.window_secs
+    }
+
+    /// Generates weak tag for content `bytes`, served at `timestamp_secs`.
+    pub fn generate(&self, bytes: &[u8], timestamp_secs: u64) -> EntityTag {
+        let hash = hash::content_with_seed(bytes, self.bucket(timestamp_secs));
+        let mut tag = EntityTag::from_len_hash(bytes.len() as u64, hash);
+        tag.set_weak(true);
+        tag
+    }
+
+    #[cfg(feature = "std")]
+    /// Generates weak tag for content `bytes`, served now.
+    pub fn generate_now(&self, bytes: &[u8]) -> EntityTag {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |now| now.as_secs());
+        self.generate(bytes, now)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Generates tags, distinct for each tenant, even when content is the same.
+///
+/// Identifier of the tenant is hashed into seed, so it doesn't take tag's capacity.
+///
+/// ## Format:
+///
+/// `<len>-<hash>`, where hash, selected by `hash-*` features, is seeded with hash of tenant's identifier.
+///
+/// ```rust
+/// use etag::generator::Namespace;
+///
+/// let first = Namespace::new("tenant-1");
+/// let second = Namespace::new("tenant-2");
+/// assert_eq!(first.from_data(b"page"), Namespace::new("tenant-1").from_data(b"page"));
+/// assert_ne!(first.from_data(b"page"), second.from_data(b"page"));
+/// ```
+pub struct Namespace {
+    seed: u64,
+}
+
+impl Namespace {
+    #[inline]
+    /// Creates namespace of tenant, identified by `id`.
+    pub const fn new(id: &str) -> Self {
+        Self {
+            seed: hash::const_content(id.as_bytes()) as u64,
+        }
+    }
+
+    #[inline]
+    /// Returns seed, derived from tenant's identifier.
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline]
+    /// Creates strong tag by hashing content `bytes` within namespace.
+    pub fn from_data(&self, bytes: &[u8]) -> EntityTag {
+        let hash = hash::content_with_seed(bytes, self.seed);
+        EntityTag::from_len_hash(bytes.len() as u64, hash)
+    }
+
+    /// Creates tag, unique to namespace, by hashing existing `tag`, preserving its weakness.
+    ///
+    /// Allows to namespace tags produced by other means, e.g. `EntityTag::from_file_meta`.
+    /// `EntityTag::ANY` is returned as it is.
+    pub fn apply(&self, tag: &EntityTag) -> EntityTag {
+        if tag.is_any() {
+            return EntityTag::ANY;
+        }
+
+        let mut result = self.from_data(tag.tag().as_bytes());
+        result.set_weak(tag.is_weak());
+        result
+    }
+}
+
+#[cfg(any(target_has_atomic = "32", feature = "portable-atomic"))]
+/// Generation of namespace, which is mixed into every tag produced under it.
+///
+/// Bumping generation invalidates all tags of the namespace at once, e.g. after deploy or schema
+/// migration, without touching individual resources.
+/// Generation can be shared between threads, as it is updated atomically.
+///
+/// ## Format:
+///
+/// `<len>-<hash>`, where hash, selected by `hash-*` features, is seeded with generation.
+///
+/// ```rust
+/// use etag::generator::Generation;
+///
+/// static GENERATION: Generation = Generation::new(0);
+///
+/// let tag = GENERATION.from_data(b"page");
+/// assert_eq!(tag, GENERATION.from_data(b"page"));
+///
+/// assert_eq!(GENERATION.bump_all(), Ok(1));
+/// assert_ne!(tag, GENERATION.from_data(b"page"));
+/// ```
+pub struct Generation {
+    current: AtomicU32,
+}
+
+#[cfg(any(target_has_atomic = "32", feature = "portable-atomic"))]
+impl Generation {
+    #[inline]
+    /// Creates namespace at `generation`, e.g. restored from persistent storage.
+    pub const fn new(generation: u32) -> Self {
+        Self {
+            current: AtomicU32::new(generation),
+        }
+    }
+
+    #[inline]
+    /// Returns current generation.
+    pub fn get(&self) -> u32 {
+        self.current.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    /// Invalidates all tags of the namespace, returning new generation.
+    ///
+    /// Returns `GeneratorError::Exhausted`, leaving generation unchanged, if it is already `u32::MAX`,
+    /// as wrapping around would reissue tags of the first generations.
+    pub fn bump_all(&self) -> Result<u32, GeneratorError<core::convert::Infallible>> {
+        match self.current.fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| current.checked_add(1)) {
+            Ok(previous) => Ok(previous + 1),
+            Err(_) => Err(GeneratorError::Exhausted),
+        }
+    }
+
+    #[inline]
+    /// Creates strong tag by hashing content `bytes` within current generation.
+    pub fn from_data(&self, bytes: &[u8]) -> EntityTag {
+        let hash = hash::content_with_seed(bytes, self.get() as u64);
+        EntityTag::from_len_hash(bytes.len() as u64, hash)
+    }
+
+    /// Creates tag, unique to current generation, by hashing existing `tag`, preserving its weakness.
+    ///
+    /// `EntityTag::ANY` is returned as it is.
+    pub fn apply(&self, tag: &EntityTag) -> EntityTag {
+        if tag.is_any() {
+            return EntityTag::ANY;
+        }
+
+        let mut result = self.from_data(tag.tag().as_bytes());
+        result.set_weak(tag.is_weak());
+        result
+    }
+}
+
+#[cfg(any(target_has_atomic = "32", feature = "portable-atomic"))]
+impl core::fmt::Debug for Generation {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_tuple("Generation").field(&self.get()).finish()
+    }
+}
+
+/// Persistent storage of `EtagGenerator` counters.
+pub trait Storage {
+    /// Error of the storage.
+    type Error;
+
+    /// Loads last issued counter of `resource`, returning `None` if there is none.
+    fn load(&mut self, resource: &str) -> Result<Option<u64>, Self::Error>;
+
+    /// Stores last issued counter of `resource`.
+    ///
+    /// Counter is considered issued only once this method succeeds, so it must be durable by then.
+    fn store(&mut self, resource: &str, counter: u64) -> Result<(), Self::Error>;
+}
+
+impl<S: Storage + ?Sized> Storage for &mut S {
+    type Error = S::Error;
+
+    #[inline(always)]
+    fn load(&mut self, resource: &str) -> Result<Option<u64>, Self::Error> {
+        S::load(self, resource)
+    }
+
+    #[inline(always)]
+    fn store(&mut self, resource: &str, counter: u64) -> Result<(), Self::Error> {
+        S::store(self, resource, counter)
+    }
+}
+
+#[cfg(feature = "std")]
+///In-memory storage, which is not persistent.
+impl Storage for std::collections::HashMap<std::string::String, u64> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn load(&mut self, resource: &str) -> Result<Option<u64>, Self::Error> {
+        Ok(self.get(resource).copied())
+    }
+
+    #[inline]
+    fn store(&mut self, resource: &str, counter: u64) -> Result<(), Self::Error> {
+        self.insert(resource.into(), counter);
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Error of `EtagGenerator::next` and `Generation::bump_all`.
+pub enum GeneratorError<E> {
+    /// Storage failed to load or store counter.
+    Storage(E),
+    /// Counter reached its maximum, i.e. `u64::MAX` for resource or `u32::MAX` for generation, so no more tags can be issued.
+    Exhausted,
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for GeneratorError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            GeneratorError::Storage(error) => error.fmt(f),
+            GeneratorError::Exhausted => f.write_str("Counter is exhausted"),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Generates strictly increasing strong tags `"<counter>"` for each resource.
+///
+/// Counter is persisted before tag is returned, so tags are never reused after crash.
+/// First tag of resource is `"1"`.
+///
+/// ```rust
+/// use etag::EntityTag;
+/// use etag::generator::{EtagGenerator, Storage};
+///
+/// //Counter of single resource, e.g. kept in flash page
+/// struct Page(Option<u64>);
+///
+/// impl Storage for Page {
+///     type Error = core::convert::Infallible;
+///
+///     fn load(&mut self, _: &str) -> Result<Option<u64>, Self::Error> {
+///         Ok(self.0)
+///     }
+///
+///     fn store(&mut self, _: &str, counter: u64) -> Result<(), Self::Error> {
+///         self.0 = Some(counter);
+///         Ok(())
+///     }
+/// }
+///
+/// let mut generator = EtagGenerator::new(Page(None));
+/// assert_eq!(generator.next("/index.html").unwrap(), EntityTag::strong_unchecked("1"));
+/// assert_eq!(generator.next("/index.html").unwrap(), EntityTag::strong_unchecked("2"));
+/// ```
+///
+/// With `std` feature, `HashMap<String, u64>` can be used as in-memory storage.
+pub struct EtagGenerator<S> {
+    storage: S,
+}
+
+impl<S: Storage> EtagGenerator<S> {
+    #[inline]
+    /// Creates new generator, using `storage` to persist counters.
+    pub const fn new(storage: S) -> Self {
+        Self {
+            storage,
+        }
+    }
+
+    #[inline]
+    ///Formats counter as strong tag.
+    fn tag(counter: u64) -> EntityTag {
+        let mut tag = EntityTag::strong_unchecked("");
+        let _ = write!(tag, "{}", counter);
+        tag
+    }
+
+    /// Issues next tag of `resource`.
+    ///
+    /// Returns `GeneratorError::Exhausted` if counter of the resource cannot be incremented,
+    /// leaving it unchanged.
+    pub fn next(&mut self, resource: &str) -> Result<EntityTag, GeneratorError<S::Error>> {
+        let counter = self.storage.load(resource).map_err(GeneratorError::Storage)?;
+        let counter = match counter.unwrap_or(0).checked_add(1) {
+            Some(counter) => counter,
+            None => return Err(GeneratorError::Exhausted),
+        };
+        self.storage.store(resource, counter).map_err(GeneratorError::Storage)?;
+        Ok(Self::tag(counter))
+    }
+
+    /// Returns last issued tag of `resource`, if any.
+    pub fn current(&mut self, resource: &str) -> Result<Option<EntityTag>, S::Error> {
+        Ok(self.storage.load(resource)?.map(Self::tag))
+    }
+
+    #[inline]
+    /// Returns reference to the storage.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    #[inline]
+    /// Returns the storage, consuming generator.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
@@ -0,0 +1,257 @@
+//! Const evaluable hash functions.
+
+///64-bit FNV-1a offset basis.
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+///64-bit FNV-1a prime.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[inline]
+///Computes 64-bit FNV-1a hash.
+pub(crate) const fn fnv1a_64(bytes: &[u8]) -> u64 {
+    fnv1a_64_with_seed(bytes, 0)
+}
+
+///Computes 64-bit FNV-1a hash, with `seed` mixed into offset basis.
+const fn fnv1a_64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    let mut hash = FNV_OFFSET ^ seed;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        idx += 1;
+    }
+    hash
+}
+
+///Reflected polynomial of CRC-32 (ISO-HDLC).
+const CRC32_POLY: u32 = 0xedb88320;
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut idx = 0;
+    while idx < table.len() {
+        let mut value = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = match value & 1 {
+                1 => (value >> 1) ^ CRC32_POLY,
+                _ => value >> 1,
+            };
+            bit += 1;
+        }
+        table[idx] = value;
+        idx += 1;
+    }
+    table
+}
+
+///Computes CRC-32 (ISO-HDLC) checksum, compatible with zlib's `crc32`.
+pub(crate) const fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        crc = CRC32_TABLE[((crc ^ bytes[idx] as u32) & 0xFF) as usize] ^ (crc >> 8);
+        idx += 1;
+    }
+    !crc
+}
+
+///Primes of wyhash.
+const WY_P: [u64; 6] = [0xa0761d6478bd642f, 0xe7037ed1a0b428db, 0x8ebc6af09c88c6e3, 0x589965cc75374cc3, 0x1d8e4e27c47d124f, 0xeb44accab455d165];
+
+const fn wymum(left: u64, right: u64) -> u64 {
+    let result = left as u128 * right as u128;
+    ((result >> 64) ^ result) as u64
+}
+
+const fn read32(bytes: &[u8], at: usize) -> u64 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]) as u64
+}
+
+const fn read64(bytes: &[u8], at: usize) -> u64 {
+    read32(bytes, at) | read32(bytes, at + 4) << 32
+}
+
+const fn read64_swapped(bytes: &[u8], at: usize) -> u64 {
+    read32(bytes, at) << 32 | read32(bytes, at + 4)
+}
+
+///Reads last 1 to 8 bytes, starting at `at`.
+const fn read_rest(bytes: &[u8], at: usize) -> u64 {
+    match bytes.len() - at {
+        1 => bytes[at] as u64,
+        2 => (bytes[at + 1] as u64) << 8 | bytes[at] as u64,
+        3 => (bytes[at + 1] as u64) << 16 | (bytes[at] as u64) << 8 | bytes[at + 2] as u64,
+        4 => read32(bytes, at),
+        5 => read32(bytes, at) << 8 | bytes[at + 4] as u64,
+        6 => read32(bytes, at) << 16 | (bytes[at + 5] as u64) << 8 | bytes[at + 4] as u64,
+        7 => read32(bytes, at) << 24 | (bytes[at + 5] as u64) << 16 | (bytes[at + 4] as u64) << 8 | bytes[at + 6] as u64,
+        _ => read64_swapped(bytes, at),
+    }
+}
+
+///Mixes 32 bytes block, starting at `at`, into wyhash state.
+const fn wyhash_block(seed: u64, bytes: &[u8], at: usize) -> u64 {
+    let left = wymum(read64(bytes, at) ^ WY_P[1], read64(bytes, at + 8) ^ WY_P[2]);
+    let right = wymum(read64(bytes, at + 16) ^ WY_P[3], read64(bytes, at + 24) ^ WY_P[4]);
+    wymum(seed ^ WY_P[0], left ^ right)
+}
+
+///Mixes bytes after `at`, shorter than block, and total length `len` into wyhash state.
+const fn wyhash_finish(mut seed: u64, bytes: &[u8], at: usize, len: u64) -> u64 {
+    seed ^= WY_P[0];
+
+    if at < bytes.len() {
+        seed = match (bytes.len() - at - 1) / 8 {
+            0 => wymum(seed, read_rest(bytes, at) ^ WY_P[1]),
+            1 => wymum(read64_swapped(bytes, at) ^ seed, read_rest(bytes, at + 8) ^ WY_P[2]),
+            2 => wymum(read64_swapped(bytes, at) ^ seed, read64_swapped(bytes, at + 8) ^ WY_P[2]) ^ wymum(seed, read_rest(bytes, at + 16) ^ WY_P[3]),
+            _ => wymum(read64_swapped(bytes, at) ^ seed, read64_swapped(bytes, at + 8) ^ WY_P[2]) ^ wymum(read64_swapped(bytes, at + 16) ^ seed, read_rest(bytes, at + 24) ^ WY_P[4]),
+        };
+    }
+
+    wymum(seed, len ^ WY_P[5])
+}
+
+///Computes 64-bit wyhash, compatible with `wyhash` crate.
+pub(crate) const fn wyhash(bytes: &[u8], mut seed: u64) -> u64 {
+    let mut at = 0;
+    while at + 32 <= bytes.len() {
+        seed = wyhash_block(seed, bytes, at);
+        at += 32;
+    }
+
+    wyhash_finish(seed, bytes, at, bytes.len() as u64)
+}
+
+#[inline]
+///Hashes content with algorithm, selected by `hash-*` features.
+pub(crate) fn content(bytes: &[u8]) -> u128 {
+    #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+    {
+        fnv1a_64(bytes) as u128
+    }
+    #[cfg(feature = "hash-wyhash")]
+    {
+        wyhash(bytes, 0) as u128
+    }
+    #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+    {
+        xxhash_rust::xxh3::xxh3_128(bytes)
+    }
+}
+
+#[inline]
+///Hashes content with algorithm, selected by `hash-*` features, mixing `seed` in.
+///
+///Zero seed produces the same hash as `content`.
+pub(crate) fn content_with_seed(bytes: &[u8], seed: u64) -> u128 {
+    #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+    {
+        fnv1a_64_with_seed(bytes, seed) as u128
+    }
+    #[cfg(feature = "hash-wyhash")]
+    {
+        wyhash(bytes, seed) as u128
+    }
+    #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+    {
+        xxhash_rust::xxh3::xxh3_128_with_seed(bytes, seed)
+    }
+}
+
+#[inline]
+///Hashes content with algorithm, selected by `hash-*` features, in const context.
+pub(crate) const fn const_content(bytes: &[u8]) -> u128 {
+    #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+    {
+        fnv1a_64(bytes) as u128
+    }
+    #[cfg(feature = "hash-wyhash")]
+    {
+        wyhash(bytes, 0) as u128
+    }
+    #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+    {
+        xxhash_rust::const_xxh3::xxh3_128(bytes)
+    }
+}
+
+#[derive(Clone)]
+///Streaming hasher of content, producing the same hash as `content`.
+pub(crate) struct ContentHasher {
+    #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+    hasher: xxhash_rust::xxh3::Xxh3,
+    #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+    hash: u64,
+    #[cfg(feature = "hash-wyhash")]
+    seed: u64,
+    #[cfg(feature = "hash-wyhash")]
+    block: [u8; 32],
+    #[cfg(feature = "hash-wyhash")]
+    block_len: usize,
+    #[cfg(feature = "hash-wyhash")]
+    len: u64,
+}
+
+impl ContentHasher {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+            hasher: xxhash_rust::xxh3::Xxh3::new(),
+            #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+            hash: FNV_OFFSET,
+            #[cfg(feature = "hash-wyhash")]
+            seed: 0,
+            #[cfg(feature = "hash-wyhash")]
+            block: [0; 32],
+            #[cfg(feature = "hash-wyhash")]
+            block_len: 0,
+            #[cfg(feature = "hash-wyhash")]
+            len: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+        self.hasher.update(bytes);
+
+        #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+
+        #[cfg(feature = "hash-wyhash")]
+        {
+            self.len += bytes.len() as u64;
+            //Full block is never the tail, so it is mixed as soon as it is filled.
+            for byte in bytes {
+                self.block[self.block_len] = *byte;
+                self.block_len += 1;
+                if self.block_len == self.block.len() {
+                    self.seed = wyhash_block(self.seed, &self.block, 0);
+                    self.block_len = 0;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn digest(&self) -> u128 {
+        #[cfg(not(any(feature = "hash-fnv", feature = "hash-wyhash")))]
+        {
+            self.hasher.digest128()
+        }
+        #[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+        {
+            self.hash as u128
+        }
+        #[cfg(feature = "hash-wyhash")]
+        {
+            wyhash_finish(self.seed, &self.block[..self.block_len], 0, self.len) as u128
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! `http` support.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use http::HeaderMap;
+//!
+//! let mut headers = HeaderMap::new();
+//! etag::http::set_etag(&mut headers, &EntityTag::weak_unchecked("v1")).unwrap();
+//! assert_eq!(etag::http::get_etag(&headers), Some(Ok(EntityTag::weak_unchecked("v1"))));
+//! ```
+
+use ::http::header::{HeaderMap, HeaderValue, ETAG, IF_MATCH, IF_NONE_MATCH};
+
+use crate::{EntityTag, FieldLines, ParseError};
+
+#[inline]
+fn get_str(headers: &HeaderMap, name: ::http::header::HeaderName) -> Option<Result<&str, ParseError>> {
+    headers.get(name).map(|value| value.to_str().map_err(|_| ParseError::NotAscii))
+}
+
+/// Parses `ETag` header.
+///
+/// Returns `None` if header is absent.
+pub fn get_etag(headers: &HeaderMap) -> Option<Result<EntityTag, ParseError>> {
+    get_str(headers, ETAG).map(|value| value.and_then(str::parse))
+}
+
+#[inline]
+fn get_lines(headers: &HeaderMap, name: ::http::header::HeaderName) -> Option<FieldLines<'_>> {
+    FieldLines::from_lines(headers.get_all(name).iter().map(|value| value.to_str().ok()))
+}
+
+/// Returns all field lines of `If-None-Match` header.
+///
+/// Returns `None` if header is absent.
+/// Field line with non-ASCII characters makes header invalid, so that it matches nothing.
+pub fn get_if_none_match(headers: &HeaderMap) -> Option<FieldLines<'_>> {
+    get_lines(headers, IF_NONE_MATCH)
+}
+
+/// Returns all field lines of `If-Match` header, to be evaluated by `preconditions::check_write_lines`.
+///
+/// Returns `None` if header is absent.
+/// Field line with non-ASCII characters makes header invalid, so that it matches nothing.
+pub fn get_if_match(headers: &HeaderMap) -> Option<FieldLines<'_>> {
+    get_lines(headers, IF_MATCH)
+}
+
+/// Sets `ETag` header, replacing existing value.
+///
+/// Returns `ParseError::InvalidFormat` if tag contains characters not allowed in header value.
+pub fn set_etag(headers: &mut HeaderMap, tag: &EntityTag) -> Result<(), ParseError> {
+    match HeaderValue::from_str(tag.as_header_str()) {
+        Ok(value) => {
+            headers.insert(ETAG, value);
+            Ok(())
+        },
+        Err(_) => Err(ParseError::InvalidFormat),
+    }
+}
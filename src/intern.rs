@@ -0,0 +1,137 @@
+//! Interning of tags.
+
+use core::convert::TryFrom;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use crate::EntityTag;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Handle of tag, interned by `TagPool`.
+///
+/// It is only valid until all its references are released.
+pub struct TagHandle(u32);
+
+struct Entry {
+    tag: EntityTag,
+    refs: usize,
+}
+
+#[derive(Default)]
+/// Pool of reference counted tags, storing each distinct tag once.
+///
+/// Intended for tables holding many duplicate tags, which can store 4 byte `TagHandle` instead.
+///
+/// ```rust
+/// use etag::EntityTag;
+/// use etag::intern::TagPool;
+///
+/// let mut pool = TagPool::new();
+/// let first = pool.intern(&EntityTag::strong_unchecked("a"));
+/// let second = pool.intern(&EntityTag::strong_unchecked("a"));
+/// assert_eq!(first, second);
+/// assert_eq!(pool.len(), 1);
+/// assert_eq!(pool.refs(first), 2);
+/// ```
+pub struct TagPool {
+    entries: Vec<Option<Entry>>,
+    index: HashMap<EntityTag, u32>,
+    free: Vec<u32>,
+}
+
+impl TagPool {
+    #[inline]
+    /// Creates new empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Returns number of distinct tags in the pool.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[inline]
+    /// Returns whether pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Interns tag, returning its handle and incrementing its reference count.
+    ///
+    /// # Panics
+    ///
+    /// If number of distinct tags exceeds `u32::MAX`.
+    pub fn intern(&mut self, tag: &EntityTag) -> TagHandle {
+        if let Some(idx) = self.index.get(tag) {
+            if let Some(entry) = &mut self.entries[*idx as usize] {
+                entry.refs += 1;
+            }
+            return TagHandle(*idx);
+        }
+
+        let entry = Some(Entry {
+            tag: tag.clone(),
+            refs: 1,
+        });
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.entries[idx as usize] = entry;
+                idx
+            },
+            None => {
+                let idx = u32::try_from(self.entries.len()).expect("TagPool overflow");
+                self.entries.push(entry);
+                idx
+            },
+        };
+        self.index.insert(tag.clone(), idx);
+        TagHandle(idx)
+    }
+
+    #[inline]
+    /// Returns tag of the handle.
+    pub fn get(&self, handle: TagHandle) -> Option<&EntityTag> {
+        self.entry(handle).map(|entry| &entry.tag)
+    }
+
+    #[inline]
+    /// Returns number of references to the handle.
+    pub fn refs(&self, handle: TagHandle) -> usize {
+        self.entry(handle).map_or(0, |entry| entry.refs)
+    }
+
+    /// Increments reference count of the handle, returning whether it is valid.
+    pub fn retain(&mut self, handle: TagHandle) -> bool {
+        match self.entries.get_mut(handle.0 as usize) {
+            Some(Some(entry)) => {
+                entry.refs += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Decrements reference count of the handle, removing tag when it reaches zero.
+    ///
+    /// Returns removed tag.
+    pub fn release(&mut self, handle: TagHandle) -> Option<EntityTag> {
+        let slot = self.entries.get_mut(handle.0 as usize)?;
+        let entry = slot.as_mut()?;
+        entry.refs -= 1;
+        if entry.refs > 0 {
+            return None;
+        }
+
+        let entry = slot.take()?;
+        self.index.remove(&entry.tag);
+        self.free.push(handle.0);
+        Some(entry.tag)
+    }
+
+    #[inline]
+    fn entry(&self, handle: TagHandle) -> Option<&Entry> {
+        self.entries.get(handle.0 as usize).and_then(Option::as_ref)
+    }
+}
@@ -0,0 +1,88 @@
+//! Map keys with comparison semantics.
+
+use core::hash::{Hash, Hasher};
+
+use crate::EntityTag;
+
+#[derive(Clone, Debug)]
+/// Key, comparing tags using weak comparison.
+///
+/// Tags are equal when their opaque tags match, regardless of weakness.
+/// `EntityTag::ANY` is only equal to itself.
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use etag::{EntityTag, WeakKey};
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(WeakKey(EntityTag::strong_unchecked("v1"))));
+/// assert!(!seen.insert(WeakKey(EntityTag::weak_unchecked("v1"))));
+/// ```
+pub struct WeakKey(pub EntityTag);
+
+impl PartialEq for WeakKey {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.weak_eq(&other.0)
+    }
+}
+
+impl Eq for WeakKey {}
+
+impl Hash for WeakKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.is_any().hash(state);
+        self.0.tag().hash(state);
+    }
+}
+
+impl From<EntityTag> for WeakKey {
+    #[inline(always)]
+    fn from(tag: EntityTag) -> Self {
+        Self(tag)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Key, comparing tags using strong comparison.
+///
+/// Strong tags are equal when their opaque tags match, and never equal to weak tags.
+/// As `Eq` must be reflexive, weak tag is still equal to the same weak tag, but it is distinct from
+/// any strong tag.
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use etag::{EntityTag, StrongKey};
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(StrongKey(EntityTag::strong_unchecked("v1"))));
+/// assert!(seen.insert(StrongKey(EntityTag::weak_unchecked("v1"))));
+/// assert!(!seen.insert(StrongKey(EntityTag::strong_unchecked("v1"))));
+/// ```
+pub struct StrongKey(pub EntityTag);
+
+impl PartialEq for StrongKey {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.strong_eq(&other.0) || (self.0.is_weak() && other.0.is_weak() && self.0.weak_eq(&other.0))
+    }
+}
+
+impl Eq for StrongKey {}
+
+impl Hash for StrongKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.is_weak().hash(state);
+        self.0.is_any().hash(state);
+        self.0.tag().hash(state);
+    }
+}
+
+impl From<EntityTag> for StrongKey {
+    #[inline(always)]
+    fn from(tag: EntityTag) -> Self {
+        Self(tag)
+    }
+}
@@ -2,7 +2,38 @@
 //!
 //! # Features
 //!
-//! - `std` - Add `EntityTag::from_file_meta` in order to generate ETag using file's metadata.
+//! - `alloc` - Adds conversions of `EntityTag` into and from `String`, growable `EtagSet` and `dependency` tracking of derived tags.
+//! - `std` - Enables `alloc` and adds `EntityTag::from_file_meta` in order to generate ETag using file's metadata, and `EntityTag::from_files` to hash content of multiple files.
+//! - `defmt` - Implements `defmt::Format` for `EntityTag` and `ParseError`.
+//! - `ufmt` - Implements `ufmt::uDisplay` and `ufmt::uDebug` for `EntityTag` and `ParseError`.
+//! - `serde` - Implements `Serialize` and `Deserialize` for `EntityTag`.
+//! - `rkyv` - Implements zero-copy `rkyv` archiving for `EntityTag`.
+//! - `borsh` - Implements `BorshSerialize` and `BorshDeserialize` for `EntityTag`.
+//! - `schemars` - Implements `JsonSchema` for `EntityTag`.
+//! - `utoipa` - Implements `ToSchema` for `EntityTag` and provides OpenAPI descriptions of conditional headers.
+//! - `arbitrary` - Implements `Arbitrary` for `EntityTag`, generating valid tags.
+//! - `proptest` - Provides `proptest` strategies for valid and malformed tags.
+//! - `wasm` - Provides `wasm-bindgen` bindings, exposing `EntityTag` to JavaScript.
+//! - `portable-atomic` - Uses `portable-atomic` to provide `AtomicEntityTag` on targets without native atomics.
+//! - `httpdate` - Enables `std` and adds parsing of HTTP-date values to `preconditions`.
+//! - `http` - Provides helpers to get and set tags in `http::HeaderMap`.
+//! - `embedded-svc` - Implements `embedded::RequestHeaders` for `embedded-svc` server requests and allows to initiate their conditional responses.
+//! - `picoserve` - Implements `embedded::RequestHeaders` for `picoserve` request headers and converts conditional responses into `picoserve` responses.
+//! - `cache-digest` - Enables `alloc` and provides `cache_digest`, implementing HTTP cache digests.
+//! - `sqlx` - Enables `std` and provides `sqlx` Postgres integration for row versions and `If-Match` guarded updates.
+//! - `getrandom` - Adds `EntityTag::random_strong`, generating random tags using `getrandom`.
+//! - `ulid` - Adds `EntityTag::from_ulid` and `EntityTag::to_ulid`, using ULID as sortable tag. With `std` it also adds `EntityTag::new_ulid`.
+//! - `signed` - Adds `EntityTag::signed` and `EntityTag::verify_signature`, using HMAC-SHA256.
+//! - `zeroize` - Scrubs key material from HMAC state and digest buffers of `signed` tags after use.
+//! - `encrypted` - Provides `encrypted`, encrypting payloads such as resource id and version into tags.
+//! - `cas` - Enables `std` and provides `cas`, content-addressable storage of blobs keyed by their tags.
+//! - `safe-buffer` - Stores tags in zero-initialized buffer without `unsafe` code, validating UTF-8 on access. Unless `rkyv` is enabled, crate is compiled with `forbid(unsafe_code)`.
+//! - `hash-xxh3` - Selects 128-bit `xxh3` as algorithm of `EntityTag::from_data`, which is the default.
+//! - `hash-fnv` - Selects 64-bit FNV-1a as algorithm of `EntityTag::from_data`, taking precedence over `hash-xxh3`.
+//! - `hash-wyhash` - Selects 64-bit wyhash as algorithm of `EntityTag::from_data`, taking precedence over `hash-fnv` and `hash-xxh3`.
+//!   `hash-*` features should be chosen by application, rather than library.
+//! - `buffer-128` - Increases maximum length of opaque tag to 124 characters.
+//! - `buffer-256` - Increases maximum length of opaque tag to 251 characters, taking precedence over `buffer-128`.
 //!
 //! # Usage
 //!
@@ -10,7 +41,7 @@
 //! use etag::EntityTag;
 //!
 //! fn main() {
-//!     let my_tag = EntityTag::strong("lolka");
+//!     let my_tag = EntityTag::strong_unchecked("lolka");
 //!     let text_etag = my_tag.to_string();
 //!     let parse_tag = text_etag.parse::<EntityTag>().unwrap();
 //!
@@ -20,14 +51,112 @@
 
 #![no_std]
 #![deny(warnings)]
+#![cfg_attr(all(feature = "safe-buffer", not(feature = "rkyv")), forbid(unsafe_code))]
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "proptest", test))]
 extern crate std;
+#[cfg(any(feature = "alloc", feature = "schemars", feature = "utoipa", feature = "wasm"))]
+extern crate alloc;
 
-use core::mem;
 use core::fmt::{self, Write};
 
-type Buffer = str_buf::StrBuf::<62>;
+mod buffer;
+mod etagc;
+mod hash;
+pub use buffer::StrBuf;
+use buffer::PaddedStrBuf;
+mod list;
+pub use list::{display_list, parse_list, parse_many, write_list, Comparison, DisplayList, EntityTagList, IfNoneMatch, TagIter, ValidTags};
+pub mod embedded;
+pub mod raw;
+pub mod delta;
+pub mod revision;
+pub mod expiring;
+pub mod resource;
+pub mod trailer;
+pub mod generator;
+pub mod surrogate;
+pub mod zip;
+mod key;
+pub use key::{StrongKey, WeakKey};
+mod set;
+pub use set::FixedEtagSet;
+#[cfg(feature = "alloc")]
+pub use set::EtagSet;
+#[cfg(feature = "alloc")]
+pub mod dependency;
+mod filter;
+pub use filter::EtagFilter;
+mod scan;
+pub use scan::{scan_headers, FieldLines, ScannedConditionals};
+mod cache;
+pub use cache::EtagCache;
+#[cfg(feature = "std")]
+pub use cache::ValidatorCache;
+#[cfg(feature = "std")]
+pub mod preconditions;
+#[cfg(feature = "std")]
+pub mod revalidation;
+#[cfg(feature = "std")]
+pub mod serve;
+#[cfg(feature = "std")]
+pub mod intern;
+#[cfg(any(target_has_atomic = "32", feature = "portable-atomic"))]
+mod atomic;
+#[cfg(any(target_has_atomic = "32", feature = "portable-atomic"))]
+pub use atomic::AtomicEntityTag;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::ArchivedEntityTag;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "utoipa")]
+pub mod utoipa;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "cache-digest")]
+pub mod cache_digest;
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+#[cfg(feature = "encrypted")]
+pub mod encrypted;
+#[cfg(feature = "cas")]
+pub mod cas;
+
+///Maximum length of opaque tag.
+#[cfg(not(any(feature = "buffer-128", feature = "buffer-256")))]
+const MAX_TAG_LEN: usize = 62;
+///Maximum length of opaque tag.
+#[cfg(all(feature = "buffer-128", not(feature = "buffer-256")))]
+const MAX_TAG_LEN: usize = 124;
+///Maximum length of opaque tag, limited by buffer's `u8` length.
+#[cfg(feature = "buffer-256")]
+const MAX_TAG_LEN: usize = 251;
+///Storage for header representation `W/"<tag>"`
+type Buffer = StrBuf<{MAX_TAG_LEN + 4}>;
+///Stack buffer, large enough to hold header representation of any `EntityTag`.
+pub type HeaderBuffer = Buffer;
+///Stack buffer, holding opaque tag of `EntityTag`, as returned by `EntityTag::into_parts`.
+pub type TagBuffer = StrBuf<MAX_TAG_LEN>;
+///Zero padded storage of `EntityTag`, which takes no space for length.
+type Storage = PaddedStrBuf<{MAX_TAG_LEN + 4}>;
+///Length of weakness indicator `W/`
+const WEAK_PREFIX_LEN: usize = 2;
+///Separator between API version and the rest of versioned tag.
+const VERSION_SEP: char = ':';
+///Placeholder for weakness indicator, used by strong tags.
+const STRONG_PREFIX: &str = "  ";
 
 /// An entity tag, defined in [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3)
 ///
@@ -45,7 +174,10 @@ type Buffer = str_buf::StrBuf::<62>;
 /// # Size limit
 ///
 /// In order to avoid allocation, ETag size is limited to 62 characters, which should be sufficient
-/// for any hashing mechanism.
+/// for most hashing mechanisms. Limit is available as `EntityTag::MAX_TAG_LEN`.
+///
+/// Features `buffer-128` and `buffer-256` increase the limit to 124 and 251 characters respectively,
+/// enlarging `EntityTag` accordingly.
 ///
 /// # Format `W/"<etag_value>"`
 ///
@@ -60,6 +192,13 @@ type Buffer = str_buf::StrBuf::<62>;
 /// modification timestamp, or just a revision number is used. For example, MDN uses a hash of
 /// hexadecimal digits of the wiki content.
 ///
+/// # Display
+///
+/// Tag is displayed using its header representation.
+/// Alternate form `{:#}` omits DQUOTEs, e.g. `W/675af34563dc-tr34`.
+///
+/// Either form is emitted with a single `write_str` call.
+///
 /// # Comparison
 /// To check if two entity tags are equivalent in an application always use the
 /// `strong_eq` or `weak_eq` methods based on the context of the Tag. Only use
@@ -74,114 +213,368 @@ type Buffer = str_buf::StrBuf::<62>;
 /// | `W/"1"` | `W/"2"` | no match          | no match        |
 /// | `W/"1"` | `"1"`   | no match          | match           |
 /// | `"1"`   | `"1"`   | match             | match           |
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::doc_lazy_continuation)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct EntityTag {
-    /// Weakness indicator for the tag
-    pub weak: bool,
-    /// The opaque string in between the DQUOTEs
-    tag: Buffer,
+    /// Header representation of the tag, prefixed with `W/` for weak tags and with placeholder
+    /// of the same length for strong tags.
+    ///
+    /// Prefix is skipped for strong tags, which allows to avoid formatting on every use.
+    /// It also serves as weakness indicator, while length is determined by zero padding,
+    /// so the tag takes exactly `MAX_TAG_LEN + 4` bytes.
+    tag: Storage,
 }
 
 impl EntityTag {
-    /// Constructs a new EntityTag, asserting that it doesn't overflow and valid ASCII string.
+    /// Maximum length of opaque tag.
+    pub const MAX_TAG_LEN: usize = MAX_TAG_LEN;
+    /// Maximum number of bytes, that can be stored using `from_binary_tag`.
+    pub const MAX_BINARY_LEN: usize = MAX_TAG_LEN * 3 / 4;
+
+    /// Wildcard `*`, matching any current representation in `If-Match` and `If-None-Match` headers.
     ///
-    /// Assertions are performed in debug mode only.
-    pub fn new(weak: bool, tag: &str) -> Self {
-        let mut result = Self {
-            weak,
-            tag: Buffer::new(),
+    /// It is distinct from strong tag `"*"`, and has empty opaque tag.
+    pub const ANY: Self = {
+        let mut storage = [0; MAX_TAG_LEN + 4];
+        storage[0] = b' ';
+        storage[1] = b' ';
+        storage[2] = b'*';
+
+        Self {
+            tag: Storage::from_storage(storage)
+        }
+    };
+
+    #[inline]
+    ///Writes header representation of opaque tag, truncating it if necessary.
+    const fn from_tag(weak: bool, tag: &str) -> Self {
+        let tag = tag.as_bytes();
+        let mut len = match tag.len() > MAX_TAG_LEN {
+            true => MAX_TAG_LEN,
+            false => tag.len(),
+        };
+        //Skip UTF-8 continuation bytes to stay on char boundary
+        while len < tag.len() && (tag[len] & 0xC0) == 0x80 {
+            len -= 1;
+        }
+
+        let prefix = match weak {
+            true => "W/".as_bytes(),
+            false => STRONG_PREFIX.as_bytes(),
         };
+        let mut storage = [0; MAX_TAG_LEN + 4];
+        storage[0] = prefix[0];
+        storage[1] = prefix[1];
+        storage[2] = b'"';
+        let mut idx = 0;
+        while idx < len {
+            storage[WEAK_PREFIX_LEN + 1 + idx] = tag[idx];
+            idx += 1;
+        }
+        storage[WEAK_PREFIX_LEN + 1 + len] = b'"';
+
+        Self {
+            //Storage is filled with prefix and tag, truncated on char boundary
+            tag: Storage::from_storage(storage)
+        }
+    }
+
+    #[inline]
+    ///Creates tag from header representation, prefixed with `W/` or placeholder.
+    fn from_header(header: &str) -> Self {
+        let mut tag = Storage::from_storage([0; MAX_TAG_LEN + 4]);
+        tag.push_str(header);
+        Self {
+            tag
+        }
+    }
 
+    /// Constructs a new EntityTag, asserting that it doesn't overflow and valid ASCII string.
+    ///
+    /// Assertions are performed in debug mode only, while in release mode tag is truncated to `MAX_TAG_LEN`.
+    /// Caller is responsible for validity of the tag, use `checked_new` for panic-free construction.
+    ///
+    /// Being `const`, it can be used to define tags of static resources:
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// static TAGS: [EntityTag; 2] = [EntityTag::strong_unchecked("index"), EntityTag::weak_unchecked("style")];
+    /// assert_eq!(TAGS[0].as_header_str(), "\"index\"");
+    /// ```
+    pub const fn new_unchecked(weak: bool, tag: &str) -> Self {
         debug_assert!(tag.is_ascii());
-        let written = result.tag.push_str(tag);
-        debug_assert_eq!(written, tag.len());
-        result
+        debug_assert!(tag.len() <= MAX_TAG_LEN);
+        Self::from_tag(weak, tag)
     }
 
     #[inline]
-    /// Constructs a new weak EntityTag, using the same checks as `new`.
-    pub fn weak(tag: &str) -> Self {
-        Self::new(true, tag)
+    /// Constructs a new weak EntityTag, using the same checks as `new_unchecked`.
+    pub const fn weak_unchecked(tag: &str) -> Self {
+        Self::new_unchecked(true, tag)
     }
 
     #[inline]
-    /// Constructs a new strong EntityTag, using the same checks as `new`.
-    pub fn strong(tag: &str) -> Self {
-        Self::new(false, tag)
+    /// Constructs a new strong EntityTag, using the same checks as `new_unchecked`.
+    pub const fn strong_unchecked(tag: &str) -> Self {
+        Self::new_unchecked(false, tag)
     }
 
-    /// Constructs a new EntityTag, verifying it's size and whether it includes ASCII.
-    pub fn checked_new(weak: bool, tag: &str) -> Result<Self, ParseError> {
-        if tag.is_ascii() {
-            let mut result = Self {
-                weak,
-                tag: Buffer::new(),
-            };
+    #[inline]
+    #[deprecated(note = "Use `checked_new` or, if tag is known to be valid, `new_unchecked`")]
+    /// Constructs a new EntityTag, same as `new_unchecked`.
+    pub const fn new(weak: bool, tag: &str) -> Self {
+        Self::new_unchecked(weak, tag)
+    }
 
-            match result.tag.push_str(tag) == tag.len() {
-                true => Ok(result),
-                false => Err(ParseError::Overflow)
-            }
-        } else {
+    #[inline]
+    #[deprecated(note = "Use `checked_weak` or, if tag is known to be valid, `weak_unchecked`")]
+    /// Constructs a new weak EntityTag, same as `weak_unchecked`.
+    pub const fn weak(tag: &str) -> Self {
+        Self::new_unchecked(true, tag)
+    }
+
+    #[inline]
+    #[deprecated(note = "Use `checked_strong` or, if tag is known to be valid, `strong_unchecked`")]
+    /// Constructs a new strong EntityTag, same as `strong_unchecked`.
+    pub const fn strong(tag: &str) -> Self {
+        Self::new_unchecked(false, tag)
+    }
+
+    /// Constructs a new EntityTag, verifying it's size and whether it includes ASCII.
+    ///
+    /// Tag must consist of `etagc` characters only, i.e. it cannot contain DQUOTE, whitespace or
+    /// control characters, otherwise `ParseError::InvalidFormat` is returned.
+    ///
+    /// In `const` context it allows to reject invalid tag at compile time:
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// const TAG: EntityTag = match EntityTag::checked_strong("index") {
+    ///     Ok(tag) => tag,
+    ///     Err(_) => panic!("Invalid tag"),
+    /// };
+    /// assert_eq!(TAG.tag(), "index");
+    /// ```
+    pub const fn checked_new(weak: bool, tag: &str) -> Result<Self, ParseError> {
+        if !tag.is_ascii() {
             Err(ParseError::NotAscii)
+        } else if tag.len() > MAX_TAG_LEN {
+            Err(ParseError::Overflow)
+        } else if !etagc::is_valid(tag) {
+            Err(ParseError::InvalidFormat)
+        } else {
+            Ok(Self::from_tag(weak, tag))
         }
     }
 
     #[inline]
     /// Constructs a new weak EntityTag, using the same checks as `checked_new`.
-    pub fn checked_weak(tag: &str) -> Result<Self, ParseError> {
+    pub const fn checked_weak(tag: &str) -> Result<Self, ParseError> {
         Self::checked_new(true, tag)
     }
 
     #[inline]
     /// Constructs a new strong EntityTag, using the same checks as `checked_new`.
-    pub fn checked_strong(tag: &str) -> Result<Self, ParseError> {
+    pub const fn checked_strong(tag: &str) -> Result<Self, ParseError> {
         Self::checked_new(false, tag)
     }
 
+    #[inline]
+    /// Parses header representation of the tag, ignoring leading and trailing whitespace (spaces and tabs).
+    ///
+    /// `FromStr` is strict and rejects such input.
+    pub fn parse_trimmed(text: &str) -> Result<Self, ParseError> {
+        text.trim_matches(|ch| ch == ' ' || ch == '\t').parse()
+    }
+
     #[cfg(feature = "std")]
     /// Creates weak EntityTag from file metadata using modified time and len.
     ///
     /// ## Format:
     ///
     /// `[modified-]<len>`
+    ///
+    /// ## Panics
+    ///
+    /// If modified time is earlier than `UNIX_EPOCH`, use `try_from_file_meta` to handle it.
+    #[deprecated(note = "Use `try_from_file_meta`, which doesn't panic on modified time earlier than `UNIX_EPOCH`")]
     pub fn from_file_meta(metadata: &std::fs::Metadata) -> Self {
+        Self::try_from_file_meta(metadata).expect("Modified is earlier than time::UNIX_EPOCH!")
+    }
+
+    #[cfg(feature = "std")]
+    /// Creates weak EntityTag from file metadata, in the same format as `from_file_meta`.
+    ///
+    /// Returns `None` if modified time is earlier than `UNIX_EPOCH`.
+    pub fn try_from_file_meta(metadata: &std::fs::Metadata) -> Option<Self> {
         let mut tag = Buffer::new();
-        let _ = match metadata.modified().map(|modified| modified.duration_since(std::time::UNIX_EPOCH).expect("Modified is earlier than time::UNIX_EPOCH!")) {
-            Ok(modified) => write!(tag, "{}.{}-{}", modified.as_secs(), modified.subsec_nanos(), metadata.len()),
-            _ => write!(tag, "{}", metadata.len())
+        tag.push_str("W/\"");
+        //Longest output is `u64.u32-u64`, which fits into the smallest buffer.
+        let _ = match metadata.modified() {
+            Ok(modified) => {
+                let modified = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+                write!(tag, "{}.{}-{}", modified.as_secs(), modified.subsec_nanos(), metadata.len())
+            },
+            Err(_) => write!(tag, "{}", metadata.len())
         };
+        tag.push_str("\"");
 
-        Self {
-            weak: true,
-            tag
+        Some(Self::from_header(&tag))
+    }
+
+    #[cfg(feature = "std")]
+    /// Creates strong EntityTag by hashing content of multiple files, in order of `paths`.
+    ///
+    /// Intended for bundles, served as concatenation or archive of several files.
+    ///
+    /// ## Format:
+    ///
+    /// `<len>-<hash>`, where `len` is total length of all files and `hash` is XXH3-128 over
+    /// length (`u64`) and XXH3-128 of content (`u128`) of each file, both little-endian.
+    ///
+    /// Hence result depends on order of files and on how their content is split between them,
+    /// i.e. it is different from tag of concatenated content as produced by `from_data`.
+    ///
+    /// Always uses XXH3, regardless of algorithm selected by `hash-*` feature.
+    pub fn from_files<P: AsRef<std::path::Path>, I: IntoIterator<Item = P>>(paths: I) -> std::io::Result<Self> {
+        use std::io::Read;
+
+        let mut buffer = [0u8; 8 * 1024];
+        let mut total_len = 0u64;
+        let mut bundle = xxhash_rust::xxh3::Xxh3::new();
+
+        for path in paths {
+            let mut file = std::fs::File::open(path)?;
+            let mut len = 0u64;
+            let mut content = xxhash_rust::xxh3::Xxh3::new();
+            loop {
+                let size = match file.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(size) => size,
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(error),
+                };
+                content.update(&buffer[..size]);
+                len += size as u64;
+            }
+
+            bundle.update(&len.to_le_bytes());
+            bundle.update(&content.digest128().to_le_bytes());
+            total_len += len;
         }
+
+        Ok(Self::from_len_hash(total_len, bundle.digest128()))
     }
 
-    /// Creates strong EntityTag by hashing provided bytes.
+    /// Creates EntityTag, using `Display` output of `value` as opaque tag.
+    ///
+    /// Performs the same checks as `checked_new`, returning `ParseError::Overflow` instead of truncating
+    /// output that doesn't fit.
+    ///
+    /// ```rust
+    /// use etag::{EntityTag, ParseError};
+    ///
+    /// assert_eq!(EntityTag::try_from_display(false, &42).unwrap().as_header_str(), "\"42\"");
+    /// assert_eq!(EntityTag::try_from_display(true, &"a b"), Err(ParseError::InvalidFormat));
+    /// ```
+    pub fn try_from_display<T: fmt::Display + ?Sized>(weak: bool, value: &T) -> Result<Self, ParseError> {
+        struct Writer {
+            tag: EntityTag,
+            error: Option<ParseError>,
+        }
+
+        impl fmt::Write for Writer {
+            #[inline]
+            fn write_str(&mut self, text: &str) -> fmt::Result {
+                self.tag.try_push_str(text).map_err(|error| {
+                    self.error = Some(error);
+                    fmt::Error
+                })
+            }
+        }
+
+        let mut writer = Writer {
+            tag: Self::from_tag(weak, ""),
+            error: None,
+        };
+        match write!(writer, "{}", value) {
+            Ok(()) => Ok(writer.tag),
+            //Error of `Display` implementation itself means output is incomplete.
+            Err(_) => Err(writer.error.unwrap_or(ParseError::InvalidFormat)),
+        }
+    }
+
+    /// Creates strong EntityTag by hashing provided bytes, producing the same tag as `from_data`.
     ///
     /// ## Format:
     ///
     /// `<len>-<hash>`
     pub const fn const_from_data(bytes: &[u8]) -> Self {
+        Self::from_len_hash(bytes.len() as u64, hash::const_content(bytes))
+    }
+
+    #[inline]
+    /// Creates strong EntityTag by hashing provided bytes with 64-bit FNV-1a.
+    ///
+    /// ## Format:
+    ///
+    /// `<len>-<hash>`
+    pub const fn const_from_data_fnv1a(bytes: &[u8]) -> Self {
+        Self::from_len_hash(bytes.len() as u64, hash::fnv1a_64(bytes) as u128)
+    }
+
+    #[inline]
+    /// Creates strong EntityTag by hashing provided bytes with 64-bit wyhash, seeded with zero.
+    ///
+    /// ## Format:
+    ///
+    /// `<len>-<hash>`
+    pub const fn const_from_data_wyhash(bytes: &[u8]) -> Self {
+        Self::from_len_hash(bytes.len() as u64, hash::wyhash(bytes, 0) as u128)
+    }
+
+    #[inline]
+    /// Creates strong EntityTag by hashing provided bytes with CRC-32 (ISO-HDLC, as used by zlib).
+    ///
+    /// ## Format:
+    ///
+    /// `<len>-<hash>`
+    pub const fn const_from_data_crc32(bytes: &[u8]) -> Self {
+        Self::from_len_hash(bytes.len() as u64, hash::crc32(bytes) as u128)
+    }
+
+    #[inline]
+    /// Creates strong EntityTag from CRC-32 and length of content, which are already known.
+    ///
+    /// It is the same as `const_from_data_crc32` of content, e.g. archive member with stored checksum.
+    pub const fn from_crc32(crc32: u32, len: u64) -> Self {
+        Self::from_len_hash(len, crc32 as u128)
+    }
+
+    ///Writes strong tag `<len>-<hash>`.
+    pub(crate) const fn from_len_hash(mut bytes_len: u64, mut hash: u128) -> Self {
         const SEP: u8 = b'-';
-        let mut bytes_len = bytes.len() as u64;
-        let mut hash = xxhash_rust::const_xxh3::xxh3_128(bytes);
 
-        let mut storage_len = 0;
-        let mut storage = [mem::MaybeUninit::<u8>::uninit(); 62];
+        let mut storage = [0; MAX_TAG_LEN + 4];
+        storage[0] = b' ';
+        storage[1] = b' ';
+        storage[2] = b'"';
+        let mut storage_len = 3;
+        let first_part_cursor = storage_len;
         while bytes_len > 9 {
             let digit = bytes_len % 10;
             bytes_len = bytes_len / 10;
-            storage[storage_len] = mem::MaybeUninit::new(b'0' + digit as u8);
+            storage[storage_len] = b'0' + digit as u8;
 
             storage_len += 1;
         }
 
-        storage[storage_len] = mem::MaybeUninit::new(b'0' + (bytes_len % 10) as u8);
+        storage[storage_len] = b'0' + (bytes_len % 10) as u8;
         storage_len += 1;
 
-        let mut idx = 0;
+        let mut idx = first_part_cursor;
         let mut storage_end = storage_len - 1;
         while idx < storage_end {
             let temp = storage[idx];
@@ -191,22 +584,22 @@ impl EntityTag {
             storage_end -= 1;
         }
 
-        storage[storage_len] = mem::MaybeUninit::new(SEP);
+        storage[storage_len] = SEP;
         storage_len += 1;
 
-        let first_part_cursor = storage_len;
+        let second_part_cursor = storage_len;
 
         while hash > 9 {
             let digit = hash % 10;
             hash = hash / 10;
-            storage[storage_len] = mem::MaybeUninit::new(b'0' + digit as u8);
+            storage[storage_len] = b'0' + digit as u8;
 
             storage_len += 1;
         }
-        storage[storage_len] = mem::MaybeUninit::new(b'0' + (hash % 10) as u8);
+        storage[storage_len] = b'0' + (hash % 10) as u8;
         storage_len += 1;
 
-        idx = first_part_cursor;
+        idx = second_part_cursor;
         storage_end = storage_len - 1;
         while idx < storage_end {
             let temp = storage[idx];
@@ -216,46 +609,669 @@ impl EntityTag {
             storage_end -= 1;
         }
 
+        storage[storage_len] = b'"';
+
         Self {
-            weak: false,
-            tag: unsafe {
-                Buffer::from_storage(storage, storage_len as u8)
-            }
+            tag: Storage::from_storage(storage)
         }
     }
 
     /// Creates strong EntityTag by hashing provided bytes.
     ///
+    /// Uses 128-bit `xxh3`, unless other algorithm is selected by `hash-*` feature.
+    ///
     /// ## Format:
     ///
     /// `<len>-<hash>`
     pub fn from_data(bytes: &[u8]) -> Self {
-        let hash = xxhash_rust::xxh3::xxh3_128(bytes);
+        let hash = hash::content(bytes);
         let mut tag = Buffer::new();
-        let _ = write!(tag, "{}-{}", bytes.len(), hash);
+        let _ = write!(tag, "{}\"{}-{}\"", STRONG_PREFIX, bytes.len(), hash);
 
-        Self {
-            weak: false,
-            tag
+        Self::from_header(&tag)
+    }
+
+    #[inline]
+    /// Creates strong EntityTag by hashing provided bytes with default instance of `H`.
+    ///
+    /// Allows to match fingerprints, produced elsewhere with the same algorithm.
+    /// Bytes are passed to `Hasher::write` as they are, without length prefix of `Hash` implementation.
+    ///
+    /// ## Format:
+    ///
+    /// `<len>-<hash>`, where hash is result of `Hasher::finish`.
+    pub fn from_data_with<H: core::hash::Hasher + Default>(bytes: &[u8]) -> Self {
+        Self::from_data_hasher(bytes, H::default())
+    }
+
+    /// Creates strong EntityTag by hashing provided bytes with `hasher`, e.g. created by `BuildHasher`.
+    ///
+    /// Uses the same format as `from_data_with`.
+    ///
+    /// ```rust
+    /// use core::hash::BuildHasher;
+    /// use std::collections::hash_map::RandomState;
+    /// use etag::EntityTag;
+    ///
+    /// let state = RandomState::new();
+    /// let tag = EntityTag::from_data_hasher(b"content", state.build_hasher());
+    /// assert_eq!(tag, EntityTag::from_data_hasher(b"content", state.build_hasher()));
+    /// ```
+    pub fn from_data_hasher<H: core::hash::Hasher>(bytes: &[u8], mut hasher: H) -> Self {
+        hasher.write(bytes);
+        Self::from_len_hash(bytes.len() as u64, hasher.finish() as u128)
+    }
+
+    /// Appends `text` to the opaque tag.
+    ///
+    /// Performs the same checks as `checked_new`, leaving the tag unchanged on error.
+    ///
+    /// ```rust
+    /// use core::fmt::Write;
+    /// use etag::EntityTag;
+    ///
+    /// let mut tag = EntityTag::strong_unchecked("");
+    /// tag.try_push_str("sha-").unwrap();
+    /// write!(tag, "{:x}", 0xdeadbeefu32).unwrap();
+    /// assert_eq!(tag.as_header_str(), "\"sha-deadbeef\"");
+    /// ```
+    ///
+    /// `EntityTag::ANY` cannot be extended, returning `ParseError::InvalidFormat`.
+    pub fn try_push_str(&mut self, text: &str) -> Result<(), ParseError> {
+        if self.is_any() {
+            Err(ParseError::InvalidFormat)
+        } else if !text.is_ascii() {
+            Err(ParseError::NotAscii)
+        } else if text.len() > self.remaining_capacity() {
+            Err(ParseError::Overflow)
+        } else if !etagc::is_valid_simd(text) {
+            Err(ParseError::InvalidFormat)
+        } else {
+            self.tag.truncate(self.tag.len() - 1);
+            self.tag.push_str(text);
+            self.tag.push_str("\"");
+            Ok(())
+        }
+    }
+
+    /// Creates tag `<api_version>:<base>`, preserving weakness of `base`.
+    ///
+    /// Changing version invalidates all tags, produced for previous version, even if content is the same.
+    ///
+    /// Version must be non-empty and cannot contain `:`, otherwise `ParseError::InvalidFormat` is returned,
+    /// which is also returned for `EntityTag::ANY`.
+    pub fn versioned(api_version: &str, base: &EntityTag) -> Result<Self, ParseError> {
+        if base.is_any() || api_version.is_empty() || api_version.contains(VERSION_SEP) {
+            return Err(ParseError::InvalidFormat);
+        }
+
+        let mut result = Self::checked_new(base.is_weak(), api_version)?;
+        let mut sep = [0u8; 4];
+        result.try_push_str(VERSION_SEP.encode_utf8(&mut sep))?;
+        result.try_push_str(base.tag())?;
+        Ok(result)
+    }
+
+    /// Splits tag, created by `versioned`, into API version and base tag.
+    ///
+    /// Returns `None` if tag has no version.
+    pub fn split_version(&self) -> Option<(&str, EntityTag)> {
+        match self.tag().split_once(VERSION_SEP) {
+            Some((version, base)) if !version.is_empty() => Some((version, Self::from_tag(self.is_weak(), base))),
+            _ => None,
+        }
+    }
+
+    /// Creates tag from arbitrary `text`, percent-encoding characters not allowed in opaque tag and `%`.
+    ///
+    /// Non-ASCII characters are encoded as sequence of their UTF-8 bytes, taking 3 characters per byte.
+    /// Returns `ParseError::Overflow` if encoded text doesn't fit.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let tag = EntityTag::percent_encoded(false, "user \"Åsa\"").unwrap();
+    /// assert_eq!(tag.as_header_str(), "\"user%20%22%C3%85sa%22\"");
+    /// assert_eq!(tag.percent_decoded().unwrap(), "user \"Åsa\"");
+    /// ```
+    pub fn percent_encoded(weak: bool, text: &str) -> Result<Self, ParseError> {
+        let mut result = Self::from_tag(weak, "");
+        let mut start = 0;
+        for (idx, byte) in text.bytes().enumerate() {
+            if byte == b'%' || !etagc::is_valid_byte(byte) {
+                //Non-empty run consists of ASCII characters only, hence both of its ends are on char boundary.
+                if start < idx {
+                    result.try_push_str(&text[start..idx])?;
+                }
+                write!(result, "%{:02X}", byte).map_err(|_| ParseError::Overflow)?;
+                start = idx + 1;
+            }
+        }
+        result.try_push_str(&text[start..])?;
+        Ok(result)
+    }
+
+    /// Decodes opaque tag, created by `percent_encoded`.
+    ///
+    /// Returns `ParseError::InvalidFormat` if tag contains invalid escape sequence, or decoded bytes are not valid UTF-8.
+    pub fn percent_decoded(&self) -> Result<HeaderBuffer, ParseError> {
+        #[inline(always)]
+        fn hex_value(byte: u8) -> Option<u8> {
+            match byte {
+                b'0'..=b'9' => Some(byte - b'0'),
+                b'a'..=b'f' => Some(byte - b'a' + 10),
+                b'A'..=b'F' => Some(byte - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let tag = self.tag().as_bytes();
+        let mut decoded = [0u8; MAX_TAG_LEN];
+        let mut len = 0;
+        let mut idx = 0;
+        while idx < tag.len() {
+            decoded[len] = match tag[idx] {
+                b'%' => match (tag.get(idx + 1).copied().and_then(hex_value), tag.get(idx + 2).copied().and_then(hex_value)) {
+                    (Some(high), Some(low)) => {
+                        idx += 2;
+                        high << 4 | low
+                    },
+                    _ => return Err(ParseError::InvalidFormat),
+                },
+                byte => byte,
+            };
+            len += 1;
+            idx += 1;
+        }
+
+        match core::str::from_utf8(&decoded[..len]) {
+            Ok(text) => {
+                let mut result = HeaderBuffer::new();
+                result.push_str(text);
+                Ok(result)
+            },
+            Err(_) => Err(ParseError::InvalidFormat),
+        }
+    }
+
+    /// Creates strong tag from arbitrary `bytes`, encoded using unpadded base64url.
+    ///
+    /// Returns `ParseError::Overflow` if `bytes` are longer than `EntityTag::MAX_BINARY_LEN`.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let tag = EntityTag::from_binary_tag(&[0xfb, 0xff, 0x01]).unwrap();
+    /// assert_eq!(tag.as_header_str(), "\"-_8B\"");
+    ///
+    /// let mut digest = [0u8; EntityTag::MAX_BINARY_LEN];
+    /// let len = tag.binary_tag(&mut digest).unwrap();
+    /// assert_eq!(&digest[..len], [0xfb, 0xff, 0x01]);
+    /// ```
+    pub fn from_binary_tag(bytes: &[u8]) -> Result<Self, ParseError> {
+        match bytes.len() > Self::MAX_BINARY_LEN {
+            true => Err(ParseError::Overflow),
+            false => Ok(Self::encode_binary_tag(bytes)),
+        }
+    }
+
+    ///Encodes `bytes`, which must not exceed `MAX_BINARY_LEN`, using unpadded base64url.
+    fn encode_binary_tag(bytes: &[u8]) -> Self {
+        const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+        let mut tag = Buffer::new();
+        tag.push_str(STRONG_PREFIX);
+        tag.push_str("\"");
+        let mut utf8 = [0u8; 4];
+        for chunk in bytes.chunks(3) {
+            let group = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+            //Each byte of the chunk is spread over at least one character, and the first one always needs two
+            for idx in 0..chunk.len() + 1 {
+                let digit = ALPHABET[(group >> (18 - 6 * idx) & 0x3F) as usize];
+                tag.push_str(char::from(digit).encode_utf8(&mut utf8));
+            }
+        }
+        tag.push_str("\"");
+
+        Self::from_header(&tag)
+    }
+
+    #[cfg(feature = "getrandom")]
+    /// Creates strong EntityTag from `len` random bytes, encoded as `from_binary_tag` does.
+    ///
+    /// Intended for resources, which change on every write, where hashing content is pointless.
+    ///
+    /// `len` is silently capped at `EntityTag::MAX_BINARY_LEN`, hence larger `len` produces tag
+    /// of `MAX_BINARY_LEN` random bytes.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let tag = EntityTag::random_strong(16).unwrap();
+    /// assert_eq!(tag.len(), 22);
+    /// assert_ne!(tag, EntityTag::random_strong(16).unwrap());
+    /// ```
+    pub fn random_strong(len: usize) -> Result<Self, getrandom::Error> {
+        let mut bytes = [0u8; Self::MAX_BINARY_LEN];
+        let bytes = &mut bytes[..core::cmp::min(len, Self::MAX_BINARY_LEN)];
+        getrandom::fill(bytes)?;
+        Ok(Self::encode_binary_tag(bytes))
+    }
+
+    #[cfg(feature = "ulid")]
+    /// Creates strong EntityTag from canonical 26 characters representation of `ulid`.
+    ///
+    /// As ULID starts with timestamp, such tags are sorted by their creation time.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    /// use ulid::Ulid;
+    ///
+    /// let ulid = Ulid::from_parts(1_700_000_000_000, 42);
+    /// let tag = EntityTag::from_ulid(&ulid);
+    /// assert_eq!(tag.as_header_str(), "\"01HF7YAT00000000000000001A\"");
+    /// assert_eq!(tag.to_ulid(), Some(ulid));
+    /// ```
+    pub fn from_ulid(ulid: &ulid::Ulid) -> Self {
+        let mut buffer = [0u8; ulid::ULID_LEN];
+        Self::from_tag(false, ulid.array_to_str(&mut buffer))
+    }
+
+    #[cfg(all(feature = "ulid", feature = "std"))]
+    #[inline]
+    /// Creates strong EntityTag from new ULID, generated using current time.
+    pub fn new_ulid() -> Self {
+        Self::from_ulid(&ulid::Ulid::new())
+    }
+
+    #[cfg(feature = "ulid")]
+    /// Parses opaque tag as ULID, returning `None` if it is not valid ULID or `EntityTag::ANY`.
+    ///
+    /// Weakness of the tag is ignored.
+    pub fn to_ulid(&self) -> Option<ulid::Ulid> {
+        match self.is_any() {
+            true => None,
+            false => ulid::Ulid::from_string(self.tag()).ok(),
+        }
+    }
+
+    #[cfg(feature = "signed")]
+    /// Creates strong tag `<value>.<signature>`, where signature is HMAC-SHA256 of `value` with `key`,
+    /// truncated to 96 bits and encoded as unpadded base64url.
+    ///
+    /// Allows to reject tags, forged by client, before looking them up, which is useful when `value`
+    /// carries data such as row id or version.
+    ///
+    /// Performs the same checks as `checked_new` on `value`, which must leave room for 17 characters of signature.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let tag = EntityTag::signed("row-42.v7", b"secret").unwrap();
+    /// assert_eq!(tag.verify_signature(b"secret"), Some("row-42.v7"));
+    /// assert_eq!(tag.verify_signature(b"other"), None);
+    /// assert_eq!(EntityTag::strong_unchecked("row-42.v7").verify_signature(b"secret"), None);
+    /// ```
+    pub fn signed(value: &str, key: &[u8]) -> Result<Self, ParseError> {
+        let mut result = Self::checked_strong(value)?;
+        let signature = signature(value, key).ok_or(ParseError::InvalidFormat)?;
+        result.try_push_str(".")?;
+        result.try_push_str(signature.tag())?;
+        Ok(result)
+    }
+
+    #[cfg(feature = "signed")]
+    /// Verifies signature of tag, created by `signed`, returning signed value if signature is valid.
+    ///
+    /// Weakness is ignored, as intermediaries may weaken tags, e.g. when compressing response.
+    /// Signatures are compared in constant time.
+    pub fn verify_signature(&self, key: &[u8]) -> Option<&str> {
+        let (value, actual) = self.tag().rsplit_once('.')?;
+        let expected = signature(value, key)?;
+        let expected = expected.tag();
+        let diff = expected.bytes().zip(actual.bytes()).fold(0, |diff, (expected, actual)| diff | (expected ^ actual));
+
+        match diff == 0 && expected.len() == actual.len() {
+            true => Some(value),
+            false => None,
+        }
+    }
+
+    /// Decodes opaque tag, created by `from_binary_tag`, into `out`, returning number of written bytes.
+    ///
+    /// Buffer of `EntityTag::MAX_BINARY_LEN` bytes is always sufficient.
+    /// Returns `ParseError::InvalidFormat` if tag is not valid unpadded base64url,
+    /// or `ParseError::Overflow` if `out` is not large enough.
+    pub fn binary_tag(&self, out: &mut [u8]) -> Result<usize, ParseError> {
+        #[inline(always)]
+        fn sextet(byte: u8) -> Result<u32, ParseError> {
+            match byte {
+                b'A'..=b'Z' => Ok((byte - b'A') as u32),
+                b'a'..=b'z' => Ok((byte - b'a' + 26) as u32),
+                b'0'..=b'9' => Ok((byte - b'0' + 52) as u32),
+                b'-' => Ok(62),
+                b'_' => Ok(63),
+                _ => Err(ParseError::InvalidFormat),
+            }
+        }
+
+        let tag = self.tag().as_bytes();
+        if self.is_any() || tag.len() % 4 == 1 {
+            return Err(ParseError::InvalidFormat);
+        }
+
+        let mut len = 0;
+        for chunk in tag.chunks(4) {
+            let mut group = 0;
+            for (idx, byte) in chunk.iter().enumerate() {
+                group |= sextet(*byte)? << (18 - 6 * idx);
+            }
+
+            let bytes = group.to_be_bytes();
+            let size = chunk.len() - 1;
+            //Unused bits of the last character must be zero, so each binary tag has single representation
+            if bytes[1 + size..].iter().any(|byte| *byte != 0) {
+                return Err(ParseError::InvalidFormat);
+            }
+            match out.get_mut(len..len + size) {
+                Some(out) => out.copy_from_slice(&bytes[1..1 + size]),
+                None => return Err(ParseError::Overflow),
+            }
+            len += size;
+        }
+
+        Ok(len)
+    }
+
+    /// Creates weak tag, containing only first `len` characters of the opaque tag.
+    ///
+    /// Intended for logging, where full validator should not be stored.
+    /// `EntityTag::ANY` is returned as it is.
+    pub fn truncated(&self, len: usize) -> Self {
+        if self.is_any() {
+            return Self::ANY;
+        }
+
+        let tag = self.tag();
+        let mut len = core::cmp::min(len, tag.len());
+        while !tag.is_char_boundary(len) {
+            len -= 1;
+        }
+        Self::from_tag(true, &tag[..len])
+    }
+
+    /// Splits tag in format `<len>-<hash>`, as produced by `from_data` and its `const` variants,
+    /// into content length and hash.
+    ///
+    /// Allows to reject content of mismatching length without hashing it.
+    ///
+    /// Returns `None` if tag uses different format.
+    pub fn content_components(&self) -> Option<(u64, u128)> {
+        let (len, hash) = self.tag().split_once('-')?;
+        Some((parse_digits(len)?, parse_digits(hash)?))
+    }
+
+    /// Returns strong tag of `bytes` if they match content, described by this tag.
+    ///
+    /// Tag must be in format `<len>-<hash>`, as produced by `from_data`, with either weakness.
+    /// Allows to issue weak tags while content is not verified, and promote them to strong once
+    /// content is known to be the same.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let mut issued = EntityTag::from_data(b"content");
+    /// issued.set_weak(true);
+    ///
+    /// assert_eq!(issued.promote_if_matches(b"content"), Some(EntityTag::from_data(b"content")));
+    /// assert_eq!(issued.promote_if_matches(b"changed"), None);
+    /// ```
+    pub fn promote_if_matches(&self, bytes: &[u8]) -> Option<EntityTag> {
+        let (len, hash) = self.content_components()?;
+        match len == bytes.len() as u64 && hash == hash::content(bytes) {
+            true => Some(Self::from_len_hash(len, hash)),
+            false => None,
+        }
+    }
+
+    #[inline]
+    /// Returns opaque tag as integer, if it is decimal number that fits `u64`.
+    ///
+    /// Weakness of the tag is ignored.
+    pub fn as_u64(&self) -> Option<u64> {
+        parse_digits(self.tag())
+    }
+
+    #[inline]
+    /// Returns opaque tag as integer, if it is decimal number that fits `u128`.
+    ///
+    /// Weakness of the tag is ignored.
+    pub fn as_u128(&self) -> Option<u128> {
+        parse_digits(self.tag())
+    }
+
+    /// Creates tag with the next value of decimal counter, preserving weakness.
+    ///
+    /// Counter is not limited to any integer type, and its width grows as necessary, while leading
+    /// zeros are preserved otherwise.
+    /// Returns `BumpError::Overflow` if the counter already takes `MAX_TAG_LEN` digits and its width has to grow.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// assert_eq!(EntityTag::strong_unchecked("41").bump(), Ok(EntityTag::strong_unchecked("42")));
+    /// assert_eq!(EntityTag::weak_unchecked("0099").bump(), Ok(EntityTag::weak_unchecked("0100")));
+    /// assert_eq!(EntityTag::strong_unchecked("999").bump(), Ok(EntityTag::strong_unchecked("1000")));
+    /// ```
+    pub fn bump(&self) -> Result<EntityTag, BumpError> {
+        let tag = self.tag().as_bytes();
+        if self.is_any() || tag.is_empty() || !tag.iter().all(u8::is_ascii_digit) {
+            return Err(BumpError::NotNumeric);
+        }
+
+        //Reserve first byte for carry
+        let mut counter = [b'1'; MAX_TAG_LEN + 1];
+        counter[1..=tag.len()].copy_from_slice(tag);
+        let mut idx = tag.len();
+        loop {
+            match counter[idx] {
+                b'9' => counter[idx] = b'0',
+                _ => {
+                    counter[idx] += 1;
+                    break;
+                }
+            }
+            idx -= 1;
+            if idx == 0 {
+                break;
+            }
+        }
+
+        let counter = match idx {
+            0 if tag.len() == MAX_TAG_LEN => return Err(BumpError::Overflow),
+            0 => &counter[..=tag.len()],
+            _ => &counter[1..=tag.len()],
+        };
+        match core::str::from_utf8(counter) {
+            Ok(counter) => Ok(Self::from_tag(self.is_weak(), counter)),
+            Err(_) => Err(BumpError::NotNumeric),
+        }
+    }
+
+    #[inline]
+    /// Returns hash of content, as returned by `content_components`, formatted as lowercase hexadecimal.
+    ///
+    /// Leading zeros are omitted, use `content_components` with `{:032x}` for fixed width output.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let tag = EntityTag::const_from_data_crc32(b"123456789");
+    /// assert_eq!(tag.hash_hex().unwrap(), "cbf43926");
+    /// assert_eq!(EntityTag::strong_unchecked("v1").hash_hex(), None);
+    /// ```
+    pub fn hash_hex(&self) -> Option<StrBuf<32>> {
+        let mut result = StrBuf::new();
+        let _ = write!(result, "{:x}", self.content_components()?.1);
+        Some(result)
+    }
+
+    #[inline]
+    /// Returns hash of content, as returned by `content_components`, formatted as uppercase hexadecimal.
+    ///
+    /// Leading zeros are omitted, use `content_components` with `{:032X}` for fixed width output.
+    pub fn hash_hex_upper(&self) -> Option<StrBuf<32>> {
+        let mut result = StrBuf::new();
+        let _ = write!(result, "{:X}", self.content_components()?.1);
+        Some(result)
+    }
+
+    #[inline]
+    /// Returns whether the tag is weak.
+    pub fn is_weak(&self) -> bool {
+        self.tag.as_bytes()[0] == b'W'
+    }
+
+    #[inline]
+    /// Returns whether the tag is wildcard `EntityTag::ANY`.
+    pub const fn is_any(&self) -> bool {
+        self.tag.len() == WEAK_PREFIX_LEN + 1
+    }
+
+    /// Changes weakness of the tag.
+    ///
+    /// Does nothing for `EntityTag::ANY`.
+    pub fn set_weak(&mut self, weak: bool) {
+        if !self.is_any() && self.is_weak() != weak {
+            *self = Self::from_tag(weak, self.clone().tag());
         }
     }
 
     /// Get the tag.
+    ///
+    /// Returns empty string for `EntityTag::ANY`.
     pub fn tag(&self) -> &str {
-        self.tag.as_str()
+        let tag = self.tag.as_str();
+        match self.is_any() {
+            true => "",
+            false => &tag[WEAK_PREFIX_LEN + 1..tag.len() - 1],
+        }
+    }
+
+    #[inline]
+    /// Returns length of the opaque tag, excluding weakness indicator and DQUOTEs.
+    pub fn len(&self) -> usize {
+        self.tag().len()
+    }
+
+    #[inline]
+    /// Returns whether the opaque tag is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    /// Returns number of characters that can still be added to the opaque tag.
+    pub fn remaining_capacity(&self) -> usize {
+        MAX_TAG_LEN - self.len()
+    }
+
+    /// Get the header representation of the tag, i.e. `W/"<tag>"`, `"<tag>"` or `*`.
+    ///
+    /// It is the same as `Display` output, but requires no formatting.
+    pub fn as_header_str(&self) -> &str {
+        match self.is_weak() {
+            true => self.tag.as_str(),
+            false => &self.tag.as_str()[WEAK_PREFIX_LEN..],
+        }
+    }
+
+    /// Get copy of the header representation of the tag, without need for allocation.
+    pub fn display_buffer(&self) -> HeaderBuffer {
+        let mut result = HeaderBuffer::new();
+        result.push_str(self.as_header_str());
+        result
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    /// Returns copy of the opaque tag.
+    pub fn tag_string(&self) -> alloc::string::String {
+        self.tag().into()
+    }
+
+    /// Splits the tag into weakness indicator and buffer, holding opaque tag.
+    ///
+    /// `EntityTag::ANY` is split into `(false, "")`, so it should be checked using `is_any` beforehand.
+    pub fn into_parts(self) -> (bool, TagBuffer) {
+        let mut tag = TagBuffer::new();
+        tag.push_str(self.tag());
+        (self.is_weak(), tag)
+    }
+
+    #[inline]
+    /// Creates tag from parts, produced by `into_parts`.
+    ///
+    /// Opaque tag is not validated, performing the same checks as `new_unchecked`,
+    /// while `TagBuffer` guarantees that it never exceeds `MAX_TAG_LEN`.
+    pub fn from_parts(weak: bool, tag: TagBuffer) -> Self {
+        Self::new_unchecked(weak, tag.as_str())
+    }
+
+    /// Writes header representation of the tag into provided buffer, returning number of written bytes.
+    ///
+    /// Returns `ParseError::Overflow` if buffer is not large enough, in which case nothing is written.
+    pub fn write_header(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        let header = self.as_header_str().as_bytes();
+        match buf.get_mut(..header.len()) {
+            Some(buf) => {
+                buf.copy_from_slice(header);
+                Ok(header.len())
+            },
+            None => Err(ParseError::Overflow),
+        }
+    }
+
+    #[inline]
+    /// Writes header representation of the tag into `out`, the same as `Display` output.
+    ///
+    /// Performs single `write_str` call, without going through formatting machinery.
+    ///
+    /// ```rust
+    /// use etag::{EntityTag, StrBuf};
+    ///
+    /// let mut response = StrBuf::<32>::new();
+    /// response.push_str("ETag: ");
+    /// EntityTag::weak_unchecked("v1").write_to(&mut response).unwrap();
+    /// assert_eq!(response, "ETag: W/\"v1\"");
+    /// ```
+    pub fn write_to<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        out.write_str(self.as_header_str())
     }
 
     /// For strong comparison two entity-tags are equivalent if both are not
     /// weak and their opaque-tags match character-by-character.
+    ///
+    /// `EntityTag::ANY` is only equivalent to itself.
     pub fn strong_eq(&self, other: &EntityTag) -> bool {
-        !self.weak && !other.weak && self.tag.as_str() == other.tag.as_str()
+        !self.is_weak() && !other.is_weak() && etagc::eq(self.quoted_bytes(), other.quoted_bytes())
     }
 
     /// For weak comparison two entity-tags are equivalent if their
     /// opaque-tags match character-by-character, regardless of either or
     /// both being tagged as "weak".
+    ///
+    /// `EntityTag::ANY` is only equivalent to itself.
     pub fn weak_eq(&self, other: &EntityTag) -> bool {
-        self.tag.as_str() == other.tag.as_str()
+        etagc::eq(self.quoted_bytes(), other.quoted_bytes())
+    }
+
+    #[inline(always)]
+    ///Returns header representation without weakness prefix.
+    ///
+    ///`EntityTag::ANY` is stored as `*`, hence it is distinct from any quoted opaque tag.
+    fn quoted_bytes(&self) -> &[u8] {
+        &self.tag.as_bytes()[WEAK_PREFIX_LEN..]
     }
 
     /// The inverse of `EntityTag.strong_eq()`.
@@ -267,22 +1283,372 @@ impl EntityTag {
     pub fn weak_ne(&self, other: &EntityTag) -> bool {
         !self.weak_eq(other)
     }
+
+    #[inline]
+    /// Returns index of first tag in `candidates`, which is equivalent using strong comparison.
+    ///
+    /// ```rust
+    /// use etag::EntityTag;
+    ///
+    /// let candidates = [EntityTag::weak_unchecked("v1"), EntityTag::strong_unchecked("v1")];
+    /// assert_eq!(EntityTag::strong_unchecked("v1").any_strong_match(&candidates), Some(1));
+    /// assert_eq!(EntityTag::weak_unchecked("v1").any_strong_match(&candidates), None);
+    /// ```
+    pub fn any_strong_match(&self, candidates: &[EntityTag]) -> Option<usize> {
+        self.any_match_with(candidates, Comparison::Strong)
+    }
+
+    #[inline]
+    /// Returns index of first tag in `candidates`, which is equivalent using weak comparison.
+    pub fn any_weak_match(&self, candidates: &[EntityTag]) -> Option<usize> {
+        self.any_match_with(candidates, Comparison::Weak)
+    }
+
+    /// Returns index of first tag in `candidates`, which is equivalent using specified comparison.
+    ///
+    /// Unlike header matching, `EntityTag::ANY` is not expanded and is only equivalent to itself.
+    pub fn any_match_with(&self, candidates: &[EntityTag], comparison: Comparison) -> Option<usize> {
+        candidates.iter().position(|candidate| comparison.matches(self, candidate))
+    }
+
+    /// Checks whether value of `If-None-Match` header matches the tag.
+    ///
+    /// Header is comma separated list of tags or `*`, which matches any tag.
+    /// As per [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.2) weak comparison is used.
+    ///
+    /// Invalid members of the list never match.
+    pub fn matches_if_none_match(&self, header: &str) -> bool {
+        IfNoneMatch::new(header).contains_weak(self)
+    }
+
+    /// Checks whether value of `If-Match` header matches the tag.
+    ///
+    /// Header is comma separated list of tags or `*`, which matches any tag.
+    /// As per [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.1) strong comparison is used.
+    ///
+    /// Invalid members of the list never match.
+    pub fn matches_if_match(&self, header: &str) -> bool {
+        if list::is_any(header) {
+            return true;
+        }
+
+        !self.is_weak() && !self.is_any() && list::Members::new(header).filter_map(list::split_tag).any(|(weak, tag)| !weak && self.tag() == tag)
+    }
 }
 
+///Writes header representation, or unquoted tag with alternate flag `{:#}`.
+///
+///Width, fill, alignment and precision are applied as for `str`, e.g. `{:>20}`.
 impl fmt::Display for EntityTag {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.weak {
-            f.write_str("W/")?;
+        if f.alternate() {
+            match self.is_weak() {
+                true => {
+                    let mut result = HeaderBuffer::new();
+                    result.push_str("W/");
+                    result.push_str(self.tag());
+                    f.pad(result.as_str())
+                },
+                false if self.is_any() => f.pad(self.as_header_str()),
+                false => f.pad(self.tag()),
+            }
+        } else {
+            f.pad(self.as_header_str())
+        }
+    }
+}
+
+///Appends to the opaque tag using `try_push_str`.
+///
+///Formatting of multiple pieces may fail after some of them were already appended.
+impl Write for EntityTag {
+    #[inline]
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.try_push_str(text).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+///Copies header representation of the tag, the same as `Display` output.
+impl From<&EntityTag> for alloc::string::String {
+    #[inline(always)]
+    fn from(tag: &EntityTag) -> Self {
+        tag.as_header_str().into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<EntityTag> for alloc::string::String {
+    #[inline(always)]
+    fn from(tag: EntityTag) -> Self {
+        Self::from(&tag)
+    }
+}
+
+///Debug output is not considered stable.
+impl fmt::Debug for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_any() {
+            return f.write_str("EntityTag(*)");
+        }
+
+        let strength = match self.is_weak() {
+            true => "weak",
+            false => "strong",
+        };
+        write!(f, "EntityTag({}, {:?})", strength, self.tag())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for EntityTag {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{=str}", self.as_header_str())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for EntityTag {
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self.as_header_str())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for EntityTag {
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        if self.is_any() {
+            return f.write_str("EntityTag(*)");
+        }
+
+        let strength = match self.is_weak() {
+            true => "weak",
+            false => "strong",
+        };
+        ufmt::uwrite!(f, "EntityTag({}, \"{}\")", strength, self.tag())
+    }
+}
+
+/// Determines whether `GET` or `HEAD` request should be responded with `304 Not Modified`.
+///
+/// Applies weak comparison against `If-None-Match` header, with `*` matching any tag.
+/// Missing or empty header never matches.
+#[inline]
+pub fn not_modified(current: &EntityTag, if_none_match: Option<&str>) -> bool {
+    match if_none_match {
+        Some(if_none_match) => current.matches_if_none_match(if_none_match),
+        None => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Outcome of `Range` request evaluation.
+pub enum RangeDecision {
+    /// Requested range can be served with `206 Partial Content`.
+    Partial,
+    /// Range must be ignored, and full representation served with `200 OK`.
+    Full,
+}
+
+/// Determines whether `Range` request can be responded with part of representation identified by `current`.
+///
+/// Parts can only be combined when they come from the same representation, which requires strong
+/// validator, hence weak `current` always results in `RangeDecision::Full`.
+///
+/// `If-Range` is evaluated as defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.1.5):
+/// tag matches using strong comparison only, while date or invalid value never matches, as `current`
+/// carries no modification date.
+pub fn validate_range_request(current: &EntityTag, if_range: Option<&str>) -> RangeDecision {
+    if current.is_weak() || current.is_any() {
+        return RangeDecision::Full;
+    }
+
+    let matches = match if_range {
+        Some(if_range) => match EntityTag::parse_trimmed(if_range) {
+            Ok(tag) => !tag.is_any() && current.strong_eq(&tag),
+            Err(_) => false,
+        },
+        None => true,
+    };
+
+    match matches {
+        true => RangeDecision::Partial,
+        false => RangeDecision::Full,
+    }
+}
+
+/// Computes composite key of cached response, identified by `url`, optional `variant` (e.g. value
+/// of headers listed in `Vary`) and its tag.
+///
+/// Key is 128-bit `xxh3` hash of length-prefixed components, making it stable across processes and
+/// platforms. Weak and strong tags with the same opaque tag produce different keys.
+pub fn cache_key(url: &str, variant: Option<&str>, tag: &EntityTag) -> [u8; 16] {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    hasher.update(&(url.len() as u64).to_le_bytes());
+    hasher.update(url.as_bytes());
+    match variant {
+        Some(variant) => {
+            hasher.update(&[1]);
+            hasher.update(&(variant.len() as u64).to_le_bytes());
+            hasher.update(variant.as_bytes());
+        },
+        None => hasher.update(&[0]),
+    }
+
+    let kind = match (tag.is_any(), tag.is_weak()) {
+        (true, _) => 2u8,
+        (false, weak) => weak as u8,
+    };
+    hasher.update(&[kind]);
+    hasher.update(&(tag.len() as u64).to_le_bytes());
+    hasher.update(tag.tag().as_bytes());
+    hasher.digest128().to_le_bytes()
+}
+
+#[doc(hidden)]
+///Hasher of `etag_from_fields!`, hashing `Display` output of each field, followed by its length.
+pub struct FieldHasher {
+    hasher: xxhash_rust::xxh3::Xxh3,
+    field_len: u64,
+}
+
+impl FieldHasher {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            hasher: xxhash_rust::xxh3::Xxh3::new(),
+            field_len: 0,
         }
+    }
+
+    #[inline]
+    pub fn field<T: fmt::Display + ?Sized>(&mut self, value: &T) {
+        self.field_len = 0;
+        let _ = write!(self, "{}", value);
+        let field_len = self.field_len.to_le_bytes();
+        self.hasher.update(&field_len);
+    }
+
+    #[inline]
+    pub fn finish(&self) -> EntityTag {
+        let mut tag = EntityTag::strong_unchecked("");
+        let _ = write!(tag, "{:032x}", self.hasher.digest128());
+        tag
+    }
+}
+
+impl Default for FieldHasher {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for FieldHasher {
+    #[inline]
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.hasher.update(text.as_bytes());
+        self.field_len += text.len() as u64;
+        Ok(())
+    }
+}
 
-        f.write_char('"')?;
-        f.write_str(self.tag.as_str())?;
-        f.write_char('"')
+///Parses non-empty decimal number, without sign.
+fn parse_digits<T: core::str::FromStr>(text: &str) -> Option<T> {
+    match !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => text.parse().ok(),
+        false => None,
     }
 }
 
+#[cfg(feature = "signed")]
+///Returns HMAC-SHA256 of `value`, truncated to 96 bits, as tag encoded by `from_binary_tag`.
+fn signature(value: &str, key: &[u8]) -> Option<EntityTag> {
+    use hmac::{KeyInit, Mac};
+
+    //HMAC accepts keys of any length.
+    let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(key).ok()?;
+    mac.update(value.as_bytes());
+    #[allow(unused_mut)]
+    let mut digest = mac.finalize().into_bytes();
+    let result = EntityTag::from_binary_tag(&digest[..12]).ok();
+    //Truncated part of digest is never published.
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut digest[..]);
+    result
+}
+
+#[macro_export]
+/// Creates strong `EntityTag` by hashing `Display` output of each field.
+///
+/// Fields are hashed together with length of their output, so moving characters between adjacent
+/// fields changes the tag. Tag consists of 32 hexadecimal digits.
+///
+/// ```rust
+/// use etag::etag_from_fields;
+///
+/// struct User {
+///     id: u64,
+///     updated_at: u64,
+///     role: &'static str,
+/// }
+///
+/// let user = User { id: 1, updated_at: 1700000000, role: "admin" };
+/// let tag = etag_from_fields!(user.id, user.updated_at, user.role);
+/// assert_eq!(tag, etag_from_fields!(1, 1700000000, "admin"));
+/// assert_ne!(tag, etag_from_fields!(user.id, user.updated_at, "user"));
+/// ```
+macro_rules! etag_from_fields {
+    ($($field:expr),+ $(,)?) => {{
+        let mut hasher = $crate::FieldHasher::new();
+        $(
+            hasher.field(&$field);
+        )+
+        hasher.finish()
+    }};
+}
+
+#[macro_export]
+/// Creates `EntityTag` from string literal, failing compilation if tag is invalid or doesn't fit.
+///
+/// Weak tag is created using `W/` prefix.
+///
+/// ```rust
+/// use etag::{static_etag, EntityTag};
+///
+/// static INDEX: EntityTag = static_etag!("index-v1");
+/// assert_eq!(INDEX, EntityTag::strong_unchecked("index-v1"));
+/// assert_eq!(static_etag!(W/"index-v1"), EntityTag::weak_unchecked("index-v1"));
+/// ```
+///
+/// ```compile_fail
+/// let tag = etag::static_etag!("index v1");
+/// ```
+///
+/// ```compile_fail
+/// let tag = etag::static_etag!("01234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789");
+/// ```
+macro_rules! static_etag {
+    (@new $weak:literal, $tag:literal) => {{
+        const TAG: $crate::EntityTag = match $crate::EntityTag::checked_new($weak, $tag) {
+            Ok(tag) => tag,
+            Err($crate::ParseError::Overflow) => panic!("Tag exceeds EntityTag::MAX_TAG_LEN"),
+            Err($crate::ParseError::NotAscii) => panic!("Tag contains non-ASCII characters"),
+            Err($crate::ParseError::InvalidFormat) => panic!("Tag contains characters not allowed in opaque tag"),
+        };
+        TAG
+    }};
+    (W/$tag:literal) => {
+        $crate::static_etag!(@new true, $tag)
+    };
+    ($tag:literal) => {
+        $crate::static_etag!(@new false, $tag)
+    };
+}
+
 ///Describes possible errors for EntityTag
 #[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ParseError {
     ///Format of EntityTag is invalid
     InvalidFormat,
@@ -292,36 +1658,165 @@ pub enum ParseError {
     Overflow,
 }
 
+impl ParseError {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            ParseError::InvalidFormat => "EntityTag uses invalid format",
+            ParseError::NotAscii => "EntityTag uses non-ASCII characters",
+            ParseError::Overflow => "EntityTag size overflows buffer",
+        }
+    }
+}
+
 impl fmt::Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for ParseError {
+    #[inline]
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for ParseError {
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            ParseError::InvalidFormat => f.write_str("InvalidFormat"),
+            ParseError::NotAscii => f.write_str("NotAscii"),
+            ParseError::Overflow => f.write_str("Overflow"),
+        }
+    }
+}
+
+///Error of `EntityTag::bump`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BumpError {
+    ///Opaque tag is not decimal counter.
+    NotNumeric,
+    ///Next value of the counter doesn't fit `MAX_TAG_LEN`.
+    Overflow,
+}
+
+impl fmt::Display for BumpError {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ParseError::InvalidFormat => f.write_str("EntityTag uses invalid format"),
-            ParseError::NotAscii => f.write_str("EntityTag uses non-ASCII characters"),
-            ParseError::Overflow => f.write_str("EntityTag size overflows buffer"),
+            BumpError::NotNumeric => f.write_str("EntityTag is not decimal counter"),
+            BumpError::Overflow => f.write_str("EntityTag counter overflows buffer"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+///Converts into error of kind `InvalidData`, carrying description of the error.
+impl From<ParseError> for std::io::Error {
+    #[inline]
+    fn from(error: ParseError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error.as_str())
+    }
+}
+
+///Parses `entity-tag = [ weak ] opaque-tag`, where `weak = %s"W/"` and `opaque-tag = DQUOTE *etagc DQUOTE`,
+///or wildcard `*` if `allow_any` is set.
+///
+///Malformed framing is reported as `ParseError::InvalidFormat` right away, while for opaque tag
+///`ParseError::NotAscii` takes precedence over `ParseError::Overflow`, which takes precedence over invalid characters.
+pub(crate) fn parse_entity_tag(text: &str, allow_any: bool) -> Result<EntityTag, ParseError> {
+    #[derive(Clone, Copy)]
+    enum State {
+        Start,
+        Any,
+        Weak,
+        WeakSlash,
+        Opaque {
+            weak: bool,
+            start: usize,
+        },
+        Closed {
+            weak: bool,
+            start: usize,
+            end: usize,
+        },
+    }
+
+    let mut state = State::Start;
+    let mut not_ascii = false;
+    let mut invalid_byte = false;
+
+    for (idx, byte) in text.bytes().enumerate() {
+        state = match (state, byte) {
+            (State::Start, b'*') if allow_any => State::Any,
+            (State::Start, b'W') => State::Weak,
+            (State::Start, b'"') => State::Opaque { weak: false, start: idx + 1 },
+            (State::Weak, b'/') => State::WeakSlash,
+            (State::WeakSlash, b'"') => State::Opaque { weak: true, start: idx + 1 },
+            (State::Opaque { weak, start }, b'"') => State::Closed { weak, start, end: idx },
+            (State::Opaque { .. }, byte) => {
+                if !byte.is_ascii() {
+                    not_ascii = true;
+                } else if !etagc::is_valid_byte(byte) {
+                    invalid_byte = true;
+                }
+                state
+            },
+            //Trailing data after closing DQUOTE or wildcard, or unexpected byte of prefix.
+            _ => return Err(ParseError::InvalidFormat),
         }
     }
+
+    match state {
+        State::Any => Ok(EntityTag::ANY),
+        State::Closed { weak, start, end } => if not_ascii {
+            Err(ParseError::NotAscii)
+        } else if end - start > MAX_TAG_LEN {
+            Err(ParseError::Overflow)
+        } else if invalid_byte {
+            Err(ParseError::InvalidFormat)
+        } else {
+            Ok(EntityTag::from_tag(weak, &text[start..end]))
+        },
+        _ => Err(ParseError::InvalidFormat),
+    }
 }
 
+///Parses header representation of the tag.
+///
+///Wildcard `*` is not an entity-tag and is rejected, it is only accepted as member of `If-Match`
+///and `If-None-Match` lists, such as by `parse_list`.
 impl core::str::FromStr for EntityTag {
     type Err = ParseError;
 
+    #[inline]
     fn from_str(text: &str) -> Result<EntityTag, ParseError> {
-        let len = text.len();
-        let slice = &text[..];
+        parse_entity_tag(text, false)
+    }
+}
 
-        if !slice.ends_with('"') || len < 2 {
-            return Err(ParseError::InvalidFormat);
-        }
+///Parses header representation of the tag, the same as `FromStr`.
+impl core::convert::TryFrom<&str> for EntityTag {
+    type Error = ParseError;
 
-        if slice.starts_with('"') {
-            let slice = &slice[1..len-1];
-            EntityTag::checked_strong(slice)
-        } else if len >= 4 && slice.starts_with("W/\"") {
-            let slice = &slice[3..len-1];
-            EntityTag::checked_weak(slice)
-        } else {
-            Err(ParseError::InvalidFormat)
-        }
+    #[inline(always)]
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+///Parses header representation of the tag, the same as `FromStr`.
+impl core::convert::TryFrom<alloc::string::String> for EntityTag {
+    type Error = ParseError;
+
+    #[inline(always)]
+    fn try_from(text: alloc::string::String) -> Result<Self, Self::Error> {
+        text.parse()
     }
 }
 
@@ -330,13 +1825,16 @@ mod tests {
     use super::{EntityTag, Buffer};
 
     #[test]
+    #[allow(clippy::legacy_numeric_constants)]
     fn assert_buffer_fits() {
-        assert_eq!(core::mem::size_of::<EntityTag>(), 64);
-        let expected = std::format!("{0}.{0}-{0}", u64::max_value());
+        assert_eq!(core::mem::size_of::<EntityTag>(), super::MAX_TAG_LEN + 4);
+        #[cfg(not(any(feature = "buffer-128", feature = "buffer-256")))]
+        assert!(core::mem::size_of::<EntityTag>() <= 66);
+        let expected = std::format!("W/\"{0}.{0}-{0}\"", u64::max_value());
         let res = Buffer::from_str_checked(&expected).expect("To fit");
-        assert_eq!(expected.as_str(), res);
-        let expected = std::format!("{0}-{1}", u64::max_value(), u128::max_value());
+        assert_eq!(expected.as_str(), res.as_str());
+        let expected = std::format!("W/\"{0}-{1}\"", u64::max_value(), u128::max_value());
         let res = Buffer::from_str_checked(&expected).expect("To fit");
-        assert_eq!(expected.as_str(), res);
+        assert_eq!(expected.as_str(), res.as_str());
     }
 }
@@ -0,0 +1,518 @@
+//! Entity tag list, as used by `If-Match` and `If-None-Match` headers.
+
+use crate::{EntityTag, ParseError};
+
+///Iterator over members of comma separated list of entity tags.
+///
+///Commas within DQUOTEs are considered part of opaque tag.
+///Follows `#rule` of RFC7230: optional whitespace around members and empty members are ignored.
+#[derive(Clone)]
+pub(crate) struct Members<'a> {
+    text: &'a str,
+}
+
+impl<'a> Members<'a> {
+    #[inline]
+    pub(crate) const fn new(text: &'a str) -> Self {
+        Self {
+            text
+        }
+    }
+}
+
+impl<'a> Iterator for Members<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.text.is_empty() {
+            let member = self.next_member();
+            if !member.is_empty() {
+                return Some(member);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> Members<'a> {
+    fn next_member(&mut self) -> &'a str {
+        let mut in_quotes = false;
+        let mut end = self.text.len();
+        for (idx, byte) in self.text.bytes().enumerate() {
+            match byte {
+                b'"' => in_quotes = !in_quotes,
+                b',' if !in_quotes => {
+                    end = idx;
+                    break;
+                },
+                _ => (),
+            }
+        }
+
+        let member = &self.text[..end];
+        self.text = match end == self.text.len() {
+            true => "",
+            false => &self.text[end+1..],
+        };
+
+        member.trim_matches(is_ows)
+    }
+}
+
+#[inline]
+const fn is_ows(ch: char) -> bool {
+    ch == ' ' || ch == '\t'
+}
+
+///Splits entity tag into weakness indicator and opaque tag, without validating its content.
+pub(crate) fn split_tag(text: &str) -> Option<(bool, &str)> {
+    let len = text.len();
+
+    if len < 2 || !text.ends_with('"') {
+        None
+    } else if text.starts_with('"') {
+        Some((false, &text[1..len-1]))
+    } else if len >= 4 && text.starts_with("W/\"") {
+        Some((true, &text[3..len-1]))
+    } else {
+        None
+    }
+}
+
+#[inline]
+///Returns whether header value is the `*` wildcard.
+pub(crate) fn is_any(text: &str) -> bool {
+    text.trim_matches(is_ows) == "*"
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Comparison function of entity tags, as defined in [RFC7232](https://tools.ietf.org/html/rfc7232#section-2.3.2).
+pub enum Comparison {
+    /// Both tags must be strong, with identical opaque tags.
+    Strong,
+    /// Opaque tags must be identical, regardless of weakness.
+    Weak,
+}
+
+impl Comparison {
+    #[inline]
+    /// Compares tags.
+    pub fn matches(self, left: &EntityTag, right: &EntityTag) -> bool {
+        match self {
+            Comparison::Strong => left.strong_eq(right),
+            Comparison::Weak => left.weak_eq(right),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Value of `If-None-Match` header.
+///
+/// Members of the list are compared in place, without constructing tags, stopping at first match.
+pub enum IfNoneMatch<'a> {
+    /// `*`, matching any tag.
+    Any,
+    /// Comma separated list of tags.
+    List(&'a str),
+}
+
+impl<'a> IfNoneMatch<'a> {
+    #[inline]
+    /// Creates new instance from header value.
+    pub fn new(header: &'a str) -> Self {
+        match is_any(header) {
+            true => IfNoneMatch::Any,
+            false => IfNoneMatch::List(header),
+        }
+    }
+
+    #[inline]
+    /// Returns whether header is `*`.
+    pub fn is_any(&self) -> bool {
+        matches!(self, IfNoneMatch::Any)
+    }
+
+    #[inline]
+    /// Returns whether any member of the list matches `tag`, using specified comparison.
+    pub fn contains_with(&self, tag: &EntityTag, comparison: Comparison) -> bool {
+        match comparison {
+            Comparison::Strong => self.contains_strong(tag),
+            Comparison::Weak => self.contains_weak(tag),
+        }
+    }
+
+    /// Returns whether any member of the list matches `tag`, using weak comparison.
+    pub fn contains_weak(&self, tag: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::List(_) if tag.is_any() => false,
+            IfNoneMatch::List(header) => {
+                let expected = tag.tag();
+                Members::new(header).filter_map(split_tag).any(|(_, tag)| tag == expected)
+            }
+        }
+    }
+
+    /// Returns whether any member of the list matches `tag`, using strong comparison.
+    pub fn contains_strong(&self, tag: &EntityTag) -> bool {
+        match self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::List(_) if tag.is_weak() || tag.is_any() => false,
+            IfNoneMatch::List(header) => {
+                let expected = tag.tag();
+                Members::new(header).filter_map(split_tag).any(|(weak, tag)| !weak && tag == expected)
+            }
+        }
+    }
+}
+
+/// Iterator over comma separated list of tags, yielding result of parsing each member.
+///
+/// Invalid members do not affect the rest of the list, and their position can be determined
+/// using `Iterator::enumerate`.
+///
+/// Wildcard member `*` is yielded as `EntityTag::ANY`.
+///
+/// List follows `#rule` of [RFC7230](https://tools.ietf.org/html/rfc7230#section-7):
+/// optional whitespace (spaces and tabs) around commas is ignored, as well as empty members,
+/// hence `, "a" ,, "b",` yields two tags.
+#[derive(Clone)]
+pub struct TagIter<'a> {
+    members: Members<'a>,
+}
+
+impl<'a> TagIter<'a> {
+    #[inline]
+    /// Creates new instance from header value.
+    pub const fn new(header: &'a str) -> Self {
+        Self {
+            members: Members::new(header),
+        }
+    }
+
+    #[inline]
+    /// Turns iterator into one that skips invalid members.
+    pub fn skip_invalid(self) -> ValidTags<'a> {
+        ValidTags {
+            inner: self,
+        }
+    }
+}
+
+impl Iterator for TagIter<'_> {
+    type Item = Result<EntityTag, ParseError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.members.next().map(|member| crate::parse_entity_tag(member, true))
+    }
+}
+
+/// Iterator over valid members of comma separated list of tags.
+#[derive(Clone)]
+pub struct ValidTags<'a> {
+    inner: TagIter<'a>,
+}
+
+impl Iterator for ValidTags<'_> {
+    type Item = EntityTag;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().find_map(Result::ok)
+    }
+}
+
+#[inline]
+/// Parses comma separated list of tags, as used by `If-Match` and `If-None-Match` headers.
+pub const fn parse_list(header: &str) -> TagIter<'_> {
+    TagIter::new(header)
+}
+
+/// Parses comma separated list of up to `N` tags into array, returning it with number of parsed tags.
+///
+/// Tags occupy the first slots of array in order of the list, while the rest are `None`.
+/// Fails on first invalid member, or with `ParseError::Overflow` when list has more than `N` members.
+///
+/// ```rust
+/// use etag::EntityTag;
+///
+/// let (tags, len) = etag::parse_many::<4>("\"a\", W/\"b\"").unwrap();
+/// assert_eq!(len, 2);
+/// assert_eq!(tags[1], Some(EntityTag::weak_unchecked("b")));
+/// assert_eq!(tags[2], None);
+/// ```
+pub fn parse_many<const N: usize>(header: &str) -> Result<([Option<EntityTag>; N], usize), ParseError> {
+    const NONE: Option<EntityTag> = None;
+
+    let mut result = [NONE; N];
+    let mut len = 0;
+    for tag in parse_list(header) {
+        let tag = tag?;
+        match result.get_mut(len) {
+            Some(slot) => *slot = Some(tag),
+            None => return Err(ParseError::Overflow),
+        }
+        len += 1;
+    }
+
+    Ok((result, len))
+}
+
+/// Fixed capacity list of up to `N` tags, which doesn't require allocation.
+///
+/// ```rust
+/// use etag::{EntityTag, EntityTagList};
+///
+/// let list = EntityTagList::<4>::parse("\"a\", W/\"b\"").unwrap();
+/// assert_eq!(list.len(), 2);
+/// assert!(list.contains(&EntityTag::strong_unchecked("b")));
+/// ```
+#[derive(Clone)]
+pub struct EntityTagList<const N: usize> {
+    tags: [EntityTag; N],
+    len: usize,
+}
+
+impl<const N: usize> EntityTagList<N> {
+    #[inline]
+    /// Creates new empty list.
+    pub const fn new() -> Self {
+        Self {
+            tags: [EntityTag::ANY; N],
+            len: 0,
+        }
+    }
+
+    /// Parses comma separated list of tags.
+    ///
+    /// Fails on first invalid member, or with `ParseError::Overflow` when list has more than `N` members.
+    pub fn parse(header: &str) -> Result<Self, ParseError> {
+        let mut result = Self::new();
+        for tag in parse_list(header) {
+            if result.push(tag?).is_err() {
+                return Err(ParseError::Overflow);
+            }
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    /// Returns number of tags in the list.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    /// Returns whether list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Returns maximum number of tags in the list.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    /// Returns whether list reached its capacity.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Appends tag to the list, returning it back if list is full.
+    #[allow(clippy::result_large_err)]
+    pub fn push(&mut self, tag: EntityTag) -> Result<(), EntityTag> {
+        match self.tags.get_mut(self.len) {
+            Some(slot) => {
+                *slot = tag;
+                self.len += 1;
+                Ok(())
+            },
+            None => Err(tag),
+        }
+    }
+
+    #[inline]
+    /// Removes all tags from the list.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[inline]
+    /// Returns tags of the list.
+    pub fn as_slice(&self) -> &[EntityTag] {
+        &self.tags[..self.len]
+    }
+
+    #[inline]
+    /// Returns iterator over tags of the list.
+    pub fn iter(&self) -> core::slice::Iter<'_, EntityTag> {
+        self.as_slice().iter()
+    }
+
+    #[inline]
+    /// Returns whether any tag of the list matches `tag`, using weak comparison.
+    ///
+    /// `EntityTag::ANY` within the list matches any tag.
+    pub fn contains(&self, tag: &EntityTag) -> bool {
+        self.contains_with(tag, Comparison::Weak)
+    }
+
+    /// Returns whether any tag of the list matches `tag`, using specified comparison.
+    ///
+    /// `EntityTag::ANY` within the list matches any tag.
+    pub fn contains_with(&self, tag: &EntityTag, comparison: Comparison) -> bool {
+        self.iter().any(|member| member.is_any() || comparison.matches(member, tag))
+    }
+
+    /// Removes identical tags, keeping first occurrence of each tag.
+    pub fn dedup(&mut self) {
+        let mut len = 0;
+        for idx in 0..self.len {
+            if !self.tags[..len].contains(&self.tags[idx]) {
+                self.tags.swap(len, idx);
+                len += 1;
+            }
+        }
+        self.len = len;
+    }
+
+    /// Returns tags of the list, matching any tag of `other` using specified comparison.
+    pub fn intersection<const M: usize>(&self, other: &EntityTagList<M>, comparison: Comparison) -> Self {
+        self.filter(|tag| other.contains_with(tag, comparison))
+    }
+
+    /// Returns tags of the list, not matching any tag of `other` using specified comparison.
+    pub fn difference<const M: usize>(&self, other: &EntityTagList<M>, comparison: Comparison) -> Self {
+        self.filter(|tag| !other.contains_with(tag, comparison))
+    }
+
+    fn filter<F: Fn(&EntityTag) -> bool>(&self, predicate: F) -> Self {
+        let mut result = Self::new();
+        for tag in self.iter().filter(|tag| predicate(tag)) {
+            let _ = result.push(tag.clone());
+        }
+        result
+    }
+}
+
+impl<const N: usize> Default for EntityTagList<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> PartialEq for EntityTagList<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for EntityTagList<N> {}
+
+impl<'a, const N: usize> IntoIterator for &'a EntityTagList<N> {
+    type Item = &'a EntityTag;
+    type IntoIter = core::slice::Iter<'a, EntityTag>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+///Formats list as single header value, using `display_list`.
+impl<const N: usize> core::fmt::Display for EntityTagList<N> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&display_list(self), f)
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for EntityTagList<N> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats tags as single header value, such as `If-Match` or `If-None-Match`.
+///
+/// Tags are separated by `, `, unless any of them is `EntityTag::ANY`, in which case only `*` is written.
+///
+/// Created by `display_list`.
+#[derive(Clone)]
+pub struct DisplayList<I> {
+    tags: I,
+}
+
+impl<'a, I: IntoIterator<Item = &'a EntityTag> + Clone> core::fmt::Display for DisplayList<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.tags.clone().into_iter().any(EntityTag::is_any) {
+            return f.write_str(EntityTag::ANY.as_header_str());
+        }
+
+        for (idx, tag) in self.tags.clone().into_iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(tag.as_header_str())?;
+        }
+        Ok(())
+    }
+}
+
+#[inline]
+/// Creates formatter of tags as single header value.
+///
+/// ```rust
+/// use etag::{display_list, EntityTag};
+///
+/// let tags = [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b")];
+/// assert_eq!(display_list(&tags).to_string(), "\"a\", W/\"b\"");
+/// assert_eq!(display_list(&[EntityTag::strong_unchecked("a"), EntityTag::ANY]).to_string(), "*");
+/// ```
+pub fn display_list<'a, I: IntoIterator<Item = &'a EntityTag> + Clone>(tags: I) -> DisplayList<I> {
+    DisplayList {
+        tags,
+    }
+}
+
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl core::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, text: &str) -> core::fmt::Result {
+        match self.buf.get_mut(self.len..self.len + text.len()) {
+            Some(buf) => {
+                buf.copy_from_slice(text.as_bytes());
+                self.len += text.len();
+                Ok(())
+            },
+            None => Err(core::fmt::Error),
+        }
+    }
+}
+
+/// Writes tags as single header value into provided buffer, returning number of written bytes.
+///
+/// Uses the same format as `display_list`.
+/// Returns `ParseError::Overflow` if buffer is not large enough, in which case its content is unspecified.
+pub fn write_list<'a, I: IntoIterator<Item = &'a EntityTag> + Clone>(tags: I, buf: &mut [u8]) -> Result<usize, ParseError> {
+    use core::fmt::Write;
+
+    let mut writer = SliceWriter {
+        buf,
+        len: 0,
+    };
+    match write!(writer, "{}", display_list(tags)) {
+        Ok(()) => Ok(writer.len),
+        Err(_) => Err(ParseError::Overflow),
+    }
+}
@@ -0,0 +1,176 @@
+//! Evaluation of conditional request headers, as defined in [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.2).
+
+use std::time::SystemTime;
+
+use crate::{EntityTag, FieldLines};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Outcome of preconditions for state-changing methods, such as `PUT`, `PATCH` or `DELETE`.
+pub enum WriteDecision {
+    /// Request should be performed.
+    Proceed,
+    /// Request must be rejected with `412 Precondition Failed`.
+    PreconditionFailed,
+    /// Request must be rejected with `428 Precondition Required`.
+    PreconditionRequired,
+}
+
+/// Evaluates preconditions of state-changing request against current tag of the resource.
+///
+/// - `If-Match` is evaluated using strong comparison, and `*` matches as resource exists.
+/// - `If-Unmodified-Since` is only considered when `If-Match` is absent. As `current` carries no
+///   modification date, it is ignored as required by RFC, but still counts as precondition.
+/// - When `require_precondition` is set, request without any precondition is rejected with `428`,
+///   as defined in [RFC6585](https://www.rfc-editor.org/rfc/rfc6585#section-3).
+pub fn check_write(current: &EntityTag, if_match: Option<&str>, if_unmodified_since: Option<SystemTime>, require_precondition: bool) -> WriteDecision {
+    write_decision(if_match.map(|if_match| current.matches_if_match(if_match)), if_unmodified_since, require_precondition)
+}
+
+/// Evaluates preconditions of state-changing request, same as `check_write`, considering every field line of `If-Match`.
+///
+/// Invalid `If-Match`, e.g. with non-ASCII characters, is present, but never matches, so request is rejected with `412`.
+pub fn check_write_lines(current: &EntityTag, if_match: Option<FieldLines<'_>>, if_unmodified_since: Option<SystemTime>, require_precondition: bool) -> WriteDecision {
+    write_decision(if_match.map(|if_match| if_match.matches_if_match(Some(current))), if_unmodified_since, require_precondition)
+}
+
+fn write_decision(if_match: Option<bool>, if_unmodified_since: Option<SystemTime>, require_precondition: bool) -> WriteDecision {
+    match if_match {
+        Some(true) => WriteDecision::Proceed,
+        Some(false) => WriteDecision::PreconditionFailed,
+        None => match if_unmodified_since.is_none() && require_precondition {
+            true => WriteDecision::PreconditionRequired,
+            false => WriteDecision::Proceed,
+        },
+    }
+}
+
+#[inline]
+fn to_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(SystemTime::UNIX_EPOCH).ok().map(|time| time.as_secs())
+}
+
+//HTTP-date has granularity of seconds, hence sub-second part is ignored.
+fn is_modified_since(last_modified: SystemTime, date: SystemTime) -> bool {
+    match (to_secs(last_modified), to_secs(date)) {
+        (Some(last_modified), Some(date)) => last_modified > date,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Conditional headers of the request.
+pub struct RequestConditionals<'a> {
+    /// Whether request method is `GET` or `HEAD`.
+    pub is_get_or_head: bool,
+    /// Value of `If-Match` header.
+    pub if_match: Option<&'a str>,
+    /// Value of `If-None-Match` header.
+    pub if_none_match: Option<&'a str>,
+    /// Value of `If-Modified-Since` header.
+    pub if_modified_since: Option<SystemTime>,
+    /// Value of `If-Unmodified-Since` header.
+    pub if_unmodified_since: Option<SystemTime>,
+}
+
+#[cfg(feature = "httpdate")]
+impl RequestConditionals<'_> {
+    #[inline]
+    /// Sets `If-Modified-Since` from raw header value.
+    ///
+    /// Invalid date is ignored, as required by RFC.
+    pub fn with_if_modified_since(mut self, value: &str) -> Self {
+        self.if_modified_since = parse_http_date(value);
+        self
+    }
+
+    #[inline]
+    /// Sets `If-Unmodified-Since` from raw header value.
+    ///
+    /// Invalid date is ignored, as required by RFC.
+    pub fn with_if_unmodified_since(mut self, value: &str) -> Self {
+        self.if_unmodified_since = parse_http_date(value);
+        self
+    }
+}
+
+#[cfg(feature = "httpdate")]
+#[inline]
+/// Parses HTTP-date, accepting IMF-fixdate as well as obsolete formats.
+pub fn parse_http_date(value: &str) -> Option<SystemTime> {
+    httpdate::parse_http_date(value.trim()).ok()
+}
+
+#[cfg(feature = "httpdate")]
+#[inline]
+/// Formats time as IMF-fixdate, suitable for `Last-Modified` header.
+pub fn format_http_date(time: SystemTime) -> impl core::fmt::Display {
+    httpdate::HttpDate::from(time)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Outcome of evaluating request preconditions.
+pub enum Decision {
+    /// Request should be performed.
+    Proceed,
+    /// Request should be responded with `304 Not Modified`.
+    NotModified,
+    /// Request must be rejected with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Validators of the existing resource.
+pub struct ResourceValidator {
+    /// Current tag of the resource.
+    pub etag: Option<EntityTag>,
+    /// Last modification date of the resource.
+    pub last_modified: Option<SystemTime>,
+}
+
+impl ResourceValidator {
+    /// Evaluates request preconditions in order defined by [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2).
+    ///
+    /// Date based preconditions are only evaluated in absence of their tag based counterparts,
+    /// and only if resource has modification date.
+    pub fn evaluate(&self, request: &RequestConditionals<'_>) -> Decision {
+        let if_match = request.if_match.map(|if_match| match self.etag.as_ref() {
+            Some(etag) => etag.matches_if_match(if_match),
+            None => crate::list::is_any(if_match),
+        });
+        let if_none_match = request.if_none_match.map(|if_none_match| match self.etag.as_ref() {
+            Some(etag) => etag.matches_if_none_match(if_none_match),
+            None => crate::list::is_any(if_none_match),
+        });
+
+        self.evaluate_matches(request.is_get_or_head, if_match, if_none_match, request.if_modified_since, request.if_unmodified_since)
+    }
+
+    ///Evaluates preconditions, given whether present `If-Match` and `If-None-Match` match current tag.
+    pub(crate) fn evaluate_matches(&self, is_get_or_head: bool, if_match: Option<bool>, if_none_match: Option<bool>, if_modified_since: Option<SystemTime>, if_unmodified_since: Option<SystemTime>) -> Decision {
+        if let Some(is_match) = if_match {
+            if !is_match {
+                return Decision::PreconditionFailed;
+            }
+        } else if let (Some(date), Some(last_modified)) = (if_unmodified_since, self.last_modified) {
+            if is_modified_since(last_modified, date) {
+                return Decision::PreconditionFailed;
+            }
+        }
+
+        if let Some(is_match) = if_none_match {
+            if is_match {
+                return match is_get_or_head {
+                    true => Decision::NotModified,
+                    false => Decision::PreconditionFailed,
+                };
+            }
+        } else if let (true, Some(date), Some(last_modified)) = (is_get_or_head, if_modified_since, self.last_modified) {
+            if !is_modified_since(last_modified, date) {
+                return Decision::NotModified;
+            }
+        }
+
+        Decision::Proceed
+    }
+}
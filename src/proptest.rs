@@ -0,0 +1,66 @@
+//! proptest strategies.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use proptest::proptest;
+//! use etag::EntityTag;
+//!
+//! proptest! {
+//!     fn parse_header(header in etag::proptest::header()) {
+//!         assert!(header.parse::<EntityTag>().is_ok());
+//!     }
+//! }
+//!
+//! parse_header();
+//! ```
+
+use std::string::String;
+use std::format;
+
+use ::proptest::prelude::{Just, Strategy, prop_oneof};
+use ::proptest::string::string_regex;
+
+use crate::{EntityTag, MAX_TAG_LEN};
+
+///Generates valid opaque tags, i.e. content in between DQUOTEs.
+pub fn opaque_tag() -> impl Strategy<Value = String> {
+    string_regex(&format!("[\\x21\\x23-\\x7E]{{0,{}}}", MAX_TAG_LEN)).expect("Valid regex")
+}
+
+///Generates valid strong tags.
+pub fn strong_tag() -> impl Strategy<Value = EntityTag> {
+    opaque_tag().prop_map(|tag| EntityTag::strong_unchecked(&tag))
+}
+
+///Generates valid weak tags.
+pub fn weak_tag() -> impl Strategy<Value = EntityTag> {
+    opaque_tag().prop_map(|tag| EntityTag::weak_unchecked(&tag))
+}
+
+///Generates valid tags of any strength.
+pub fn entity_tag() -> impl Strategy<Value = EntityTag> {
+    prop_oneof![strong_tag(), weak_tag()]
+}
+
+///Generates valid header representation of tags.
+pub fn header() -> impl Strategy<Value = String> {
+    entity_tag().prop_map(|tag| format!("{}", tag))
+}
+
+///Generates header values that are close to valid tags, but are rejected by the parser.
+pub fn malformed_header() -> impl Strategy<Value = String> {
+    prop_oneof![
+        //Missing DQUOTEs
+        opaque_tag().prop_map(|tag| format!("\"{}", tag)),
+        opaque_tag().prop_map(|tag| format!("{}\"", tag)),
+        opaque_tag().prop_map(|tag| format!("W/{}", tag)),
+        //Case-sensitive weak indicator
+        opaque_tag().prop_map(|tag| format!("w/\"{}\"", tag)),
+        //Non-ASCII content
+        (opaque_tag(), "[^\\x00-\\x7F]{1,4}").prop_map(|(tag, non_ascii)| format!("\"{}{}\"", tag, non_ascii)),
+        //Overflow
+        string_regex(&format!("[\\x21\\x23-\\x7E]{{{},{}}}", MAX_TAG_LEN + 1, MAX_TAG_LEN + 16)).expect("Valid regex").prop_map(|tag| format!("\"{}\"", tag)),
+        Just(String::new()),
+    ]
+}
@@ -0,0 +1,77 @@
+//! Access to unparsed HTTP/1.1 messages.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//!
+//! let head = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\netag: W/\"v1\"\r\n\r\n{}";
+//! assert_eq!(etag::raw::find_etag(head), Some(EntityTag::weak_unchecked("v1")));
+//! ```
+
+use crate::EntityTag;
+
+#[inline(always)]
+fn trim_ows(mut value: &[u8]) -> &[u8] {
+    while let [b' ' | b'\t', rest @ ..] = value {
+        value = rest;
+    }
+    while let [rest @ .., b' ' | b'\t'] = value {
+        value = rest;
+    }
+    value
+}
+
+///Iterates over lines of the head, terminated by `\r\n` or `\n`, stopping at empty line.
+struct Lines<'a> {
+    head: &'a [u8],
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.head.iter().position(|byte| *byte == b'\n')?;
+        let line = &self.head[..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        self.head = &self.head[end + 1..];
+
+        match line.is_empty() {
+            true => {
+                self.head = &[];
+                None
+            },
+            false => Some(line),
+        }
+    }
+}
+
+/// Finds `ETag` header in `head` of HTTP/1.1 response.
+///
+/// `head` must start with status line, and may be followed by body, which is not inspected.
+/// Only complete lines are considered, so truncated head yields tags seen so far.
+///
+/// Returns `None` if there is no status line, header is absent or its value is not valid tag.
+pub fn find_etag(head: &[u8]) -> Option<EntityTag> {
+    let mut lines = Lines {
+        head,
+    };
+
+    if !lines.next()?.starts_with(b"HTTP/") {
+        return None;
+    }
+
+    for line in lines {
+        //Header name cannot contain whitespace, including before colon.
+        let colon = match line.iter().position(|byte| *byte == b':') {
+            Some(colon) => colon,
+            None => continue,
+        };
+        let (name, value) = (&line[..colon], &line[colon + 1..]);
+
+        if name.eq_ignore_ascii_case(b"etag") {
+            let value = core::str::from_utf8(trim_ows(value)).ok()?;
+            return value.parse().ok();
+        }
+    }
+
+    None
+}
@@ -0,0 +1,81 @@
+//! Optimistic concurrency, independent of storage layer.
+//!
+//! ```rust
+//! use core::fmt::Write;
+//! use etag::EntityTag;
+//! use etag::resource::{guard_update, VersionedResource};
+//!
+//! struct Document {
+//!     version: u64,
+//! }
+//!
+//! impl VersionedResource for Document {
+//!     fn current_etag(&self) -> EntityTag {
+//!         let mut tag = EntityTag::strong_unchecked("");
+//!         write!(tag, "{}", self.version).unwrap();
+//!         tag
+//!     }
+//! }
+//!
+//! let document = Document { version: 2 };
+//! assert!(guard_update(&document, Some("\"2\"")).is_ok());
+//! assert_eq!(guard_update(&document, Some("\"1\"")).unwrap_err().current, EntityTag::strong_unchecked("2"));
+//! ```
+
+use core::fmt;
+
+use crate::EntityTag;
+
+/// Resource, which current state is identified by tag.
+pub trait VersionedResource {
+    /// Returns tag of current state of the resource.
+    fn current_etag(&self) -> EntityTag;
+}
+
+impl VersionedResource for EntityTag {
+    #[inline(always)]
+    fn current_etag(&self) -> EntityTag {
+        self.clone()
+    }
+}
+
+impl<T: VersionedResource + ?Sized> VersionedResource for &T {
+    #[inline(always)]
+    fn current_etag(&self) -> EntityTag {
+        T::current_etag(self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error of failed `If-Match` precondition, which should be responded with `412 Precondition Failed`.
+pub struct PreconditionFailed {
+    /// Current tag of the resource.
+    pub current: EntityTag,
+}
+
+impl fmt::Display for PreconditionFailed {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Precondition failed, current tag is {}", self.current)
+    }
+}
+
+/// Checks whether `resource` can be updated by request with `If-Match` header.
+///
+/// Header is matched using strong comparison, with `*` matching any resource.
+/// Missing header doesn't restrict update.
+#[allow(clippy::result_large_err)]
+pub fn guard_update<R: VersionedResource + ?Sized>(resource: &R, if_match: Option<&str>) -> Result<(), PreconditionFailed> {
+    let if_match = match if_match {
+        Some(if_match) => if_match,
+        None => return Ok(()),
+    };
+
+    let current = resource.current_etag();
+    match current.matches_if_match(if_match) {
+        true => Ok(()),
+        false => Err(PreconditionFailed {
+            current,
+        }),
+    }
+}
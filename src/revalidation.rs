@@ -0,0 +1,302 @@
+//! Freshness of cached responses, as defined in [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-4.2).
+//!
+//! Intended for client caches, deciding whether stored response can be used as it is, or has to be
+//! revalidated using `If-None-Match`. Validators can be persisted between runs using `RevalidationStore`.
+//!
+//! ```rust
+//! use std::time::{Duration, SystemTime};
+//! use etag::EntityTag;
+//! use etag::revalidation::{CacheAction, StoredResponse};
+//!
+//! let etag = EntityTag::strong_unchecked("v1");
+//! let date = SystemTime::now();
+//! let stored = StoredResponse {
+//!     etag: Some(&etag),
+//!     date,
+//!     cache_control: Some("max-age=60"),
+//! };
+//!
+//! assert_eq!(stored.action(date + Duration::from_secs(30)), CacheAction::Fresh);
+//! assert_eq!(stored.action(date + Duration::from_secs(60)), CacheAction::Revalidate);
+//! assert_eq!(stored.if_none_match(), Some("\"v1\""));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::string::String;
+use std::time::{Duration, SystemTime};
+
+use crate::EntityTag;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Action to take with stored response.
+pub enum CacheAction {
+    /// Stored response is fresh and can be used without contacting server.
+    Fresh,
+    /// Stored response is stale, and should be revalidated with `If-None-Match` carrying its tag.
+    Revalidate,
+    /// Stored response cannot be used, and has to be fetched again.
+    Refetch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Metadata of stored response.
+pub struct StoredResponse<'a> {
+    /// Value of `ETag` header.
+    pub etag: Option<&'a EntityTag>,
+    /// Value of `Date` header or, if absent, time when response was received.
+    pub date: SystemTime,
+    /// Value of `Cache-Control` header.
+    pub cache_control: Option<&'a str>,
+}
+
+#[derive(Default)]
+struct Directives {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+//Directives are case-insensitive, with arguments optionally quoted.
+//Invalid `max-age` is treated as zero, making response stale as recommended by RFC.
+fn parse_directives(cache_control: &str) -> Directives {
+    let mut result = Directives::default();
+
+    for directive in cache_control.split(',') {
+        let directive = directive.trim_matches(|ch| ch == ' ' || ch == '\t');
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name, Some(value.trim_matches('"'))),
+            None => (directive, None),
+        };
+
+        if name.eq_ignore_ascii_case("no-store") {
+            result.no_store = true;
+        } else if name.eq_ignore_ascii_case("no-cache") {
+            result.no_cache = true;
+        } else if name.eq_ignore_ascii_case("max-age") && result.max_age.is_none() {
+            let max_age = value.filter(|value| !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()));
+            //Too large values are capped, as RFC recommends.
+            result.max_age = Some(max_age.map_or(0, |value| value.parse().unwrap_or(u64::MAX)));
+        }
+    }
+
+    result
+}
+
+impl StoredResponse<'_> {
+    /// Decides what to do with stored response at time `now`.
+    ///
+    /// - `no-store` requires to fetch response again.
+    /// - `no-cache` requires revalidation on every use.
+    /// - Response is fresh while its age is less than `max-age`, and stale afterwards.
+    ///   No heuristic freshness is applied when `max-age` is absent.
+    ///
+    /// Stale response is revalidated if it has tag, otherwise it is fetched again.
+    /// `s-maxage` is ignored, as it applies to shared caches only.
+    pub fn action(&self, now: SystemTime) -> CacheAction {
+        let directives = self.cache_control.map(parse_directives).unwrap_or_default();
+        if directives.no_store {
+            return CacheAction::Refetch;
+        }
+
+        if !directives.no_cache {
+            if let Some(max_age) = directives.max_age {
+                let age = now.duration_since(self.date).map_or(0, |age| age.as_secs());
+                if age < max_age {
+                    return CacheAction::Fresh;
+                }
+            }
+        }
+
+        match self.etag {
+            Some(etag) if !etag.is_any() => CacheAction::Revalidate,
+            _ => CacheAction::Refetch,
+        }
+    }
+
+    #[inline]
+    /// Returns value of `If-None-Match` header to revalidate response with.
+    pub fn if_none_match(&self) -> Option<&str> {
+        match self.etag {
+            Some(etag) if !etag.is_any() => Some(etag.as_header_str()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Validators of response, stored to revalidate it later.
+pub struct Validators {
+    /// Value of `ETag` header.
+    pub etag: Option<EntityTag>,
+    /// Value of `Last-Modified` header.
+    pub last_modified: Option<SystemTime>,
+    /// Time when response was fetched.
+    pub fetched_at: SystemTime,
+}
+
+impl Validators {
+    #[inline]
+    /// Returns value of `If-None-Match` header to revalidate response with.
+    pub fn if_none_match(&self) -> Option<&str> {
+        match self.etag.as_ref() {
+            Some(etag) if !etag.is_any() => Some(etag.as_header_str()),
+            _ => None,
+        }
+    }
+}
+
+#[inline]
+fn to_secs(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+#[inline]
+fn from_secs(secs: &str) -> Option<SystemTime> {
+    secs.parse().ok().and_then(|secs| SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+}
+
+#[inline]
+fn invalid_data(error: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Validators of fetched responses by URL, which can be persisted between runs of the client.
+///
+/// Store is saved as text file, with line `<url>\t<etag>\t<last-modified>\t<fetched-at>` per URL,
+/// where absent validators are empty, and time is number of seconds since `UNIX_EPOCH`.
+/// Hence time is stored with precision of seconds, which is precision of HTTP-date.
+///
+/// ```rust
+/// use std::time::SystemTime;
+/// use etag::EntityTag;
+/// use etag::revalidation::{RevalidationStore, Validators};
+///
+/// let path = std::env::temp_dir().join("etag-revalidation-doc.tsv");
+/// let mut store = RevalidationStore::load(&path).unwrap();
+/// store.update("https://example.com/feed.xml", Validators {
+///     etag: Some(EntityTag::weak_unchecked("v1")),
+///     last_modified: None,
+///     fetched_at: SystemTime::now(),
+/// }).unwrap();
+/// store.save(&path).unwrap();
+///
+/// let store = RevalidationStore::load(&path).unwrap();
+/// let validators = store.get("https://example.com/feed.xml").unwrap();
+/// assert_eq!(validators.if_none_match(), Some("W/\"v1\""));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct RevalidationStore {
+    entries: BTreeMap<String, Validators>,
+}
+
+impl RevalidationStore {
+    #[inline]
+    /// Creates empty store.
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Loads store from file at `path`, returning empty store if file doesn't exist.
+    ///
+    /// Fails with `io::ErrorKind::InvalidData` if file is malformed, including time, which cannot be represented as `SystemTime`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(error) => return Err(error),
+        };
+
+        let mut result = Self::new();
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            let mut fields = line.split('\t');
+            let (url, etag, last_modified, fetched_at) = match (fields.next(), fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(url), Some(etag), Some(last_modified), Some(fetched_at), None) => (url, etag, last_modified, fetched_at),
+                _ => return Err(invalid_data("Invalid number of fields")),
+            };
+
+            let validators = Validators {
+                etag: match etag.is_empty() {
+                    true => None,
+                    false => Some(etag.parse().map_err(|_| invalid_data("Invalid ETag"))?),
+                },
+                last_modified: match last_modified.is_empty() {
+                    true => None,
+                    false => Some(from_secs(last_modified).ok_or_else(|| invalid_data("Invalid Last-Modified"))?),
+                },
+                fetched_at: from_secs(fetched_at).ok_or_else(|| invalid_data("Invalid fetch time"))?,
+            };
+            result.entries.insert(url.into(), validators);
+        }
+
+        Ok(result)
+    }
+
+    /// Saves store into file at `path`, replacing it.
+    ///
+    /// Store is written into temporary file next to `path` first, and then renamed.
+    ///
+    /// `EntityTag::ANY` is not a validator of response, hence it is saved as absent `ETag`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        use core::fmt::Write;
+
+        let mut text = String::new();
+        for (url, validators) in self.entries.iter() {
+            let _ = write!(text, "{}\t", url);
+            match validators.etag.as_ref() {
+                Some(etag) if !etag.is_any() => text.push_str(etag.as_header_str()),
+                _ => (),
+            }
+            text.push('\t');
+            if let Some(last_modified) = validators.last_modified {
+                let _ = write!(text, "{}", to_secs(last_modified));
+            }
+            let _ = writeln!(text, "\t{}", to_secs(validators.fetched_at));
+        }
+
+        let path = path.as_ref();
+        let mut temp = std::ffi::OsString::from(path.as_os_str());
+        temp.push(".tmp");
+        fs::write(&temp, text)?;
+        fs::rename(&temp, path)
+    }
+
+    #[inline]
+    /// Returns validators of response for `url`.
+    pub fn get(&self, url: &str) -> Option<&Validators> {
+        self.entries.get(url)
+    }
+
+    /// Stores validators of response for `url`, returning previous ones.
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if URL contains tab or line break, as it cannot be saved.
+    pub fn update(&mut self, url: &str, validators: Validators) -> io::Result<Option<Validators>> {
+        match url.contains(['\t', '\r', '\n']) {
+            true => Err(io::Error::new(io::ErrorKind::InvalidInput, "URL contains tab or line break")),
+            false => Ok(self.entries.insert(url.into(), validators)),
+        }
+    }
+
+    #[inline]
+    /// Removes validators of response for `url`.
+    pub fn remove(&mut self, url: &str) -> Option<Validators> {
+        self.entries.remove(url)
+    }
+
+    #[inline]
+    /// Returns number of stored URLs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    /// Returns whether store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
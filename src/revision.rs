@@ -0,0 +1,156 @@
+//! CouchDB style revisions `<generation>-<hash>`, which are used as tags of documents.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::revision::Revision;
+//!
+//! let first = Revision::first(b"{\"name\":\"doc\"}");
+//! assert_eq!(first.generation(), 1);
+//!
+//! let second = first.next_revision(b"{\"name\":\"renamed\"}");
+//! assert_eq!(second.generation(), 2);
+//!
+//! let tag = EntityTag::from(&second);
+//! assert_eq!(tag.as_header_str(), format!("\"{}\"", second));
+//! assert_eq!(Revision::from_etag(&tag), Ok(second));
+//! ```
+
+use core::fmt::{self, Write};
+
+use crate::{EntityTag, ParseError};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Revision of document, consisting of generation number and hexadecimal hash.
+pub struct Revision {
+    generation: u64,
+    tag: EntityTag,
+}
+
+impl Revision {
+    ///Creates revision from its generation and hash of content, hashed together with previous revision, if any.
+    fn hashed(generation: u64, previous: Option<&Revision>, body: &[u8]) -> Self {
+        let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+        if let Some(previous) = previous {
+            hasher.update(previous.as_str().as_bytes());
+        }
+        hasher.update(body);
+
+        let mut tag = EntityTag::strong_unchecked("");
+        let _ = write!(tag, "{}-{:032x}", generation, hasher.digest128());
+        Self {
+            generation,
+            tag,
+        }
+    }
+
+    #[inline]
+    /// Creates first revision of document with content `body`.
+    pub fn first(body: &[u8]) -> Self {
+        Self::hashed(1, None, body)
+    }
+
+    #[inline]
+    /// Creates revision, following this one, for updated content `body`.
+    ///
+    /// Hash covers previous revision, so revisions of the same content differ if history differs.
+    pub fn next_revision(&self, body: &[u8]) -> Self {
+        Self::hashed(self.generation.saturating_add(1), Some(self), body)
+    }
+
+    /// Parses revision `<generation>-<hash>`.
+    ///
+    /// Generation must be positive decimal number, and hash must consist of hexadecimal digits.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let (generation, hash) = text.split_once('-').ok_or(ParseError::InvalidFormat)?;
+        let is_valid = !generation.is_empty() && generation.bytes().all(|byte| byte.is_ascii_digit())
+                       && !hash.is_empty() && hash.bytes().all(|byte| byte.is_ascii_hexdigit());
+        let generation = match is_valid {
+            true => generation.parse().map_err(|_| ParseError::Overflow)?,
+            false => return Err(ParseError::InvalidFormat),
+        };
+        if generation == 0 {
+            return Err(ParseError::InvalidFormat);
+        }
+
+        Ok(Self {
+            generation,
+            tag: EntityTag::checked_strong(text)?,
+        })
+    }
+
+    /// Parses revision from strong tag.
+    ///
+    /// Weak tag cannot identify revision, resulting in `ParseError::InvalidFormat`.
+    pub fn from_etag(tag: &EntityTag) -> Result<Self, ParseError> {
+        match tag.is_weak() || tag.is_any() {
+            true => Err(ParseError::InvalidFormat),
+            false => Self::parse(tag.tag()),
+        }
+    }
+
+    #[inline]
+    /// Returns generation number of revision, starting with `1`.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    #[inline]
+    /// Returns hash of revision.
+    pub fn hash(&self) -> &str {
+        match self.as_str().split_once('-') {
+            Some((_, hash)) => hash,
+            None => "",
+        }
+    }
+
+    #[inline]
+    /// Returns textual representation of revision.
+    pub fn as_str(&self) -> &str {
+        self.tag.tag()
+    }
+
+    #[inline]
+    /// Returns strong tag, representing the revision.
+    pub const fn as_etag(&self) -> &EntityTag {
+        &self.tag
+    }
+}
+
+impl From<&Revision> for EntityTag {
+    #[inline(always)]
+    fn from(revision: &Revision) -> Self {
+        revision.tag.clone()
+    }
+}
+
+impl From<Revision> for EntityTag {
+    #[inline(always)]
+    fn from(revision: Revision) -> Self {
+        revision.tag
+    }
+}
+
+impl core::convert::TryFrom<&EntityTag> for Revision {
+    type Error = ParseError;
+
+    #[inline(always)]
+    fn try_from(tag: &EntityTag) -> Result<Self, Self::Error> {
+        Self::from_etag(tag)
+    }
+}
+
+impl core::str::FromStr for Revision {
+    type Err = ParseError;
+
+    #[inline(always)]
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for Revision {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
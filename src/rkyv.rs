@@ -0,0 +1,138 @@
+//! rkyv support.
+//!
+//! Archived tag is stored inline, without relative pointers, so it can be used directly from
+//! memory mapped storage.
+
+use core::{fmt, str};
+
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use ::rkyv::bytecheck::{CheckBytes, Verify};
+use ::rkyv::munge::munge;
+use ::rkyv::rancor::{Fallible, Source};
+
+use crate::{etagc, EntityTag, ParseError, MAX_TAG_LEN};
+
+///Length, indicating `EntityTag::ANY`.
+const ANY_LEN: u8 = u8::MAX;
+
+/// Archived representation of `EntityTag`.
+#[derive(CheckBytes, Portable)]
+#[bytecheck(crate = ::rkyv::bytecheck, verify)]
+#[repr(C)]
+pub struct ArchivedEntityTag {
+    weak: bool,
+    len: u8,
+    tag: [u8; MAX_TAG_LEN],
+}
+
+impl ArchivedEntityTag {
+    #[inline]
+    /// Returns weakness indicator of the tag.
+    pub fn weak(&self) -> bool {
+        self.weak
+    }
+
+    #[inline]
+    /// Returns whether the tag is wildcard `EntityTag::ANY`.
+    pub fn is_any(&self) -> bool {
+        self.len == ANY_LEN
+    }
+
+    #[inline]
+    /// Get the tag.
+    pub fn tag(&self) -> &str {
+        let len = match self.is_any() {
+            true => 0,
+            false => core::cmp::min(self.len as usize, MAX_TAG_LEN),
+        };
+        //Content is verified to be valid opaque tag on access.
+        unsafe {
+            str::from_utf8_unchecked(&self.tag[..len])
+        }
+    }
+
+    #[inline]
+    fn to_tag(&self) -> EntityTag {
+        match self.is_any() {
+            true => EntityTag::ANY,
+            false => EntityTag::new_unchecked(self.weak, self.tag()),
+        }
+    }
+}
+
+impl fmt::Debug for ArchivedEntityTag {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_tag(), f)
+    }
+}
+
+impl PartialEq<EntityTag> for ArchivedEntityTag {
+    #[inline]
+    fn eq(&self, other: &EntityTag) -> bool {
+        self.weak == other.is_weak() && self.is_any() == other.is_any() && self.tag() == other.tag()
+    }
+}
+
+#[derive(Debug)]
+struct InvalidTag(ParseError);
+
+impl fmt::Display for InvalidTag {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl core::error::Error for InvalidTag {}
+
+unsafe impl<C: Fallible + ?Sized> Verify<C> for ArchivedEntityTag where C::Error: Source {
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        let len = self.len as usize;
+        if self.is_any() {
+            Ok(())
+        } else if len > MAX_TAG_LEN {
+            Err(Source::new(InvalidTag(ParseError::Overflow)))
+        } else if !self.tag[..len].is_ascii() {
+            Err(Source::new(InvalidTag(ParseError::NotAscii)))
+        } else {
+            match str::from_utf8(&self.tag[..len]) {
+                Ok(tag) if etagc::is_valid_simd(tag) => Ok(()),
+                _ => Err(Source::new(InvalidTag(ParseError::InvalidFormat))),
+            }
+        }
+    }
+}
+
+impl Archive for EntityTag {
+    type Archived = ArchivedEntityTag;
+    type Resolver = ();
+
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        let value = self.tag().as_bytes();
+        let mut storage = [0u8; MAX_TAG_LEN];
+        storage[..value.len()].copy_from_slice(value);
+
+        munge!(let ArchivedEntityTag { weak, len, tag } = out);
+        weak.write(self.is_weak());
+        len.write(match self.is_any() {
+            true => ANY_LEN,
+            false => value.len() as u8,
+        });
+        tag.write(storage);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for EntityTag {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<EntityTag, D> for ArchivedEntityTag {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<EntityTag, D::Error> {
+        Ok(self.to_tag())
+    }
+}
@@ -0,0 +1,236 @@
+//! Extraction of conditional headers from arbitrary header representation.
+
+use core::str;
+
+use crate::{list, EntityTag, ParseError};
+
+///Maximum number of field lines, kept by `FieldLines`.
+const MAX_FIELD_LINES: usize = 4;
+///Replacement of value, which is not valid UTF-8, and is invalid for every scanned header.
+const INVALID_VALUE: &str = "";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Value of list header, such as `If-Match`, which can be sent as multiple field lines.
+///
+/// As per [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-5.3) lines are equivalent to
+/// single value, joined by commas, so each of them is searched for matching tag.
+/// Value is invalid, if any line is not a valid string, e.g. not UTF-8, or there are more than 4 lines, in which case it matches nothing.
+pub struct FieldLines<'a> {
+    lines: [&'a str; MAX_FIELD_LINES],
+    len: usize,
+    is_valid: bool,
+}
+
+impl<'a> FieldLines<'a> {
+    const EMPTY: Self = Self {
+        lines: [INVALID_VALUE; MAX_FIELD_LINES],
+        len: 0,
+        is_valid: true,
+    };
+
+    #[cfg(feature = "http")]
+    ///Collects field lines, where `None` stands for line, which cannot be represented as string.
+    ///
+    ///Returns `None` if there are no lines, i.e. header is absent.
+    pub(crate) fn from_lines<I: IntoIterator<Item = Option<&'a str>>>(lines: I) -> Option<Self> {
+        let mut lines = lines.into_iter();
+        let mut result = Self::EMPTY;
+        result.push(lines.next()?);
+        for line in lines {
+            result.push(line);
+        }
+        Some(result)
+    }
+
+    fn push(&mut self, line: Option<&'a str>) {
+        match (line, self.lines.get_mut(self.len)) {
+            (Some(line), Some(slot)) => {
+                *slot = line;
+                self.len += 1;
+            },
+            _ => self.is_valid = false,
+        }
+    }
+
+    #[inline]
+    /// Returns field lines as they are.
+    ///
+    /// Lines, which are not valid strings or exceed limit, are not included.
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines[..self.len]
+    }
+
+    #[inline]
+    /// Returns whether all field lines are kept as valid strings.
+    pub const fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    #[inline]
+    /// Returns whether value is `*`, which is only valid as sole field line.
+    pub fn is_any(&self) -> bool {
+        self.is_valid && self.len == 1 && list::is_any(self.lines[0])
+    }
+
+    /// Checks whether `If-Match` matches `current` tag, or existence of resource for `*`.
+    ///
+    /// Strong comparison is used, as in `EntityTag::matches_if_match`.
+    pub fn matches_if_match(&self, current: Option<&EntityTag>) -> bool {
+        match current {
+            _ if self.is_any() => true,
+            Some(current) if self.is_valid => self.lines().iter().any(|line| !list::is_any(line) && current.matches_if_match(line)),
+            _ => false,
+        }
+    }
+
+    /// Checks whether `If-None-Match` matches `current` tag, or existence of resource for `*`.
+    ///
+    /// Weak comparison is used, as in `EntityTag::matches_if_none_match`.
+    pub fn matches_if_none_match(&self, current: Option<&EntityTag>) -> bool {
+        match current {
+            _ if self.is_any() => true,
+            Some(current) if self.is_valid => self.lines().iter().any(|line| !list::is_any(line) && current.matches_if_none_match(line)),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Tag related headers, extracted by `scan_headers`.
+///
+/// Values are kept as they are, to be parsed lazily by respective matching functions.
+pub struct ScannedConditionals<'a> {
+    /// Value of `ETag` header.
+    pub etag: Option<&'a str>,
+    /// Field lines of `If-Match` header.
+    pub if_match: Option<FieldLines<'a>>,
+    /// Field lines of `If-None-Match` header.
+    pub if_none_match: Option<FieldLines<'a>>,
+    /// Value of `If-Range` header.
+    pub if_range: Option<&'a str>,
+    /// Value of `If-Modified-Since` header.
+    pub if_modified_since: Option<&'a str>,
+    /// Value of `If-Unmodified-Since` header.
+    pub if_unmodified_since: Option<&'a str>,
+}
+
+impl<'a> ScannedConditionals<'a> {
+    #[inline]
+    /// Parses `ETag` header, returning `None` if it is absent.
+    pub fn etag(&self) -> Option<Result<EntityTag, ParseError>> {
+        self.etag.map(EntityTag::parse_trimmed)
+    }
+
+    #[inline]
+    /// Returns whether request has any precondition.
+    pub fn has_preconditions(&self) -> bool {
+        self.if_match.is_some() || self.if_none_match.is_some() || self.if_modified_since.is_some() || self.if_unmodified_since.is_some()
+    }
+
+    #[cfg(feature = "std")]
+    /// Converts into conditionals, evaluated by `preconditions::ResourceValidator`.
+    ///
+    /// Returns `None` if `If-Match` or `If-None-Match` is invalid or sent as multiple field lines,
+    /// which can be evaluated by `evaluate` instead.
+    /// Dates are parsed only with `httpdate` feature, and are ignored otherwise.
+    pub fn to_request(&self, is_get_or_head: bool) -> Option<crate::preconditions::RequestConditionals<'a>> {
+        fn single<'a>(field: Option<FieldLines<'a>>) -> Result<Option<&'a str>, ()> {
+            match field {
+                Some(field) if field.is_valid && field.len == 1 => Ok(Some(field.lines[0])),
+                Some(_) => Err(()),
+                None => Ok(None),
+            }
+        }
+
+        let (if_modified_since, if_unmodified_since) = self.dates();
+        Some(crate::preconditions::RequestConditionals {
+            is_get_or_head,
+            if_match: single(self.if_match).ok()?,
+            if_none_match: single(self.if_none_match).ok()?,
+            if_modified_since,
+            if_unmodified_since,
+        })
+    }
+
+    #[cfg(feature = "std")]
+    /// Evaluates preconditions against `validator`, as `preconditions::ResourceValidator::evaluate` does,
+    /// considering every field line of `If-Match` and `If-None-Match`.
+    ///
+    /// Dates are parsed only with `httpdate` feature, and are ignored otherwise.
+    pub fn evaluate(&self, validator: &crate::preconditions::ResourceValidator, is_get_or_head: bool) -> crate::preconditions::Decision {
+        let current = validator.etag.as_ref();
+        let if_match = self.if_match.map(|field| field.matches_if_match(current));
+        let if_none_match = self.if_none_match.map(|field| field.matches_if_none_match(current));
+        let (if_modified_since, if_unmodified_since) = self.dates();
+        validator.evaluate_matches(is_get_or_head, if_match, if_none_match, if_modified_since, if_unmodified_since)
+    }
+
+    #[cfg(feature = "std")]
+    ///Returns `If-Modified-Since` and `If-Unmodified-Since` dates.
+    fn dates(&self) -> (Option<std::time::SystemTime>, Option<std::time::SystemTime>) {
+        #[cfg(feature = "httpdate")]
+        return (
+            self.if_modified_since.and_then(crate::preconditions::parse_http_date),
+            self.if_unmodified_since.and_then(crate::preconditions::parse_http_date),
+        );
+        #[cfg(not(feature = "httpdate"))]
+        return (None, None);
+    }
+}
+
+/// Extracts tag related headers from `(name, value)` pairs of any header representation.
+///
+/// Names are matched case-insensitively.
+/// All field lines of `If-Match` and `If-None-Match` are kept, while only first occurrence of other headers is considered.
+/// Values, which are not valid UTF-8, make header present, but invalid: such field lines match nothing,
+/// while other headers are replaced with empty value.
+///
+/// ```rust
+/// use etag::{scan_headers, EntityTag};
+///
+/// let headers = [
+///     ("host", "example.com".as_bytes()),
+///     ("If-None-Match", b"W/\"v1\""),
+///     ("if-none-match", b"\"v2\""),
+///     ("if-range", b"\"v2\""),
+/// ];
+/// let scanned = scan_headers(headers.iter().copied());
+/// let if_none_match = scanned.if_none_match.unwrap();
+/// assert!(if_none_match.matches_if_none_match(Some(&EntityTag::strong_unchecked("v1"))));
+/// assert!(if_none_match.matches_if_none_match(Some(&EntityTag::strong_unchecked("v2"))));
+/// assert_eq!(scanned.if_range, Some("\"v2\""));
+/// assert_eq!(scanned.if_match, None);
+/// ```
+///
+/// With `http`, pairs of `HeaderMap` can be passed as `headers.iter().map(|(name, value)| (name.as_str(), value.as_bytes()))`.
+pub fn scan_headers<'a, I: IntoIterator<Item = (&'a str, &'a [u8])>>(headers: I) -> ScannedConditionals<'a> {
+    let mut result = ScannedConditionals::default();
+    for (name, value) in headers {
+        let field = if name.eq_ignore_ascii_case("if-match") {
+            &mut result.if_match
+        } else if name.eq_ignore_ascii_case("if-none-match") {
+            &mut result.if_none_match
+        } else {
+            let slot = if name.eq_ignore_ascii_case("etag") {
+                &mut result.etag
+            } else if name.eq_ignore_ascii_case("if-range") {
+                &mut result.if_range
+            } else if name.eq_ignore_ascii_case("if-modified-since") {
+                &mut result.if_modified_since
+            } else if name.eq_ignore_ascii_case("if-unmodified-since") {
+                &mut result.if_unmodified_since
+            } else {
+                continue;
+            };
+
+            if slot.is_none() {
+                *slot = Some(str::from_utf8(value).unwrap_or(INVALID_VALUE));
+            }
+            continue;
+        };
+
+        field.get_or_insert(FieldLines::EMPTY).push(str::from_utf8(value).ok());
+    }
+
+    result
+}
@@ -0,0 +1,36 @@
+//! schemars support.
+//!
+//! Schema describes header representation of the tag, as used by `serde` in human readable formats.
+
+use alloc::borrow::Cow;
+
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::{EntityTag, MAX_TAG_LEN};
+
+impl JsonSchema for EntityTag {
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("EntityTag")
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("etag::EntityTag")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Entity tag in format W/\"<etag_value>\" or \"<etag_value>\"",
+            "pattern": "^(W/)?\"[\\x21\\x23-\\x7E]*\"$",
+            "minLength": 2,
+            "maxLength": MAX_TAG_LEN + 4,
+        })
+    }
+}
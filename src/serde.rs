@@ -0,0 +1,103 @@
+//! Serde support.
+//!
+//! Human readable formats use header representation of the tag.
+//! Compact formats use tuple `(weak, tag)` instead, avoiding need to quote the tag.
+//! `EntityTag::ANY` is not an entity-tag on its own, so human readable formats fail to serialize it and reject `*`
+//! on deserialization, while compact formats encode it as `(false, "\"")`, since lone `"` is never valid opaque tag.
+
+use core::fmt;
+
+use ::serde::ser::{self, Serialize, Serializer, SerializeTuple};
+use ::serde::de::{self, Deserialize, DeserializeSeed, Deserializer, Visitor, SeqAccess};
+
+use crate::EntityTag;
+
+///Opaque tag of compact representation of `EntityTag::ANY`.
+const ANY_TAG: &str = "\"";
+
+impl Serialize for EntityTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            match self.is_any() {
+                true => Err(ser::Error::custom("EntityTag wildcard cannot be serialized")),
+                false => serializer.serialize_str(self.as_header_str()),
+            }
+        } else {
+            let (weak, tag) = match self.is_any() {
+                true => (false, ANY_TAG),
+                false => (self.is_weak(), self.tag()),
+            };
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(&weak)?;
+            tuple.serialize_element(tag)?;
+            tuple.end()
+        }
+    }
+}
+
+struct EntityTagVisitor;
+
+impl<'de> Visitor<'de> for EntityTagVisitor {
+    type Value = EntityTag;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("entity tag")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        text.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let weak = match seq.next_element()? {
+            Some(weak) => weak,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+
+        match seq.next_element_seed(OpaqueTag(weak))? {
+            Some(tag) => Ok(tag),
+            None => Err(de::Error::invalid_length(1, &self)),
+        }
+    }
+}
+
+///Opaque tag of compact representation.
+struct OpaqueTag(bool);
+
+impl<'de> DeserializeSeed<'de> for OpaqueTag {
+    type Value = EntityTag;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for OpaqueTag {
+    type Value = EntityTag;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("opaque tag")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        match (self.0, text) {
+            (false, ANY_TAG) => Ok(EntityTag::ANY),
+            (weak, text) => EntityTag::checked_new(weak, text).map_err(E::custom),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(EntityTagVisitor)
+        } else {
+            deserializer.deserialize_tuple(2, EntityTagVisitor)
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Conditional serving of static files.
+//!
+//! Combines validator of the file with `preconditions`, leaving only writing of the response to
+//! the HTTP framework.
+//!
+//! ```rust,no_run
+//! use etag::preconditions::RequestConditionals;
+//! use etag::serve::{serve_file, ServeDecision};
+//!
+//! let request = RequestConditionals {
+//!     is_get_or_head: true,
+//!     if_none_match: Some("W/\"1700000000.0-42\""),
+//!     ..Default::default()
+//! };
+//!
+//! match serve_file("static/index.html", &request).unwrap() {
+//!     ServeDecision::NotModified { etag } => println!("304, ETag: {}", etag),
+//!     ServeDecision::Full { etag, len, .. } => println!("200, ETag: {}, Content-Length: {}", etag, len),
+//!     ServeDecision::PreconditionFailed => println!("412"),
+//! }
+//! ```
+
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use crate::EntityTag;
+use crate::preconditions::{Decision, RequestConditionals, ResourceValidator};
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+/// Response to request for the file.
+pub enum ServeDecision {
+    /// Request should be responded with `304 Not Modified`, carrying `ETag`.
+    NotModified {
+        /// Tag of the file.
+        etag: EntityTag,
+    },
+    /// Request should be responded with content of the file.
+    Full {
+        /// Opened file.
+        file: File,
+        /// Tag of the file.
+        etag: EntityTag,
+        /// Length of the file.
+        len: u64,
+    },
+    /// Request must be rejected with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+/// Opens file at `path` and evaluates request preconditions against it.
+///
+/// Tag is created using `EntityTag::try_from_file_meta`, while modification time of the file is used
+/// for date based preconditions.
+///
+/// Fails if file cannot be opened, is not a regular file, or its modification time is earlier than `UNIX_EPOCH`.
+pub fn serve_file<P: AsRef<Path>>(path: P, request: &RequestConditionals<'_>) -> io::Result<ServeDecision> {
+    serve_file_with(path, request, |metadata| {
+        EntityTag::try_from_file_meta(metadata).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Modified is earlier than time::UNIX_EPOCH!"))
+    })
+}
+
+/// Opens file at `path` and evaluates request preconditions against it, using tag returned by `validator`.
+///
+/// Allows to look up precomputed tag, e.g. content hash, stored alongside the file.
+pub fn serve_file_with<P: AsRef<Path>, F: FnOnce(&fs::Metadata) -> io::Result<EntityTag>>(path: P, request: &RequestConditionals<'_>, validator: F) -> io::Result<ServeDecision> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Path is not a file"));
+    }
+
+    let etag = validator(&metadata)?;
+    let validator = ResourceValidator {
+        etag: Some(etag.clone()),
+        last_modified: metadata.modified().ok(),
+    };
+
+    Ok(match validator.evaluate(request) {
+        Decision::Proceed => ServeDecision::Full {
+            file,
+            etag,
+            len: metadata.len(),
+        },
+        Decision::NotModified => ServeDecision::NotModified {
+            etag,
+        },
+        Decision::PreconditionFailed => ServeDecision::PreconditionFailed,
+    })
+}
@@ -0,0 +1,323 @@
+//! Sets of tags, using open addressing over hash of opaque tag.
+//!
+//! Tags are stored in slots, with `EntityTag::ANY` marking empty slot, hence it can never be a member.
+//! As hash ignores weakness, strong and weak variants of the same tag are found by single probe sequence,
+//! which allows both comparisons.
+
+use crate::{Comparison, EntityTag};
+
+#[inline(always)]
+fn home(tag: &EntityTag, slots_len: usize) -> usize {
+    (xxhash_rust::xxh3::xxh3_64(tag.tag().as_bytes()) % slots_len as u64) as usize
+}
+
+///Returns slots, starting at home of `tag`, until first empty slot, which is not included.
+fn probe<'a>(slots: &'a [EntityTag], tag: &EntityTag) -> impl Iterator<Item = (usize, &'a EntityTag)> + 'a {
+    let start = match slots.len() {
+        0 => 0,
+        len => home(tag, len),
+    };
+    slots[start..].iter().enumerate().map(move |(idx, slot)| (start + idx, slot))
+                  .chain(slots[..start].iter().enumerate())
+                  .take_while(|(_, slot)| !slot.is_any())
+}
+
+///Returns index of identical tag, or index of empty slot to insert it into, if any.
+fn find(slots: &[EntityTag], tag: &EntityTag) -> Result<usize, Option<usize>> {
+    let mut probed = 0;
+    for (idx, slot) in probe(slots, tag) {
+        if slot == tag {
+            return Ok(idx);
+        }
+        probed += 1;
+    }
+
+    match probed < slots.len() {
+        true => Err(Some((home(tag, slots.len()) + probed) % slots.len())),
+        false => Err(None),
+    }
+}
+
+#[inline]
+fn contains_with(slots: &[EntityTag], tag: &EntityTag, comparison: Comparison) -> bool {
+    !tag.is_any() && probe(slots, tag).any(|(_, slot)| comparison.matches(slot, tag))
+}
+
+#[allow(clippy::result_large_err)]
+fn insert(slots: &mut [EntityTag], tag: EntityTag) -> Result<bool, EntityTag> {
+    if tag.is_any() {
+        return Ok(false);
+    }
+
+    match find(slots, &tag) {
+        Ok(_) => Ok(false),
+        Err(Some(idx)) => {
+            slots[idx] = tag;
+            Ok(true)
+        },
+        Err(None) => Err(tag),
+    }
+}
+
+///Removes tag, shifting following members of the probe sequence back, so no tombstones are needed.
+fn remove(slots: &mut [EntityTag], tag: &EntityTag) -> bool {
+    let mut hole = match tag.is_any() {
+        true => return false,
+        false => match find(slots, tag) {
+            Ok(idx) => idx,
+            Err(_) => return false,
+        },
+    };
+
+    let len = slots.len();
+    let mut next = (hole + 1) % len;
+    for _ in 1..len {
+        if slots[next].is_any() {
+            break;
+        }
+
+        //Member can fill the hole, unless its home lies cyclically after the hole
+        let home = home(&slots[next], len);
+        if (next + len - home) % len >= (next + len - hole) % len {
+            slots.swap(hole, next);
+            hole = next;
+        }
+        next = (next + 1) % len;
+    }
+
+    slots[hole] = EntityTag::ANY;
+    true
+}
+
+/// Fixed capacity set of up to `N` tags, which doesn't require allocation.
+///
+/// Lookup is expected to be O(1) while set is sparse, so capacity should exceed expected number
+/// of tags, e.g. by a third.
+///
+/// ```rust
+/// use etag::{EntityTag, FixedEtagSet};
+///
+/// let mut issued = FixedEtagSet::<64>::new();
+/// assert_eq!(issued.insert(EntityTag::strong_unchecked("v1")), Ok(true));
+/// assert!(issued.contains(&EntityTag::weak_unchecked("v1")));
+/// assert!(!issued.contains(&EntityTag::strong_unchecked("v2")));
+/// ```
+#[derive(Clone)]
+pub struct FixedEtagSet<const N: usize> {
+    slots: [EntityTag; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedEtagSet<N> {
+    #[inline]
+    /// Creates new empty set.
+    pub const fn new() -> Self {
+        Self {
+            slots: [EntityTag::ANY; N],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    /// Returns number of tags in the set.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    /// Returns whether set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Returns maximum number of tags in the set.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Adds tag to the set, returning whether it was not present.
+    ///
+    /// `EntityTag::ANY` is never added.
+    /// Returns tag back if set is full.
+    #[allow(clippy::result_large_err)]
+    pub fn insert(&mut self, tag: EntityTag) -> Result<bool, EntityTag> {
+        let inserted = insert(&mut self.slots, tag)?;
+        self.len += inserted as usize;
+        Ok(inserted)
+    }
+
+    /// Removes identical tag from the set, returning whether it was present.
+    pub fn remove(&mut self, tag: &EntityTag) -> bool {
+        let removed = remove(&mut self.slots, tag);
+        self.len -= removed as usize;
+        removed
+    }
+
+    #[inline]
+    /// Returns whether any tag of the set matches `tag`, using weak comparison.
+    pub fn contains(&self, tag: &EntityTag) -> bool {
+        self.contains_with(tag, Comparison::Weak)
+    }
+
+    #[inline]
+    /// Returns whether any tag of the set matches `tag`, using specified comparison.
+    pub fn contains_with(&self, tag: &EntityTag, comparison: Comparison) -> bool {
+        contains_with(&self.slots, tag, comparison)
+    }
+
+    /// Removes all tags from the set.
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = EntityTag::ANY;
+        }
+        self.len = 0;
+    }
+
+    #[inline]
+    /// Returns iterator over tags of the set, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = &EntityTag> {
+        self.slots.iter().filter(|slot| !slot.is_any())
+    }
+}
+
+impl<const N: usize> Default for FixedEtagSet<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for FixedEtagSet<N> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Set of tags, growing as necessary.
+///
+/// ```rust
+/// use etag::{Comparison, EntityTag, EtagSet};
+///
+/// let mut issued = EtagSet::new();
+/// assert!(issued.insert(EntityTag::weak_unchecked("v1")));
+/// assert!(!issued.insert(EntityTag::weak_unchecked("v1")));
+/// assert!(issued.contains(&EntityTag::strong_unchecked("v1")));
+/// assert!(!issued.contains_with(&EntityTag::strong_unchecked("v1"), Comparison::Strong));
+/// ```
+#[derive(Clone, Default)]
+pub struct EtagSet {
+    slots: alloc::vec::Vec<EntityTag>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl EtagSet {
+    const MIN_SLOTS: usize = 16;
+
+    #[inline]
+    /// Creates new empty set, without allocating.
+    pub const fn new() -> Self {
+        Self {
+            slots: alloc::vec::Vec::new(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    /// Creates new empty set, able to hold at least `capacity` tags without growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut result = Self::new();
+        result.resize(Self::slots_for(capacity));
+        result
+    }
+
+    #[inline]
+    /// Returns number of tags in the set.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    /// Returns whether set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Returns number of tags, that set can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.slots.len() / 4 * 3
+    }
+
+    #[inline]
+    ///Returns number of slots to keep set at most 3/4 full.
+    fn slots_for(capacity: usize) -> usize {
+        core::cmp::max(Self::MIN_SLOTS, (capacity / 3 * 4 + 4).next_power_of_two())
+    }
+
+    fn resize(&mut self, slots_len: usize) {
+        let slots = core::mem::replace(&mut self.slots, alloc::vec![EntityTag::ANY; slots_len]);
+        for tag in slots.into_iter().filter(|slot| !slot.is_any()) {
+            let _ = insert(&mut self.slots, tag);
+        }
+    }
+
+    /// Adds tag to the set, returning whether it was not present.
+    ///
+    /// `EntityTag::ANY` is never added.
+    pub fn insert(&mut self, tag: EntityTag) -> bool {
+        if self.len >= self.capacity() {
+            self.resize(Self::slots_for(self.len + 1));
+        }
+
+        //Set always has free slots after growing.
+        let inserted = insert(&mut self.slots, tag).unwrap_or(false);
+        self.len += inserted as usize;
+        inserted
+    }
+
+    /// Removes identical tag from the set, returning whether it was present.
+    pub fn remove(&mut self, tag: &EntityTag) -> bool {
+        let removed = !self.slots.is_empty() && remove(&mut self.slots, tag);
+        self.len -= removed as usize;
+        removed
+    }
+
+    #[inline]
+    /// Returns whether any tag of the set matches `tag`, using weak comparison.
+    pub fn contains(&self, tag: &EntityTag) -> bool {
+        self.contains_with(tag, Comparison::Weak)
+    }
+
+    #[inline]
+    /// Returns whether any tag of the set matches `tag`, using specified comparison.
+    pub fn contains_with(&self, tag: &EntityTag, comparison: Comparison) -> bool {
+        contains_with(&self.slots, tag, comparison)
+    }
+
+    #[inline]
+    /// Removes all tags from the set, keeping allocated memory.
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = EntityTag::ANY;
+        }
+        self.len = 0;
+    }
+
+    #[inline]
+    /// Returns iterator over tags of the set, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = &EntityTag> {
+        self.slots.iter().filter(|slot| !slot.is_any())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for EtagSet {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,143 @@
+//! `sqlx` support for optimistic locking with Postgres.
+//!
+//! Row version, such as integer column incremented on each update or `xmin` system column
+//! (selected as `xmin::text::bigint`), is exposed as strong tag of the row.
+//! Updates are then guarded by `If-Match`, and request is rejected with `412 Precondition Failed`
+//! when no row was affected.
+//!
+//! ```rust
+//! use etag::sqlx::{push_if_match, RowVersion};
+//! use sqlx::{Postgres, QueryBuilder};
+//!
+//! assert_eq!(RowVersion(7).etag().as_header_str(), "\"7\"");
+//!
+//! let mut query = QueryBuilder::<Postgres>::new("UPDATE docs SET body = ");
+//! query.push_bind("text").push(", version = version + 1 WHERE id = ").push_bind(1).push(" AND ");
+//! push_if_match(&mut query, "version", "\"7\", W/\"8\"");
+//! assert_eq!(query.sql(), "UPDATE docs SET body = $1, version = version + 1 WHERE id = $2 AND version IN ($3)");
+//! ```
+
+use core::fmt::Write;
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use sqlx::{Decode, Encode, QueryBuilder, Type, ValueRef};
+
+use crate::{parse_list, EntityTag, ParseError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Version of the row, decoded from `INT8` or `INT4` column.
+pub struct RowVersion(pub i64);
+
+impl RowVersion {
+    #[inline]
+    /// Returns strong tag `"<version>"`.
+    pub fn etag(&self) -> EntityTag {
+        let mut tag = EntityTag::strong_unchecked("");
+        let _ = write!(tag, "{}", self.0);
+        tag
+    }
+
+    /// Parses version from strong tag, created by `etag`.
+    ///
+    /// Returns `ParseError::InvalidFormat` for weak tags, as they cannot identify version,
+    /// and for tags, which are not exactly as `etag` would produce them, e.g. `"007"` or `"+7"`.
+    pub fn from_etag(tag: &EntityTag) -> Result<Self, ParseError> {
+        if tag.is_weak() || tag.is_any() {
+            return Err(ParseError::InvalidFormat);
+        }
+
+        match tag.tag().parse() {
+            Ok(version) if Self(version).etag() == *tag => Ok(Self(version)),
+            _ => Err(ParseError::InvalidFormat),
+        }
+    }
+}
+
+impl From<RowVersion> for EntityTag {
+    #[inline(always)]
+    fn from(version: RowVersion) -> Self {
+        version.etag()
+    }
+}
+
+impl Type<Postgres> for RowVersion {
+    #[inline(always)]
+    fn type_info() -> PgTypeInfo {
+        <i64 as Type<Postgres>>::type_info()
+    }
+
+    #[inline(always)]
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <i64 as Type<Postgres>>::compatible(ty) || <i32 as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for RowVersion {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match *value.type_info() == <i32 as Type<Postgres>>::type_info() {
+            true => <i32 as Decode<Postgres>>::decode(value).map(|version| Self(version.into())),
+            false => <i64 as Decode<Postgres>>::decode(value).map(Self),
+        }
+    }
+}
+
+impl Encode<'_, Postgres> for RowVersion {
+    #[inline(always)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <i64 as Encode<Postgres>>::encode_by_ref(&self.0, buf)
+    }
+}
+
+///Stored as `TEXT` using header representation.
+impl Type<Postgres> for EntityTag {
+    #[inline(always)]
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    #[inline(always)]
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for EntityTag {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let text = <&str as Decode<Postgres>>::decode(value)?;
+        text.parse().map_err(|error: ParseError| error.as_str().into())
+    }
+}
+
+impl Encode<'_, Postgres> for EntityTag {
+    #[inline(always)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode_by_ref(&self.as_header_str(), buf)
+    }
+}
+
+/// Pushes condition, matching `If-Match` header against version `column`, using strong comparison.
+///
+/// - `*` results in `TRUE`, as row is expected to exist.
+/// - Versions of strong tags are bound as `<column> IN (...)`.
+/// - If there are no such tags, `FALSE` is pushed, so update affects no rows.
+pub fn push_if_match<'args>(builder: &mut QueryBuilder<'args, Postgres>, column: &str, if_match: &str) {
+    if crate::list::is_any(if_match) {
+        builder.push("TRUE");
+        return;
+    }
+
+    let mut versions = parse_list(if_match).skip_invalid().filter_map(|tag| RowVersion::from_etag(&tag).ok()).peekable();
+    if versions.peek().is_none() {
+        builder.push("FALSE");
+        return;
+    }
+
+    builder.push(column).push(" IN (");
+    let mut separated = builder.separated(", ");
+    for version in versions {
+        separated.push_bind(version);
+    }
+    separated.push_unseparated(")");
+}
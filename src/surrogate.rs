@@ -0,0 +1,113 @@
+//! Surrogate keys, used to purge CDN caches by tag.
+//!
+//! Keys are derived from opaque tags, ignoring weakness, so both variants of the same fingerprint
+//! refer to the same content.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::surrogate::{write_keys, KeyFormat};
+//!
+//! let tags = [EntityTag::strong_unchecked("page-1"), EntityTag::weak_unchecked("asset,2")];
+//! let mut header = String::new();
+//! let written = write_keys(&KeyFormat::FASTLY, "etag:", tags.iter(), &mut header).unwrap();
+//! assert_eq!(written, 2);
+//! assert_eq!(header, "etag:page-1 etag:asset_2");
+//! ```
+
+use core::fmt;
+
+use crate::EntityTag;
+
+/// Describes header, listing surrogate keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyFormat {
+    /// Separator between keys.
+    pub separator: char,
+    /// Maximum length of single key in bytes, longer keys are truncated.
+    pub max_key_len: usize,
+    /// Maximum length of header value in bytes, keys that do not fit are omitted.
+    pub max_header_len: usize,
+}
+
+impl KeyFormat {
+    /// Fastly `Surrogate-Key` header.
+    pub const FASTLY: Self = Self {
+        separator: ' ',
+        max_key_len: 1024,
+        max_header_len: 16384,
+    };
+
+    /// Cloudflare `Cache-Tag` header.
+    pub const CLOUDFLARE: Self = Self {
+        separator: ',',
+        max_key_len: 1024,
+        max_header_len: 16384,
+    };
+}
+
+#[inline(always)]
+const fn is_key_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b':')
+}
+
+///Writes first `limit` bytes of `text`, replacing each byte outside of `[A-Za-z0-9._:-]` with `_`.
+fn write_sanitized<W: fmt::Write>(out: &mut W, text: &str, limit: usize) -> fmt::Result {
+    let bytes = &text.as_bytes()[..limit];
+    let mut start = 0;
+    for (idx, byte) in bytes.iter().enumerate() {
+        if !is_key_byte(*byte) {
+            //Non-empty run consists of ASCII characters only, hence both of its ends are on char boundary.
+            if start < idx {
+                out.write_str(&text[start..idx])?;
+            }
+            out.write_str("_")?;
+            start = idx + 1;
+        }
+    }
+
+    match start < limit {
+        true => out.write_str(&text[start..limit]),
+        false => Ok(()),
+    }
+}
+
+/// Writes surrogate keys `<prefix><opaque tag>` of `tags` into `out`, returning number of written keys.
+///
+/// Characters other than `[A-Za-z0-9._:-]` are replaced with `_`.
+/// `EntityTag::ANY` and empty keys are skipped.
+pub fn write_keys<'a, I: IntoIterator<Item = &'a EntityTag>, W: fmt::Write>(format: &KeyFormat, prefix: &str, tags: I, out: &mut W) -> Result<usize, fmt::Error> {
+    let mut header_len = 0;
+    let mut written = 0;
+
+    for tag in tags {
+        if tag.is_any() {
+            continue;
+        }
+
+        let tag = tag.tag();
+        let key_len = core::cmp::min(prefix.len() + tag.len(), format.max_key_len);
+        if key_len == 0 {
+            continue;
+        }
+
+        let separator_len = match written {
+            0 => 0,
+            _ => format.separator.len_utf8(),
+        };
+        if header_len + separator_len + key_len > format.max_header_len {
+            break;
+        }
+
+        if separator_len > 0 {
+            out.write_char(format.separator)?;
+        }
+        let prefix_len = core::cmp::min(prefix.len(), key_len);
+        write_sanitized(out, prefix, prefix_len)?;
+        write_sanitized(out, tag, key_len - prefix_len)?;
+
+        header_len += separator_len + key_len;
+        written += 1;
+    }
+
+    Ok(written)
+}
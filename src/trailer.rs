@@ -0,0 +1,109 @@
+//! Tags of streamed responses, sent as HTTP trailer.
+//!
+//! Body of streamed response is not known when headers are sent, so its tag can only be provided
+//! in trailer section, announced by `Trailer: ETag` header.
+//! With chunked transfer coding, `StreamingTag` also produces framing of each chunk and the last chunk
+//! carrying trailer.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//! use etag::trailer::StreamingTag;
+//!
+//! let mut body = String::new();
+//! let mut tag = StreamingTag::new();
+//! for frame in ["Hello", ", ", "world!"] {
+//!     body.push_str(&tag.chunk_header(frame.as_bytes()));
+//!     body.push_str(frame);
+//!     body.push_str("\r\n");
+//! }
+//! tag.write_last_chunk(&mut body).unwrap();
+//!
+//! assert_eq!(tag.etag(), EntityTag::from_data(b"Hello, world!"));
+//! assert!(body.starts_with("5\r\nHello\r\n2\r\n, \r\n6\r\nworld!\r\n0\r\nETag: \"13-"));
+//! assert!(body.ends_with("\"\r\n\r\n"));
+//! ```
+
+use core::fmt::{self, Write};
+
+use crate::{EntityTag, StrBuf};
+use crate::hash::ContentHasher;
+
+/// `Trailer` header announcing `ETag` trailer field.
+pub const TRAILER: (&str, &str) = ("Trailer", "ETag");
+
+#[derive(Clone)]
+/// Hashes body of streamed response, producing the same strong tag as `EntityTag::from_data` on whole body.
+pub struct StreamingTag {
+    hasher: ContentHasher,
+    len: u64,
+}
+
+impl StreamingTag {
+    #[inline]
+    /// Creates new instance for empty body.
+    pub fn new() -> Self {
+        Self {
+            hasher: ContentHasher::new(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    /// Returns number of hashed bytes.
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    /// Returns whether no bytes were hashed yet.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    /// Hashes `frame` of the body.
+    ///
+    /// Use it when transport provides its own framing, e.g. HTTP/2 `DATA` frames.
+    pub fn update(&mut self, frame: &[u8]) {
+        self.hasher.update(frame);
+        self.len += frame.len() as u64;
+    }
+
+    /// Hashes `frame` of the body, returning chunk size line to send before it.
+    ///
+    /// Chunk must be terminated by `\r\n` after `frame`.
+    /// Empty `frame` must not be sent at all, as zero sized chunk terminates the body, hence empty
+    /// line is returned for it.
+    pub fn chunk_header(&mut self, frame: &[u8]) -> StrBuf<18> {
+        let mut result = StrBuf::new();
+        if !frame.is_empty() {
+            self.update(frame);
+            let _ = write!(result, "{:x}\r\n", frame.len());
+        }
+        result
+    }
+
+    #[inline]
+    /// Returns strong tag of the body hashed so far.
+    pub fn etag(&self) -> EntityTag {
+        EntityTag::from_len_hash(self.len, self.hasher.digest())
+    }
+
+    /// Writes last chunk, followed by `ETag` trailer field and end of the body.
+    pub fn write_last_chunk<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "0\r\nETag: {}\r\n\r\n", self.etag())
+    }
+}
+
+impl Default for StreamingTag {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for StreamingTag {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("StreamingTag").field("len", &self.len).finish()
+    }
+}
@@ -0,0 +1,76 @@
+//! utoipa support.
+//!
+//! Besides `ToSchema` for `EntityTag`, provides `IntoParams` implementations describing
+//! conditional request headers, to be used as `#[utoipa::path(params(etag::utoipa::IfMatch))]`.
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use ::utoipa::{IntoParams, PartialSchema, ToSchema};
+use ::utoipa::openapi::{ObjectBuilder, RefOr, Required, Type};
+use ::utoipa::openapi::header::{Header, HeaderBuilder};
+use ::utoipa::openapi::path::{Parameter, ParameterBuilder, ParameterIn};
+use ::utoipa::openapi::schema::Schema;
+
+use crate::{EntityTag, MAX_TAG_LEN};
+
+impl PartialSchema for EntityTag {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new().schema_type(Type::String)
+                            .description(Some("Entity tag in format W/\"<etag_value>\" or \"<etag_value>\""))
+                            .pattern(Some("^(W/)?\"[\\x21\\x23-\\x7E]*\"$"))
+                            .min_length(Some(2))
+                            .max_length(Some(MAX_TAG_LEN + 4))
+                            .into()
+    }
+}
+
+impl ToSchema for EntityTag {
+    #[inline]
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("EntityTag")
+    }
+}
+
+fn list_schema() -> RefOr<Schema> {
+    ObjectBuilder::new().schema_type(Type::String)
+                        .description(Some("Comma separated list of entity tags or `*`"))
+                        .into()
+}
+
+fn list_parameter(name: &str, description: &str) -> Vec<Parameter> {
+    let param = ParameterBuilder::new().name(name)
+                                       .parameter_in(ParameterIn::Header)
+                                       .required(Required::False)
+                                       .description(Some(description))
+                                       .schema(Some(list_schema()))
+                                       .build();
+    alloc::vec![param]
+}
+
+/// `If-Match` request header parameter.
+pub struct IfMatch;
+
+impl IntoParams for IfMatch {
+    #[inline]
+    fn into_params(_: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        list_parameter("If-Match", "Request is performed only if current entity tag strongly matches any of provided tags")
+    }
+}
+
+/// `If-None-Match` request header parameter.
+pub struct IfNoneMatch;
+
+impl IntoParams for IfNoneMatch {
+    #[inline]
+    fn into_params(_: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        list_parameter("If-None-Match", "Request is performed only if current entity tag weakly matches none of provided tags")
+    }
+}
+
+/// Creates `ETag` response header description.
+pub fn etag_header() -> Header {
+    HeaderBuilder::new().schema(EntityTag::schema())
+                        .description(Some("Entity tag of the returned representation"))
+                        .build()
+}
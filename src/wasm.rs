@@ -0,0 +1,91 @@
+//! JavaScript bindings.
+//!
+//! Exposes `EntityTag` class to JavaScript, sharing the same parsing and comparison semantics.
+
+use alloc::string::{String, ToString};
+
+use ::wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::EntityTag;
+
+/// JavaScript `EntityTag` class.
+#[wasm_bindgen(js_name = EntityTag)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WasmEntityTag(EntityTag);
+
+#[wasm_bindgen(js_class = EntityTag)]
+impl WasmEntityTag {
+    #[wasm_bindgen(constructor)]
+    /// Constructs a new tag, verifying it's size and whether it includes ASCII.
+    pub fn new(weak: bool, tag: &str) -> Result<WasmEntityTag, JsError> {
+        match EntityTag::checked_new(weak, tag) {
+            Ok(tag) => Ok(Self(tag)),
+            Err(error) => Err(JsError::new(error.as_str())),
+        }
+    }
+
+    /// Parses tag from its header representation.
+    pub fn parse(text: &str) -> Result<WasmEntityTag, JsError> {
+        match text.parse() {
+            Ok(tag) => Ok(Self(tag)),
+            Err(error) => Err(JsError::new(error.as_str())),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromData)]
+    /// Creates strong tag by hashing provided bytes.
+    pub fn from_data(bytes: &[u8]) -> WasmEntityTag {
+        Self(EntityTag::from_data(bytes))
+    }
+
+    #[wasm_bindgen(getter)]
+    /// Weakness indicator for the tag.
+    pub fn weak(&self) -> bool {
+        self.0.is_weak()
+    }
+
+    #[wasm_bindgen(getter, js_name = isAny)]
+    /// Whether the tag is wildcard `*`.
+    pub fn is_any(&self) -> bool {
+        self.0.is_any()
+    }
+
+    #[wasm_bindgen(getter)]
+    /// The opaque tag.
+    pub fn tag(&self) -> String {
+        self.0.tag().to_string()
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    /// Header representation of the tag.
+    pub fn to_string(&self) -> String {
+        self.0.as_header_str().to_string()
+    }
+
+    #[wasm_bindgen(js_name = strongEq)]
+    /// Performs strong comparison.
+    pub fn strong_eq(&self, other: &WasmEntityTag) -> bool {
+        self.0.strong_eq(&other.0)
+    }
+
+    #[wasm_bindgen(js_name = weakEq)]
+    /// Performs weak comparison.
+    pub fn weak_eq(&self, other: &WasmEntityTag) -> bool {
+        self.0.weak_eq(&other.0)
+    }
+}
+
+impl From<EntityTag> for WasmEntityTag {
+    #[inline]
+    fn from(tag: EntityTag) -> Self {
+        Self(tag)
+    }
+}
+
+impl From<WasmEntityTag> for EntityTag {
+    #[inline]
+    fn from(tag: WasmEntityTag) -> Self {
+        tag.0
+    }
+}
@@ -0,0 +1,133 @@
+//! Tags of ZIP archive members, derived from stored CRC-32 and uncompressed size.
+//!
+//! Tag of the member is the same as `EntityTag::const_from_data_crc32` of its uncompressed content,
+//! but it is produced without decompressing it.
+//!
+//! ```rust
+//! use etag::EntityTag;
+//!
+//! //Central directory header of `hello.txt`, stored without compression.
+//! let mut header = Vec::new();
+//! header.extend_from_slice(&0x02014b50u32.to_le_bytes());
+//! header.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+//! header.extend_from_slice(&0x3610a686u32.to_le_bytes());
+//! header.extend_from_slice(&5u32.to_le_bytes());
+//! header.extend_from_slice(&5u32.to_le_bytes());
+//! header.extend_from_slice(&9u16.to_le_bytes());
+//! header.extend_from_slice(&[0; 16]);
+//! header.extend_from_slice(b"hello.txt");
+//!
+//! let entry = etag::zip::parse_central_header(&header).unwrap();
+//! assert_eq!(entry.name, b"hello.txt");
+//! assert_eq!(entry.etag(), EntityTag::const_from_data_crc32(b"hello"));
+//! ```
+
+use crate::EntityTag;
+
+const CENTRAL_SIGNATURE: u32 = 0x02014b50;
+const CENTRAL_HEADER_LEN: usize = 46;
+const LOCAL_SIGNATURE: u32 = 0x04034b50;
+const LOCAL_HEADER_LEN: usize = 30;
+///Indicates that CRC-32 and sizes are stored in data descriptor after content.
+const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+const ZIP64_MARKER: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Member of ZIP archive.
+pub struct ZipEntry<'a> {
+    /// Name of the member, as it is stored in archive.
+    pub name: &'a [u8],
+    /// CRC-32 of uncompressed content.
+    pub crc32: u32,
+    /// Size of uncompressed content.
+    pub size: u64,
+    /// Length of the header, including variable length fields.
+    ///
+    /// For central directory header, it is offset of the next header.
+    pub header_len: usize,
+}
+
+impl ZipEntry<'_> {
+    #[inline]
+    /// Returns strong tag of the member.
+    pub const fn etag(&self) -> EntityTag {
+        EntityTag::from_crc32(self.crc32, self.size)
+    }
+}
+
+#[inline(always)]
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[inline(always)]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+///Returns uncompressed size from ZIP64 extended information, which is always its first field.
+fn zip64_size(mut extra: &[u8]) -> Option<u64> {
+    while extra.len() >= 4 {
+        let id = read_u16(extra, 0);
+        let len = read_u16(extra, 2) as usize;
+        let data = extra.get(4..4 + len)?;
+        if id == ZIP64_EXTRA_ID {
+            let size = data.get(..8)?;
+            return Some(u64::from_le_bytes([size[0], size[1], size[2], size[3], size[4], size[5], size[6], size[7]]));
+        }
+        extra = &extra[4 + len..];
+    }
+
+    None
+}
+
+fn parse_entry(record: &[u8], fixed_len: usize, crc_offset: usize, name_offset: usize, tail_len: usize) -> Option<ZipEntry<'_>> {
+    let name_len = read_u16(record, name_offset) as usize;
+    let extra_len = read_u16(record, name_offset + 2) as usize;
+    let name = record.get(fixed_len..fixed_len + name_len)?;
+    let extra = record.get(fixed_len + name_len..fixed_len + name_len + extra_len)?;
+    let header_len = fixed_len + name_len + extra_len + tail_len;
+    if record.len() < header_len {
+        return None;
+    }
+
+    let size = match read_u32(record, crc_offset + 8) {
+        ZIP64_MARKER => zip64_size(extra)?,
+        size => size as u64,
+    };
+
+    Some(ZipEntry {
+        name,
+        crc32: read_u32(record, crc_offset),
+        size,
+        header_len,
+    })
+}
+
+/// Parses central directory file header at the start of `record`.
+///
+/// Returns `None` if header is truncated or malformed.
+pub fn parse_central_header(record: &[u8]) -> Option<ZipEntry<'_>> {
+    if record.len() < CENTRAL_HEADER_LEN || read_u32(record, 0) != CENTRAL_SIGNATURE {
+        return None;
+    }
+
+    let comment_len = read_u16(record, 32) as usize;
+    parse_entry(record, CENTRAL_HEADER_LEN, 16, 28, comment_len)
+}
+
+/// Parses local file header at the start of `record`.
+///
+/// Returns `None` if header is truncated or malformed, or if CRC-32 and sizes are stored in data
+/// descriptor, in which case central directory header has to be used instead.
+pub fn parse_local_header(record: &[u8]) -> Option<ZipEntry<'_>> {
+    if record.len() < LOCAL_HEADER_LEN || read_u32(record, 0) != LOCAL_SIGNATURE {
+        return None;
+    }
+    if read_u16(record, 6) & FLAG_DATA_DESCRIPTOR != 0 {
+        return None;
+    }
+
+    parse_entry(record, LOCAL_HEADER_LEN, 14, 26, 0)
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use etag::EntityTag;
+
+#[test]
+fn test_arbitrary_roundtrip() {
+    let mut data = [0u8; 4096];
+    for (idx, byte) in data.iter_mut().enumerate() {
+        *byte = (idx * 31 % 251) as u8;
+    }
+
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let tag = EntityTag::arbitrary(&mut u).expect("To generate tag");
+        let parsed = tag.to_string().parse::<EntityTag>().expect("To parse generated tag");
+        assert_eq!(parsed, tag);
+        assert!(!tag.tag().contains('"'));
+    }
+}
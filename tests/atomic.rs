@@ -0,0 +1,58 @@
+use etag::{AtomicEntityTag, EntityTag};
+
+#[test]
+fn test_atomic_load_store() {
+    let cell = AtomicEntityTag::new(&EntityTag::strong_unchecked("first"));
+    assert_eq!(cell.load(), EntityTag::strong_unchecked("first"));
+
+    cell.store(&EntityTag::weak_unchecked("second"));
+    assert_eq!(cell.load(), EntityTag::weak_unchecked("second"));
+
+    let max = "1".repeat(EntityTag::MAX_TAG_LEN);
+    cell.store(&EntityTag::weak_unchecked(&max));
+    assert_eq!(cell.load(), EntityTag::weak_unchecked(&max));
+    cell.store(&EntityTag::strong_unchecked(""));
+    assert_eq!(cell.load(), EntityTag::strong_unchecked(""));
+    cell.store(&EntityTag::ANY);
+    assert_eq!(cell.load(), EntityTag::ANY);
+}
+
+#[test]
+fn test_atomic_concurrent() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let first = EntityTag::strong_unchecked("first");
+    let second = EntityTag::weak_unchecked("second-and-longer");
+    let cell = Arc::new(AtomicEntityTag::new(&first));
+
+    let readers = (0..4).map(|_| {
+        let cell = cell.clone();
+        let first = first.clone();
+        let second = second.clone();
+        thread::spawn(move || {
+            for _ in 0..10_000 {
+                let tag = cell.load();
+                assert!(tag == first || tag == second, "Torn read {:?}", tag);
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    let writers = (0..2).map(|_| {
+        let cell = cell.clone();
+        let first = first.clone();
+        let second = second.clone();
+        thread::spawn(move || {
+            for idx in 0..10_000 {
+                match idx % 2 {
+                    0 => cell.store(&second),
+                    _ => cell.store(&first),
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    for handle in readers.into_iter().chain(writers) {
+        handle.join().expect("To finish");
+    }
+}
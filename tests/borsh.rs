@@ -0,0 +1,29 @@
+#![cfg(feature = "borsh")]
+
+use etag::EntityTag;
+
+#[test]
+fn test_borsh_roundtrip() {
+    for tag in [EntityTag::strong_unchecked("foobar"), EntityTag::weak_unchecked("weak-tag"), EntityTag::strong_unchecked(""), EntityTag::from_data(b"12")].iter() {
+        let bytes = borsh::to_vec(tag).expect("To serialize");
+        assert_eq!(bytes.len(), 1 + 4 + tag.tag().len());
+        assert_eq!(borsh::from_slice::<EntityTag>(&bytes).expect("To deserialize"), *tag);
+    }
+}
+
+#[test]
+fn test_borsh_any() {
+    let bytes = borsh::to_vec(&EntityTag::ANY).expect("To serialize");
+    assert_eq!(bytes, [2]);
+    assert!(borsh::from_slice::<EntityTag>(&bytes).expect("To deserialize").is_any());
+    assert!(borsh::from_slice::<EntityTag>(&[3, 0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn test_borsh_invalid() {
+    let bytes = borsh::to_vec(&(true, "ろり")).expect("To serialize");
+    assert!(borsh::from_slice::<EntityTag>(&bytes).is_err());
+
+    let bytes = borsh::to_vec(&(false, "1".repeat(EntityTag::MAX_TAG_LEN + 1))).expect("To serialize");
+    assert!(borsh::from_slice::<EntityTag>(&bytes).is_err());
+}
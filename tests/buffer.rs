@@ -0,0 +1,132 @@
+use core::fmt::Write;
+use core::hash::{Hash, Hasher};
+
+use etag::{EntityTag, StrBuf};
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_str_buf_capacity() {
+    let buf = StrBuf::<0>::new();
+    assert_eq!(buf.capacity(), 0);
+    assert_eq!(buf.remaining(), 0);
+    assert!(buf.is_empty());
+    assert_eq!(buf.as_str(), "");
+    assert!(StrBuf::<0>::from_str_checked("").is_some());
+    assert!(StrBuf::<0>::from_str_checked("a").is_none());
+
+    let max = "a".repeat(u8::MAX as usize);
+    let buf = StrBuf::<255>::from_str_checked(&max).expect("To fit");
+    assert_eq!(buf.len(), 255);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf, max.as_str());
+
+    let above_max = "a".repeat(u8::MAX as usize + 1);
+    assert!(StrBuf::<255>::from_str_checked(&above_max).is_none());
+
+    assert_eq!(StrBuf::<4>::from_str_checked("abcd").unwrap(), "abcd");
+    assert!(StrBuf::<4>::from_str_checked("abcde").is_none());
+}
+
+#[test]
+fn test_str_buf_push_overflow() {
+    let mut buf = StrBuf::<8>::new();
+    assert_eq!(buf.push_str("abc"), 3);
+    assert_eq!(buf.remaining(), 5);
+    //Truncated at char boundary
+    assert_eq!(buf.push_str("дёф"), 4);
+    assert_eq!(buf, "abcдё");
+    assert_eq!(buf.push_str("x"), 1);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf.push_str("y"), 0);
+    assert_eq!(buf.as_bytes(), "abcдёx".as_bytes());
+
+    let mut buf = StrBuf::<3>::new();
+    assert_eq!(buf.push_str("ab"), 2);
+    assert_eq!(buf.push_str("ё"), 0);
+    assert_eq!(buf, "ab");
+
+    let mut buf = StrBuf::<4>::new();
+    assert!(write!(buf, "{}", 1234).is_ok());
+    assert!(write!(buf, "5").is_err());
+    assert_eq!(buf, "1234");
+
+    buf.clear();
+    assert!(buf.is_empty());
+    assert_eq!(buf.remaining(), 4);
+    assert_eq!(buf.push_str("ё"), 2);
+    assert_eq!(buf, "ё");
+}
+
+#[test]
+fn test_str_buf_eq_hash_written_bytes() {
+    let mut first = StrBuf::<8>::new();
+    first.push_str("abcdefgh");
+    first.clear();
+    first.push_str("abc");
+
+    let mut second = StrBuf::<8>::new();
+    second.push_str("xyz");
+    second.clear();
+    second.push_str("abc");
+
+    //Stale bytes past cursor differ, but are never compared
+    assert_eq!(first, second);
+    assert_eq!(hash(&first), hash(&second));
+    assert_eq!(first, "abc");
+    assert_eq!(first, *"abc");
+
+    second.push_str("d");
+    assert_ne!(first, second);
+    assert_ne!(hash(&first), hash(&second));
+}
+
+#[test]
+fn test_str_buf_truncate() {
+    let mut buf = StrBuf::<8>::new();
+    assert_eq!(buf.push_str("abcдё"), 7);
+
+    buf.truncate(5);
+    assert_eq!(buf, "abcд");
+    buf.truncate(6);
+    assert_eq!(buf.len(), 5);
+    buf.clear();
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_str_buf_copy_partial() {
+    let mut buf = StrBuf::<16>::new();
+    assert!(write!(buf, "{}-{}", 12, 34).is_ok());
+
+    //Copies storage with uninitialized tail
+    let copy = buf;
+    let cloned = Clone::clone(&copy);
+    assert_eq!(copy, buf);
+    assert_eq!(cloned.as_str(), "12-34");
+
+    let mut cloned = cloned;
+    assert!(cloned.try_push_str("56"));
+    assert_eq!(cloned, "12-3456");
+    assert_eq!(buf, "12-34");
+}
+
+#[test]
+fn test_tag_storage() {
+    let any = EntityTag::ANY;
+    assert_eq!(any.clone(), EntityTag::ANY);
+    assert_eq!(any.to_string(), "*");
+
+    let tag = EntityTag::from_data(b"content");
+    assert_eq!(tag.clone(), tag);
+    assert_eq!(EntityTag::from_crc32(0, 7).tag(), "7-0");
+
+    let long = "a".repeat(EntityTag::MAX_TAG_LEN);
+    let tag = EntityTag::weak_unchecked(&long);
+    assert_eq!(tag.clone().tag(), long);
+    assert_eq!(tag.to_string().len(), EntityTag::MAX_TAG_LEN + 4);
+}
@@ -0,0 +1,165 @@
+use etag::{EntityTag, EtagCache};
+
+#[test]
+fn test_etag_cache_lru() {
+    let mut cache = EtagCache::<&str, 2>::new();
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), 2);
+
+    assert_eq!(cache.insert("/first", EntityTag::strong_unchecked("1")), None);
+    assert_eq!(cache.insert("/second", EntityTag::strong_unchecked("2")), None);
+    assert_eq!(cache.len(), 2);
+
+    //Make first one the most recently used
+    assert_eq!(cache.get(&"/first"), Some(&EntityTag::strong_unchecked("1")));
+    assert_eq!(cache.insert("/third", EntityTag::strong_unchecked("3")), Some(("/second", EntityTag::strong_unchecked("2"))));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.peek(&"/second"), None);
+
+    //Peek doesn't affect order
+    assert_eq!(cache.peek(&"/first"), Some(&EntityTag::strong_unchecked("1")));
+    assert_eq!(cache.insert("/fourth", EntityTag::strong_unchecked("4")), Some(("/first", EntityTag::strong_unchecked("1"))));
+    assert_eq!(cache.get(&"/third"), Some(&EntityTag::strong_unchecked("3")));
+    assert_eq!(cache.get(&"/fourth"), Some(&EntityTag::strong_unchecked("4")));
+}
+
+#[test]
+fn test_etag_cache_replace_remove() {
+    let mut cache = EtagCache::<u32, 3>::default();
+
+    assert_eq!(cache.insert(1, EntityTag::strong_unchecked("1")), None);
+    assert_eq!(cache.insert(2, EntityTag::strong_unchecked("2")), None);
+    assert_eq!(cache.insert(1, EntityTag::weak_unchecked("1")), Some((1, EntityTag::strong_unchecked("1"))));
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(cache.remove(&1), Some(EntityTag::weak_unchecked("1")));
+    assert_eq!(cache.remove(&1), None);
+    assert_eq!(cache.len(), 1);
+
+    assert_eq!(cache.insert(3, EntityTag::strong_unchecked("3")), None);
+    assert_eq!(cache.insert(4, EntityTag::strong_unchecked("4")), None);
+    assert_eq!(cache.insert(5, EntityTag::strong_unchecked("5")), Some((2, EntityTag::strong_unchecked("2"))));
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&3), None);
+    assert_eq!(cache.insert(6, EntityTag::strong_unchecked("6")), None);
+    assert_eq!(cache.get(&6), Some(&EntityTag::strong_unchecked("6")));
+}
+
+#[test]
+fn test_etag_cache_zero_capacity() {
+    let mut cache = EtagCache::<u32, 0>::new();
+    assert_eq!(cache.insert(1, EntityTag::strong_unchecked("1")), Some((1, EntityTag::strong_unchecked("1"))));
+    assert_eq!(cache.get(&1), None);
+}
+
+#[test]
+fn test_etag_cache_index() {
+    let mut cache = EtagCache::<u32, 16>::new();
+
+    for key in 0..65u32 {
+        cache.insert(key, EntityTag::strong_unchecked("tag"));
+        if key % 3 == 0 {
+            assert_eq!(cache.remove(&key), Some(EntityTag::strong_unchecked("tag")));
+        }
+    }
+    assert_eq!(cache.len(), 16);
+
+    //Only the most recently inserted keys remain, regardless of removals shifting index.
+    let mut remaining = 0;
+    for key in 0..65u32 {
+        match cache.peek(&key) {
+            Some(_) => {
+                assert!(key % 3 != 0 && key >= 41);
+                remaining += 1;
+            },
+            None => assert!(key % 3 == 0 || key < 41),
+        }
+    }
+    assert_eq!(remaining, 16);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_validator_cache_capacity() {
+    use std::time::Duration;
+    use etag::ValidatorCache;
+
+    let cache = ValidatorCache::new(2, Duration::from_secs(60));
+    assert!(cache.is_empty());
+
+    cache.insert("/first", EntityTag::strong_unchecked("1"));
+    cache.insert("/second", EntityTag::strong_unchecked("2"));
+    cache.insert("/first", EntityTag::strong_unchecked("1.1"));
+    assert_eq!(cache.len(), 2);
+    cache.insert("/third", EntityTag::strong_unchecked("3"));
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(cache.get(&"/second"), None);
+    assert_eq!(cache.get(&"/first"), Some(EntityTag::strong_unchecked("1.1")));
+    assert_eq!(cache.get(&"/third"), Some(EntityTag::strong_unchecked("3")));
+
+    //Access refreshes recency, so the least recently used tag is evicted.
+    assert_eq!(cache.get(&"/first"), Some(EntityTag::strong_unchecked("1.1")));
+    cache.insert("/fourth", EntityTag::strong_unchecked("4"));
+    assert_eq!(cache.get(&"/third"), None);
+    assert_eq!(cache.get(&"/first"), Some(EntityTag::strong_unchecked("1.1")));
+    for _ in 0..8 {
+        assert_eq!(cache.get(&"/fourth"), Some(EntityTag::strong_unchecked("4")));
+    }
+    cache.insert("/third", EntityTag::strong_unchecked("3"));
+    assert_eq!(cache.get(&"/first"), None);
+    assert_eq!(cache.get(&"/fourth"), Some(EntityTag::strong_unchecked("4")));
+
+    assert_eq!(cache.remove(&"/third"), Some(EntityTag::strong_unchecked("3")));
+    assert_eq!(cache.len(), 1);
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_validator_cache_ttl() {
+    use std::time::Duration;
+    use etag::ValidatorCache;
+
+    let cache = ValidatorCache::new(8, Duration::from_millis(50));
+
+    let mut computed = 0;
+    let tag = cache.get_or_compute(1, || {
+        computed += 1;
+        EntityTag::strong_unchecked("1")
+    });
+    assert_eq!(tag, EntityTag::strong_unchecked("1"));
+    let tag = cache.get_or_compute(1, || {
+        computed += 1;
+        EntityTag::strong_unchecked("1")
+    });
+    assert_eq!(tag, EntityTag::strong_unchecked("1"));
+    assert_eq!(computed, 1);
+
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(cache.get(&1), None);
+    let tag = cache.get_or_compute(1, || {
+        computed += 1;
+        EntityTag::strong_unchecked("2")
+    });
+    assert_eq!(tag, EntityTag::strong_unchecked("2"));
+    assert_eq!(computed, 2);
+    assert_eq!(cache.len(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_validator_cache_unbounded_ttl() {
+    use std::time::Duration;
+    use etag::ValidatorCache;
+
+    let cache = ValidatorCache::new(4, Duration::MAX);
+    cache.insert(1, EntityTag::strong_unchecked("1"));
+    cache.insert(2, EntityTag::strong_unchecked("2"));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&1), Some(EntityTag::strong_unchecked("1")));
+    assert_eq!(cache.get(&2), Some(EntityTag::strong_unchecked("2")));
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "cache-digest")]
+
+use etag::EntityTag;
+use etag::cache_digest::CacheDigest;
+
+#[test]
+fn test_cache_digest() {
+    let tags: Vec<(String, EntityTag)> = (0..100).map(|idx| (format!("https://example.com/{}.js", idx), EntityTag::from_data(&[idx as u8]))).collect();
+    let digest = CacheDigest::new(tags.iter().map(|(url, tag)| (url.as_str(), Some(tag))), 8);
+    assert!(digest.len() <= 100);
+    assert!(!digest.is_empty());
+
+    let encoded = digest.encode();
+    let received = CacheDigest::parse(&encoded).unwrap();
+    assert_eq!(received, digest);
+
+    for (url, tag) in tags.iter() {
+        assert!(received.contains(url, Some(tag)));
+    }
+    let false_positives = tags.iter().filter(|(url, _)| received.contains(url, Some(&EntityTag::strong_unchecked("other")))).count();
+    assert!(false_positives < 5, "{}", false_positives);
+}
+
+#[test]
+fn test_cache_digest_encoding() {
+    let empty = CacheDigest::new(None, 7);
+    assert!(empty.is_empty());
+    //5 bits of log2(N) = 0, followed by 5 bits of log2(P) = 7, without values
+    assert_eq!(empty.encode(), [0b0000_0001, 0b1100_0000]);
+    assert_eq!(CacheDigest::parse(&empty.encode()).unwrap(), empty);
+
+    let digest = CacheDigest::new([("/", None), ("/", None)], 0);
+    assert_eq!(digest.len(), 1);
+    assert!(digest.contains("/", None));
+    assert_eq!(CacheDigest::parse(&digest.encode()).unwrap(), digest);
+
+    assert_eq!(CacheDigest::parse(&[]), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(CacheDigest::parse(&[0]), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(CacheDigest::parse(&[0b0000_0000, 0b0000_0000]), Err(etag::ParseError::InvalidFormat));
+    assert!(CacheDigest::parse(&[0b1111_1111, 0b1100_0000]).unwrap().is_empty());
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "cas")]
+
+use std::fs;
+
+use etag::EntityTag;
+use etag::cas::BlobStore;
+
+fn store(name: &str) -> BlobStore {
+    let root = std::env::temp_dir().join(format!("etag-cas-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    BlobStore::open(root).expect("To open store")
+}
+
+#[test]
+fn test_put_get() {
+    let store = store("put");
+    let tag = store.put(b"blob").unwrap();
+    //Key is always xxh3, regardless of hash-* features.
+    let expected = format!("4-{}", xxhash_rust::xxh3::xxh3_128(b"blob"));
+    assert_eq!(tag, EntityTag::checked_strong(&expected).unwrap());
+    assert!(store.contains(&tag));
+    assert_eq!(store.get(&tag).unwrap(), Some(b"blob".to_vec()));
+    assert_eq!(store.path(&tag), Some(store.root().join(tag.tag())));
+
+    assert_eq!(store.put(b"blob").unwrap(), tag);
+    assert_eq!(fs::read_dir(store.root()).unwrap().count(), 1);
+
+    let empty = store.put(b"").unwrap();
+    assert_eq!(store.get(&empty).unwrap(), Some(Vec::new()));
+
+    let missing = EntityTag::from_data(b"missing");
+    assert!(!store.contains(&missing));
+    assert_eq!(store.get(&missing).unwrap(), None);
+
+    fs::remove_dir_all(store.root()).unwrap();
+}
+
+#[test]
+fn test_invalid_tags() {
+    let store = store("invalid");
+    let tag = store.put(b"blob").unwrap();
+
+    for tag in [EntityTag::weak_unchecked(tag.tag()), EntityTag::strong_unchecked("../blob"), EntityTag::ANY] {
+        assert_eq!(store.path(&tag), None);
+        assert!(!store.contains(&tag));
+        assert_eq!(store.get(&tag).unwrap(), None);
+        assert!(!store.remove(&tag).unwrap());
+    }
+
+    fs::remove_dir_all(store.root()).unwrap();
+}
+
+#[test]
+fn test_corrupted_blob() {
+    let store = store("corrupted");
+    let tag = store.put(b"blob").unwrap();
+    fs::write(store.path(&tag).unwrap(), b"blub").unwrap();
+
+    assert!(store.contains(&tag));
+    assert_eq!(store.get(&tag).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(store.root()).unwrap();
+}
+
+#[test]
+fn test_remove() {
+    let store = store("remove");
+    let tag = store.put(b"blob").unwrap();
+    assert!(store.remove(&tag).unwrap());
+    assert!(!store.remove(&tag).unwrap());
+    assert!(!store.contains(&tag));
+
+    fs::remove_dir_all(store.root()).unwrap();
+}
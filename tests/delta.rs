@@ -0,0 +1,27 @@
+use etag::EntityTag;
+use etag::delta::{negotiate, DeltaDecision};
+
+#[test]
+fn test_negotiate() {
+    let current = EntityTag::strong_unchecked("v3");
+    let has_base = |base: &EntityTag| base.tag() != "v0";
+
+    assert_eq!(negotiate(&current, Some("\"v1\", \"v3\""), Some("vcdiff"), &["vcdiff"], has_base), DeltaDecision::NotModified);
+    assert_eq!(negotiate(&current, None, Some("vcdiff"), &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("\"v1\""), None, &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("\"v1\""), Some("gzip"), &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("\"v1\""), Some("vcdiff;q=0.0"), &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("\"v1\""), Some("vcdiff; q=0"), &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("W/\"v1\", \"v0\""), Some("vcdiff"), &["vcdiff"], has_base), DeltaDecision::Full);
+    assert_eq!(negotiate(&current, Some("*"), Some("vcdiff"), &["vcdiff"], has_base), DeltaDecision::NotModified);
+
+    match negotiate(&current, Some("\"v0\", W/\"v1\", \"v2\", \"v1\""), Some("gzip, VCDIFF;q=0.5, diffe"), &["diffe", "vcdiff"], has_base) {
+        DeltaDecision::Delta(response) => {
+            assert_eq!(response.status(), 226);
+            assert_eq!(response.im(), "vcdiff");
+            assert_eq!(response.base(), &EntityTag::strong_unchecked("v2"));
+            assert_eq!(response.headers(), [("IM", "vcdiff"), ("Delta-Base", "\"v2\""), ("ETag", "\"v3\"")]);
+        },
+        decision => panic!("Unexpected decision {:?}", decision),
+    }
+}
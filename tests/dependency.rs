@@ -0,0 +1,69 @@
+#![cfg(feature = "alloc")]
+
+use etag::EntityTag;
+use etag::dependency::EtagDependencyTracker;
+
+#[test]
+fn test_dependency_tracker() {
+    let user = EntityTag::strong_unchecked("user-v1");
+    let posts = EntityTag::strong_unchecked("posts-v7");
+    let feed = EntityTag::strong_unchecked("feed-1");
+
+    let mut tracker = EtagDependencyTracker::new();
+    assert!(tracker.is_empty());
+    tracker.record("/profile", EntityTag::strong_unchecked("profile-1"), [("user", &user), ("posts", &posts)]);
+    tracker.record("/feed", feed.clone(), [("posts", &posts)]);
+    tracker.record("/index", EntityTag::strong_unchecked("index-1"), [("/feed", &feed)]);
+    assert_eq!(tracker.len(), 3);
+
+    assert_eq!(tracker.tag("/feed"), Some(&feed));
+    assert_eq!(tracker.tag("user"), None);
+    assert_eq!(tracker.sources("/profile").collect::<Vec<_>>(), [("user", &user), ("posts", &posts)]);
+    assert_eq!(tracker.dependents("posts").collect::<Vec<_>>(), ["/feed", "/profile"]);
+    assert_eq!(tracker.dependents("/index").count(), 0);
+
+    assert!(tracker.invalidated("posts", &posts).is_empty());
+    assert!(tracker.invalidated("unknown", &posts).is_empty());
+    assert_eq!(tracker.invalidated("posts", &EntityTag::weak_unchecked("posts-v7")), ["/feed", "/index", "/profile"]);
+    assert_eq!(tracker.invalidated("/feed", &EntityTag::strong_unchecked("feed-2")), ["/index"]);
+
+    tracker.record("/feed", EntityTag::strong_unchecked("feed-2"), [("user", &user)]);
+    assert_eq!(tracker.len(), 3);
+    assert_eq!(tracker.dependents("posts").collect::<Vec<_>>(), ["/profile"]);
+    assert_eq!(tracker.invalidated("user", &EntityTag::strong_unchecked("user-v2")), ["/feed", "/index", "/profile"]);
+
+    assert_eq!(tracker.remove("/profile"), Some(EntityTag::strong_unchecked("profile-1")));
+    assert_eq!(tracker.remove("/profile"), None);
+    assert_eq!(tracker.dependents("posts").count(), 0);
+    assert!(tracker.invalidated("posts", &EntityTag::strong_unchecked("posts-v8")).is_empty());
+    assert_eq!(tracker.len(), 2);
+}
+
+#[test]
+fn test_dependency_tracker_weak_source() {
+    let config = EntityTag::weak_unchecked("1700000000.0-42");
+
+    let mut tracker = EtagDependencyTracker::new();
+    tracker.record("/settings", EntityTag::strong_unchecked("settings-1"), [("config", &config)]);
+    tracker.record("/index", EntityTag::strong_unchecked("index-1"), [("/settings", &EntityTag::strong_unchecked("settings-1"))]);
+
+    //Unchanged weak source is not stale
+    assert!(tracker.invalidated("config", &config).is_empty());
+    assert!(tracker.invalidated("config", &EntityTag::weak_unchecked("1700000000.0-42")).is_empty());
+    assert_eq!(tracker.invalidated("config", &EntityTag::weak_unchecked("1700000001.0-42")), ["/index", "/settings"]);
+    //Change of weakness alone is a change
+    assert_eq!(tracker.invalidated("config", &EntityTag::strong_unchecked("1700000000.0-42")), ["/index", "/settings"]);
+}
+
+#[test]
+fn test_dependency_tracker_cycle() {
+    let a = EntityTag::strong_unchecked("a");
+    let b = EntityTag::strong_unchecked("b");
+
+    let mut tracker = EtagDependencyTracker::new();
+    tracker.record("a", a.clone(), [("b", &b)]);
+    tracker.record("b", b.clone(), [("a", &a)]);
+
+    assert!(tracker.invalidated("a", &a).is_empty());
+    assert_eq!(tracker.invalidated("a", &EntityTag::strong_unchecked("a2")), ["a", "b"]);
+}
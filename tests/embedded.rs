@@ -0,0 +1,143 @@
+use etag::EntityTag;
+use etag::embedded::{conditional_get, RequestHeaders};
+
+struct Request {
+    if_none_match: Option<&'static str>,
+}
+
+impl RequestHeaders for Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        match name.eq_ignore_ascii_case("if-none-match") {
+            true => self.if_none_match,
+            false => None,
+        }
+    }
+}
+
+#[test]
+fn test_conditional_get() {
+    let tag = EntityTag::weak_unchecked("v1");
+
+    let response = conditional_get(&Request { if_none_match: Some("\"v0\", \"v1\"") }, &tag);
+    assert!(response.is_not_modified());
+    assert_eq!(response.status(), 304);
+    assert_eq!(response.headers(), [("ETag", "W/\"v1\"")]);
+
+    let response = conditional_get(&Request { if_none_match: Some("\"v0\"") }, &tag);
+    assert!(!response.is_not_modified());
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers(), [("ETag", "W/\"v1\"")]);
+
+    let response = conditional_get(&Request { if_none_match: None }, &tag);
+    assert_eq!(response.status(), 200);
+}
+
+#[test]
+fn test_conditional_get_header_list() {
+    let tag = EntityTag::strong_unchecked("v1");
+    let request: &[(&str, &str)] = &[("if-none-match", "*")];
+    assert_eq!(request.header("IF-NONE-MATCH"), Some("*"));
+    assert_eq!(conditional_get(request, &tag).status(), 304);
+    assert_eq!(conditional_get(&[][..], &tag).status(), 200);
+}
+
+#[cfg(feature = "embedded-svc")]
+mod svc {
+    use core::convert::Infallible;
+
+    use embedded_svc::http::server::{Connection, Headers, Method, Query, Request};
+    use embedded_svc::io::{ErrorType, Read, Write};
+    use etag::EntityTag;
+    use etag::embedded::conditional_get;
+
+    #[derive(Default)]
+    struct Conn {
+        if_none_match: Option<&'static str>,
+        status: Option<u16>,
+        etag: Option<String>,
+    }
+
+    impl Query for Conn {
+        fn uri(&self) -> &str {
+            "/"
+        }
+
+        fn method(&self) -> Method {
+            Method::Get
+        }
+    }
+
+    impl Headers for Conn {
+        fn header(&self, name: &str) -> Option<&str> {
+            match name.eq_ignore_ascii_case("if-none-match") {
+                true => self.if_none_match,
+                false => None,
+            }
+        }
+    }
+
+    impl ErrorType for Conn {
+        type Error = Infallible;
+    }
+
+    impl Read for Conn {
+        fn read(&mut self, _: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(0)
+        }
+    }
+
+    impl Write for Conn {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl Connection for Conn {
+        type Headers = Self;
+        type Read = Self;
+        type RawConnectionError = Infallible;
+        type RawConnection = Self;
+
+        fn split(&mut self) -> (&Self::Headers, &mut Self::Read) {
+            unimplemented!()
+        }
+
+        fn initiate_response<'a>(&'a mut self, status: u16, _: Option<&'a str>, headers: &'a [(&'a str, &'a str)]) -> Result<(), Self::Error> {
+            self.status = Some(status);
+            self.etag = headers.iter().find(|(name, _)| *name == "ETag").map(|(_, value)| value.to_string());
+            Ok(())
+        }
+
+        fn is_response_initiated(&self) -> bool {
+            self.status.is_some()
+        }
+
+        fn raw_connection(&mut self) -> Result<&mut Self::RawConnection, Self::Error> {
+            Ok(self)
+        }
+    }
+
+    #[test]
+    fn test_conditional_get_svc() {
+        let tag = EntityTag::strong_unchecked("v1");
+
+        let mut conn = Conn { if_none_match: Some("\"v1\""), ..Conn::default() };
+        let request = Request::wrap(&mut conn);
+        let response = conditional_get(&request, &tag);
+        assert!(response.is_not_modified());
+        response.into_svc_response(request).unwrap();
+        assert_eq!(conn.status, Some(304));
+        assert_eq!(conn.etag.as_deref(), Some("\"v1\""));
+
+        let mut conn = Conn { if_none_match: Some("\"v0\""), ..Conn::default() };
+        let request = Request::wrap(&mut conn);
+        let response = conditional_get(&request, &tag);
+        response.into_svc_response(request).unwrap();
+        assert_eq!(conn.status, Some(200));
+        assert_eq!(conn.etag.as_deref(), Some("\"v1\""));
+    }
+}
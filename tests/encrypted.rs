@@ -0,0 +1,60 @@
+#![cfg(feature = "encrypted")]
+
+use etag::{EntityTag, ParseError};
+use etag::encrypted::TagCipher;
+
+const KEY: [u8; 32] = [1; 32];
+
+#[test]
+fn test_seal_open() {
+    let cipher = TagCipher::new(&KEY);
+    let tag = cipher.seal(b"payload").unwrap();
+    assert!(!tag.is_weak());
+    assert!(!tag.tag().contains("payload"));
+    assert_eq!(tag, cipher.seal(b"payload").unwrap());
+    assert_ne!(tag, cipher.seal(b"payloaD").unwrap());
+
+    let mut out = [0u8; TagCipher::MAX_PAYLOAD_LEN];
+    assert_eq!(cipher.open(&tag, &mut out), Ok(7));
+    assert_eq!(&out[..7], b"payload");
+    assert_eq!(cipher.open(&EntityTag::weak_unchecked(tag.tag()), &mut out), Ok(7));
+    assert_eq!(cipher.open(&tag, &mut out[..6]), Err(ParseError::Overflow));
+
+    let empty = cipher.seal(b"").unwrap();
+    assert_eq!(cipher.open(&empty, &mut out), Ok(0));
+
+    let max = [0xAA; TagCipher::MAX_PAYLOAD_LEN];
+    let tag = cipher.seal(&max).unwrap();
+    assert_eq!(cipher.open(&tag, &mut out), Ok(max.len()));
+    assert_eq!(out, max);
+    assert_eq!(cipher.seal(&[0; TagCipher::MAX_PAYLOAD_LEN + 1]), Err(ParseError::Overflow));
+}
+
+#[test]
+fn test_open_rejects_forged() {
+    let cipher = TagCipher::new(&KEY);
+    let mut out = [0u8; TagCipher::MAX_PAYLOAD_LEN];
+    let tag = cipher.seal(b"payload").unwrap();
+
+    assert_eq!(TagCipher::new(&[2; 32]).open(&tag, &mut out), Err(ParseError::InvalidFormat));
+
+    let mut forged = tag.tag().to_owned().into_bytes();
+    forged[0] = if forged[0] == b'A' { b'B' } else { b'A' };
+    let forged = EntityTag::strong_unchecked(std::str::from_utf8(&forged).unwrap());
+    assert_eq!(cipher.open(&forged, &mut out), Err(ParseError::InvalidFormat));
+
+    assert_eq!(cipher.open(&EntityTag::from_binary_tag(&[0; 15]).unwrap(), &mut out), Err(ParseError::InvalidFormat));
+    assert_eq!(cipher.open(&EntityTag::strong_unchecked("not base64!"), &mut out), Err(ParseError::InvalidFormat));
+    assert_eq!(cipher.open(&EntityTag::ANY, &mut out), Err(ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_version() {
+    let cipher = TagCipher::new(&KEY);
+    let tag = cipher.seal_version(u64::MAX, 1).unwrap();
+    assert_eq!(cipher.open_version(&tag), Ok((u64::MAX, 1)));
+    assert_ne!(tag, cipher.seal_version(u64::MAX, 2).unwrap());
+
+    let other = cipher.seal(b"payload").unwrap();
+    assert_eq!(cipher.open_version(&other), Err(ParseError::InvalidFormat));
+}
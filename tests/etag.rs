@@ -1,9 +1,56 @@
+#![allow(clippy::redundant_static_lifetimes)]
+
 extern crate etag;
 
 use etag::EntityTag;
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_string_conversion() {
+    let weak = EntityTag::weak_unchecked("xyzzy");
+    assert_eq!(String::from(&weak), "W/\"xyzzy\"");
+    assert_eq!(weak.tag_string(), "xyzzy");
+
+    let strong: String = EntityTag::strong_unchecked("xyzzy").into();
+    assert_eq!(strong, "\"xyzzy\"");
+    assert_eq!(String::from(EntityTag::ANY), "*");
+    assert_eq!(EntityTag::ANY.tag_string(), "");
+
+    use std::convert::TryFrom;
+    assert_eq!(EntityTag::try_from(String::from("W/\"xyzzy\"")), Ok(weak));
+    assert_eq!(EntityTag::try_from(String::from("xyzzy")), Err(etag::ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_try_from() {
+    use std::convert::{TryFrom, TryInto};
+
+    fn convert<T: TryInto<EntityTag>>(value: T) -> Result<EntityTag, T::Error> {
+        value.try_into()
+    }
+
+    assert_eq!(EntityTag::try_from("\"xyzzy\""), Ok(EntityTag::strong_unchecked("xyzzy")));
+    assert_eq!(convert("W/\"xyzzy\""), Ok(EntityTag::weak_unchecked("xyzzy")));
+    assert_eq!(convert("*"), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(convert("\"xyzzy"), Err(etag::ParseError::InvalidFormat));
+}
+
 #[cfg(feature = "std")]
 #[test]
+fn test_io_error() {
+    fn read_tag(text: &str) -> std::io::Result<EntityTag> {
+        Ok(text.parse()?)
+    }
+
+    assert_eq!(read_tag("\"xyzzy\"").unwrap(), EntityTag::strong_unchecked("xyzzy"));
+    let error = read_tag("xyzzy").unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), etag::ParseError::InvalidFormat.to_string());
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[allow(deprecated)]
 fn test_from_file_meta() {
     use std::fs;
 
@@ -11,12 +58,42 @@ fn test_from_file_meta() {
     let metadata = file.metadata().expect("To get metadata");
     let etag = EntityTag::from_file_meta(&metadata);
 
-    assert_eq!(etag.weak, true);
+    assert!(etag.is_weak());
     //Make sure we stick to format
     match metadata.modified().map(|modified| modified.duration_since(std::time::UNIX_EPOCH).expect("Modified is earlier than time::UNIX_EPOCH!")) {
         Ok(modified) => assert_eq!(format!("{}.{}-{}", modified.as_secs(), modified.subsec_nanos(), metadata.len()), etag.tag()),
         _ => assert_eq!(format!("{}", metadata.len()), etag.tag())
     }
+    assert_eq!(EntityTag::try_from_file_meta(&metadata), Some(etag));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_files() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join(format!("etag-from-files-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("To create dir");
+    let first = dir.join("first.css");
+    let second = dir.join("second.css");
+    fs::write(&first, "a{}").expect("To write");
+    fs::write(&second, "b{}").expect("To write");
+
+    let tag = EntityTag::from_files([&first, &second]).expect("To hash files");
+    assert!(!tag.is_weak());
+    assert_eq!(tag.content_components().map(|(len, _)| len), Some(6));
+    assert_eq!(EntityTag::from_files([&first, &second]).unwrap(), tag);
+    assert_ne!(EntityTag::from_files([&second, &first]).unwrap(), tag);
+    assert_ne!(EntityTag::from_files([&first]).unwrap(), tag);
+    assert_ne!(EntityTag::from_data(b"a{}b{}"), tag);
+
+    fs::write(&second, "b{}\n").expect("To write");
+    assert_ne!(EntityTag::from_files([&first, &second]).unwrap(), tag);
+
+    assert_eq!(EntityTag::from_files(core::iter::empty::<&str>()).unwrap(), EntityTag::from_files(Vec::<&str>::new()).unwrap());
+    assert_eq!(EntityTag::from_files([dir.join("missing")]).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+
+    fs::remove_dir_all(&dir).expect("To remove dir");
 }
 
 #[test]
@@ -55,15 +132,266 @@ fn test_etag_from_data() {
     assert_eq!(very_big, const_very_big);
 }
 
+#[test]
+fn test_etag_const_from_data_hashes() {
+    const FNV1A: EntityTag = EntityTag::const_from_data_fnv1a(b"123456789");
+    const CRC32: EntityTag = EntityTag::const_from_data_crc32(b"123456789");
+
+    assert!(!FNV1A.is_weak());
+    assert_eq!(FNV1A.tag(), format!("9-{}", 0x06d5573923c6cdfcu64));
+    assert_eq!(EntityTag::const_from_data_fnv1a(b"").tag(), format!("0-{}", 0xcbf29ce484222325u64));
+    assert!(!CRC32.is_weak());
+    assert_eq!(CRC32.tag(), format!("9-{}", 0xcbf43926u32));
+    assert_eq!(EntityTag::const_from_data_crc32(b"").tag(), "0-0");
+
+    const WYHASH: EntityTag = EntityTag::const_from_data_wyhash(b"123456789");
+    assert!(!WYHASH.is_weak());
+    assert_eq!(WYHASH.tag(), format!("9-{}", 0x78d5dcca46301417u64));
+}
+
+#[cfg(all(feature = "hash-fnv", not(feature = "hash-wyhash")))]
+#[test]
+fn test_etag_from_data_fnv() {
+    assert_eq!(EntityTag::from_data(b"123456789"), EntityTag::const_from_data_fnv1a(b"123456789"));
+    assert_eq!(EntityTag::from_data(b""), EntityTag::const_from_data_fnv1a(b""));
+}
+
+#[cfg(feature = "hash-wyhash")]
+#[test]
+fn test_etag_from_data_wyhash() {
+    assert_eq!(EntityTag::from_data(b"123456789"), EntityTag::const_from_data_wyhash(b"123456789"));
+    assert_eq!(EntityTag::const_from_data(b""), EntityTag::const_from_data_wyhash(b""));
+}
+
+#[test]
+fn test_etag_from_data_with() {
+    use core::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+    #[derive(Default)]
+    struct Fnv1a(Option<u64>);
+
+    impl Hasher for Fnv1a {
+        fn write(&mut self, bytes: &[u8]) {
+            let mut hash = self.0.unwrap_or(0xcbf29ce484222325);
+            for byte in bytes {
+                hash = (hash ^ *byte as u64).wrapping_mul(0x100000001b3);
+            }
+            self.0 = Some(hash);
+        }
+
+        fn finish(&self) -> u64 {
+            self.0.unwrap_or(0xcbf29ce484222325)
+        }
+    }
+
+    let tag = EntityTag::from_data_with::<Fnv1a>(b"123456789");
+    assert!(!tag.is_weak());
+    assert_eq!(tag, EntityTag::const_from_data_fnv1a(b"123456789"));
+    assert_eq!(EntityTag::from_data_with::<Fnv1a>(b""), EntityTag::const_from_data_fnv1a(b""));
+
+    let state = BuildHasherDefault::<Fnv1a>::default();
+    assert_eq!(EntityTag::from_data_hasher(b"123456789", state.build_hasher()), tag);
+    assert_eq!(EntityTag::from_data_hasher(b"123456789", Fnv1a(Some(0))).content_components().map(|(len, _)| len), Some(9));
+    assert_ne!(EntityTag::from_data_hasher(b"123456789", Fnv1a(Some(0))), tag);
+}
+
+#[test]
+fn test_versioned() {
+    let base = EntityTag::from_data(b"content");
+    let tag = EntityTag::versioned("v2", &base).unwrap();
+    assert!(!tag.is_weak());
+    assert_eq!(tag.tag(), format!("v2:{}", base.tag()));
+    assert_eq!(tag.split_version(), Some(("v2", base.clone())));
+    assert!(tag.strong_ne(&EntityTag::versioned("v3", &base).unwrap()));
+
+    let tag = EntityTag::versioned("1.0", &EntityTag::weak_unchecked("abc")).unwrap();
+    assert_eq!(tag.as_header_str(), "W/\"1.0:abc\"");
+    assert_eq!(tag.split_version(), Some(("1.0", EntityTag::weak_unchecked("abc"))));
+
+    assert_eq!(EntityTag::versioned("", &base), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::versioned("v:1", &base), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::versioned("v 1", &base), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::versioned("v1", &EntityTag::ANY), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::versioned(&"v".repeat(EntityTag::MAX_TAG_LEN), &base), Err(etag::ParseError::Overflow));
+
+    assert_eq!(base.split_version(), None);
+    assert_eq!(EntityTag::strong_unchecked(":abc").split_version(), None);
+}
+
+#[test]
+fn test_truncated() {
+    assert_eq!(EntityTag::strong_unchecked("abcdef").truncated(3), EntityTag::weak_unchecked("abc"));
+    assert_eq!(EntityTag::weak_unchecked("abcdef").truncated(0), EntityTag::weak_unchecked(""));
+    assert_eq!(EntityTag::strong_unchecked("abc").truncated(10), EntityTag::weak_unchecked("abc"));
+    assert_eq!(EntityTag::ANY.truncated(1), EntityTag::ANY);
+}
+
+#[test]
+fn test_content_components() {
+    let tag = EntityTag::from_data(b"12");
+    let (len, hash) = tag.content_components().expect("To have components");
+    assert_eq!(len, 2);
+    assert_eq!(tag.tag(), format!("2-{}", hash));
+
+    assert_eq!(EntityTag::const_from_data_crc32(b"123456789").content_components(), Some((9, 0xcbf43926)));
+    assert_eq!(EntityTag::strong_unchecked("0-0").content_components(), Some((0, 0)));
+    assert_eq!(EntityTag::strong_unchecked("abc").content_components(), None);
+    assert_eq!(EntityTag::strong_unchecked("1-").content_components(), None);
+    assert_eq!(EntityTag::strong_unchecked("-1").content_components(), None);
+    assert_eq!(EntityTag::strong_unchecked("+1-2").content_components(), None);
+    assert_eq!(EntityTag::strong_unchecked("1-2-3").content_components(), None);
+    assert_eq!(EntityTag::weak_unchecked("1.5-2").content_components(), None);
+}
+
 #[test]
 fn test_etag_size_limit() {
-    const MAX: &'static str = "12345678901234567890123456789012345678901234567890123456789012";
-    const ABOVE_MAX: &'static str = "123456789012345678901234567890123456789012345678901234567890123";
-    assert_eq!(MAX.len(), 62);
-    assert_eq!(ABOVE_MAX.len(), 63);
+    let max = "1".repeat(EntityTag::MAX_TAG_LEN);
+    let above_max = "1".repeat(EntityTag::MAX_TAG_LEN + 1);
+
+    assert_eq!(format!("\"{}\"", max).parse::<EntityTag>().unwrap(), EntityTag::checked_strong(&max).unwrap());
+    assert_eq!(format!("\"{}\"", above_max).parse::<EntityTag>().unwrap_err(), etag::ParseError::Overflow);
+}
+
+#[test]
+fn test_const_constructors() {
+    const STRONG: EntityTag = EntityTag::strong_unchecked("strong");
+    const CHECKED: Result<EntityTag, etag::ParseError> = EntityTag::checked_weak("with space");
+    static TAGS: [EntityTag; 2] = [EntityTag::weak_unchecked("weak"), EntityTag::new_unchecked(false, "")];
+
+    assert_eq!(STRONG, EntityTag::checked_strong("strong").unwrap());
+    assert_eq!(CHECKED, Err(etag::ParseError::InvalidFormat));
+    assert_eq!(TAGS[0].as_header_str(), "W/\"weak\"");
+    assert_eq!(TAGS[1].as_header_str(), "\"\"");
+}
+
+#[test]
+fn test_capacity() {
+    #[cfg(not(any(feature = "buffer-128", feature = "buffer-256")))]
+    assert_eq!(EntityTag::MAX_TAG_LEN, 62);
+    #[cfg(all(feature = "buffer-128", not(feature = "buffer-256")))]
+    assert_eq!(EntityTag::MAX_TAG_LEN, 124);
+    #[cfg(feature = "buffer-256")]
+    assert_eq!(EntityTag::MAX_TAG_LEN, 251);
+
+    let empty = EntityTag::weak_unchecked("");
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.remaining_capacity(), EntityTag::MAX_TAG_LEN);
+
+    let tag = EntityTag::strong_unchecked("sha-");
+    assert!(!tag.is_empty());
+    assert_eq!(tag.len(), 4);
+    assert_eq!(tag.remaining_capacity(), EntityTag::MAX_TAG_LEN - 4);
+
+    let tag = EntityTag::from_data(b"data");
+    assert_eq!(tag.len(), tag.tag().len());
+}
+
+#[test]
+fn test_try_push_str() {
+    use core::fmt::Write;
+
+    let mut tag = EntityTag::weak_unchecked("v1-");
+    tag.try_push_str("abc").unwrap();
+    assert_eq!(tag, EntityTag::weak_unchecked("v1-abc"));
+    write!(tag, "-{}", 42).unwrap();
+    assert_eq!(tag.as_header_str(), "W/\"v1-abc-42\"");
+
+    assert_eq!(tag.try_push_str("ろ"), Err(etag::ParseError::NotAscii));
+    assert_eq!(tag.try_push_str("a b"), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(tag.try_push_str(&"a".repeat(tag.remaining_capacity() + 1)), Err(etag::ParseError::Overflow));
+    assert_eq!(tag, EntityTag::weak_unchecked("v1-abc-42"));
+
+    tag.try_push_str(&"a".repeat(tag.remaining_capacity())).unwrap();
+    assert_eq!(tag.len(), EntityTag::MAX_TAG_LEN);
+    assert_eq!(tag.remaining_capacity(), 0);
+    assert!(write!(tag, "{}", 1).is_err());
+}
 
-    assert_eq!(format!("\"{}\"", MAX).parse::<EntityTag>().unwrap(), EntityTag::checked_strong(MAX).unwrap());
-    assert_eq!(format!("\"{}\"", ABOVE_MAX).parse::<EntityTag>().unwrap_err(), etag::ParseError::Overflow);
+#[test]
+fn test_try_push_str_invalid_position() {
+    //Invalid character in every position of chunks validated at once, and of the remainder
+    for invalid in [b' ', b'"', 0x7F, 0x00] {
+        for idx in 0..40 {
+            let mut text = [b'a'; 40];
+            text[idx] = invalid;
+            let text = core::str::from_utf8(&text).unwrap();
+
+            let mut tag = EntityTag::strong_unchecked("");
+            assert_eq!(tag.try_push_str(text), Err(etag::ParseError::InvalidFormat));
+            assert_eq!(EntityTag::checked_strong(text), Err(etag::ParseError::InvalidFormat));
+        }
+    }
+
+    let mut tag = EntityTag::strong_unchecked("");
+    tag.try_push_str("!#$%&'()*+,-./0123456789:;<=>?@AZ[\\]^_`az{|}~").unwrap();
+}
+
+#[test]
+fn test_any() {
+    assert_eq!("*".parse::<EntityTag>(), Err(etag::ParseError::InvalidFormat));
+    let any = etag::parse_list("*").next().unwrap().unwrap();
+    assert_eq!(any, EntityTag::ANY);
+    assert!(any.is_any());
+    assert!(!any.is_weak());
+    assert_eq!(any.tag(), "");
+    assert_eq!(any.as_header_str(), "*");
+    assert_eq!(format!("{}", any), "*");
+    assert_eq!(format!("{:#}", any), "*");
+    assert_eq!(format!("{:?}", any), "EntityTag(*)");
+
+    let literal = "\"*\"".parse::<EntityTag>().unwrap();
+    assert!(!literal.is_any());
+    assert_ne!(literal, EntityTag::ANY);
+    assert!(!EntityTag::ANY.weak_eq(&literal));
+    assert!(!EntityTag::ANY.weak_eq(&EntityTag::strong_unchecked("")));
+    assert!(!EntityTag::ANY.strong_eq(&EntityTag::strong_unchecked("")));
+    assert!(EntityTag::ANY.strong_eq(&EntityTag::ANY));
+
+    let mut tag = EntityTag::ANY;
+    tag.set_weak(true);
+    assert!(tag.is_any());
+    assert_eq!(tag.try_push_str("a"), Err(etag::ParseError::InvalidFormat));
+    assert!(!tag.matches_if_match("\"\""));
+    assert!(!tag.matches_if_none_match("\"\""));
+
+    let tags = etag::parse_list("*").collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(tags, [EntityTag::ANY]);
+}
+
+#[test]
+fn test_into_parts() {
+    for tag in [EntityTag::weak_unchecked("weak"), EntityTag::strong_unchecked("strong"), EntityTag::strong_unchecked(""), EntityTag::from_data(b"data")].iter() {
+        let (weak, opaque) = tag.clone().into_parts();
+        assert_eq!(weak, tag.is_weak());
+        assert_eq!(opaque.as_str(), tag.tag());
+        assert_eq!(EntityTag::from_parts(weak, opaque), *tag);
+    }
+
+    let (weak, opaque) = EntityTag::ANY.into_parts();
+    assert!(!weak);
+    assert!(opaque.is_empty());
+
+    let max = "a".repeat(EntityTag::MAX_TAG_LEN);
+    let (weak, opaque) = EntityTag::weak_unchecked(&max).into_parts();
+    assert_eq!(opaque.capacity(), EntityTag::MAX_TAG_LEN);
+    assert_eq!(EntityTag::from_parts(weak, opaque).tag(), max);
+}
+
+#[test]
+fn test_set_weak() {
+    let mut etag = EntityTag::strong_unchecked("tag");
+    assert!(!etag.is_weak());
+
+    etag.set_weak(true);
+    assert!(etag.is_weak());
+    assert_eq!(etag, EntityTag::weak_unchecked("tag"));
+    assert_eq!(etag.as_header_str(), "W/\"tag\"");
+
+    etag.set_weak(false);
+    assert!(!etag.is_weak());
+    assert_eq!(etag, EntityTag::strong_unchecked("tag"));
+    assert_eq!(etag.as_header_str(), "\"tag\"");
 }
 
 #[test]
@@ -71,51 +399,149 @@ fn test_cmp() {
     const FIRST: &'static str = "FIRST";
     const SECOND: &'static str = "SECOND";
 
-    let etag1 = EntityTag::weak(FIRST);
-    let etag2 = EntityTag::weak(FIRST);
+    let etag1 = EntityTag::weak_unchecked(FIRST);
+    let etag2 = EntityTag::weak_unchecked(FIRST);
     assert!(!etag1.strong_eq(&etag2));
     assert!(etag1.weak_eq(&etag2));
     assert!(etag1.strong_ne(&etag2));
     assert!(!etag1.weak_ne(&etag2));
 
-    let etag1 = EntityTag::weak(FIRST);
-    let etag2 = EntityTag::weak(SECOND);
+    let etag1 = EntityTag::weak_unchecked(FIRST);
+    let etag2 = EntityTag::weak_unchecked(SECOND);
     assert!(!etag1.strong_eq(&etag2));
     assert!(!etag1.weak_eq(&etag2));
     assert!(etag1.strong_ne(&etag2));
     assert!(etag1.weak_ne(&etag2));
 
-    let etag1 = EntityTag::weak(FIRST);
-    let etag2 = EntityTag::strong(FIRST);
+    let etag1 = EntityTag::weak_unchecked(FIRST);
+    let etag2 = EntityTag::strong_unchecked(FIRST);
     assert!(!etag1.strong_eq(&etag2));
     assert!(etag1.weak_eq(&etag2));
     assert!(etag1.strong_ne(&etag2));
     assert!(!etag1.weak_ne(&etag2));
 
-    let etag1 = EntityTag::strong(FIRST);
-    let etag2 = EntityTag::strong(FIRST);
+    let etag1 = EntityTag::strong_unchecked(FIRST);
+    let etag2 = EntityTag::strong_unchecked(FIRST);
     assert!(etag1.strong_eq(&etag2));
     assert!(etag1.weak_eq(&etag2));
     assert!(!etag1.strong_ne(&etag2));
     assert!(!etag1.weak_ne(&etag2));
 }
 
+#[test]
+fn test_any_match() {
+    use etag::Comparison;
+
+    let candidates = [EntityTag::weak_unchecked("a"), EntityTag::ANY, EntityTag::strong_unchecked("b"), EntityTag::strong_unchecked("a")];
+
+    assert_eq!(EntityTag::strong_unchecked("a").any_strong_match(&candidates), Some(3));
+    assert_eq!(EntityTag::strong_unchecked("a").any_weak_match(&candidates), Some(0));
+    assert_eq!(EntityTag::weak_unchecked("a").any_strong_match(&candidates), None);
+    assert_eq!(EntityTag::weak_unchecked("b").any_weak_match(&candidates), Some(2));
+    assert_eq!(EntityTag::strong_unchecked("c").any_weak_match(&candidates), None);
+    assert_eq!(EntityTag::ANY.any_match_with(&candidates, Comparison::Strong), Some(1));
+    assert_eq!(EntityTag::strong_unchecked("*").any_weak_match(&candidates), None);
+    assert_eq!(EntityTag::strong_unchecked("a").any_match_with(&[], Comparison::Weak), None);
+}
+
+#[test]
+fn test_cmp_word_boundaries() {
+    for len in 0..=24 {
+        let tag = "a".repeat(len);
+        let strong = EntityTag::strong_unchecked(&tag);
+        let weak = EntityTag::weak_unchecked(&tag);
+        assert!(strong.strong_eq(&EntityTag::strong_unchecked(&tag)));
+        assert!(strong.weak_eq(&weak));
+        assert!(!strong.strong_eq(&weak));
+        assert!(!strong.weak_eq(&EntityTag::ANY));
+
+        for idx in 0..len {
+            let mut other = tag.clone().into_bytes();
+            other[idx] = b'b';
+            let other = EntityTag::strong_unchecked(core::str::from_utf8(&other).unwrap());
+            assert!(!strong.strong_eq(&other));
+            assert!(!weak.weak_eq(&other));
+        }
+
+        let longer = EntityTag::strong_unchecked(&format!("{}a", tag));
+        assert!(!strong.weak_eq(&longer));
+        assert!(!longer.weak_eq(&strong));
+    }
+}
+
 #[test]
 fn test_etag_fmt() {
-    assert_eq!(format!("{}", EntityTag::strong("foobar")), "\"foobar\"");
-    assert_eq!(format!("{}", EntityTag::strong("")), "\"\"");
-    assert_eq!(format!("{}", EntityTag::weak("weak-etag")), "W/\"weak-etag\"");
-    assert_eq!(format!("{}", EntityTag::weak("\u{0065}")), "W/\"\x65\"");
-    assert_eq!(format!("{}", EntityTag::weak("")), "W/\"\"");
+    assert_eq!(format!("{}", EntityTag::strong_unchecked("foobar")), "\"foobar\"");
+    assert_eq!(format!("{}", EntityTag::strong_unchecked("")), "\"\"");
+    assert_eq!(format!("{}", EntityTag::weak_unchecked("weak-etag")), "W/\"weak-etag\"");
+    assert_eq!(format!("{}", EntityTag::weak_unchecked("\u{0065}")), "W/\"\x65\"");
+    assert_eq!(format!("{}", EntityTag::weak_unchecked("")), "W/\"\"");
+
+    assert_eq!(format!("{:#}", EntityTag::strong_unchecked("foobar")), "foobar");
+    assert_eq!(format!("{:#}", EntityTag::strong_unchecked("")), "");
+    assert_eq!(format!("{:#}", EntityTag::weak_unchecked("weak-etag")), "W/weak-etag");
+    assert_eq!(format!("{:?}", EntityTag::strong_unchecked("foobar")), "EntityTag(strong, \"foobar\")");
+    assert_eq!(format!("{:?}", EntityTag::weak_unchecked("weak-etag")), "EntityTag(weak, \"weak-etag\")");
+    assert_eq!(EntityTag::strong_unchecked("foobar").as_header_str(), "\"foobar\"");
+    assert_eq!(EntityTag::weak_unchecked("weak-etag").as_header_str(), "W/\"weak-etag\"");
+    assert_eq!(EntityTag::from_data(b"12").as_header_str(), format!("\"{}\"", EntityTag::from_data(b"12").tag()));
+    assert_eq!(EntityTag::const_from_data(b"12").as_header_str(), format!("\"{}\"", EntityTag::from_data(b"12").tag()));
+}
+
+#[test]
+fn test_etag_fmt_padding() {
+    assert_eq!(format!("{:>8}", EntityTag::strong_unchecked("abc")), "   \"abc\"");
+    assert_eq!(format!("{:<8}|", EntityTag::weak_unchecked("abc")), "W/\"abc\" |");
+    assert_eq!(format!("{:-^7}", EntityTag::ANY), "---*---");
+    assert_eq!(format!("{:>#6}", EntityTag::strong_unchecked("abc")), "   abc");
+    assert_eq!(format!("{:2}", EntityTag::strong_unchecked("abc")), "\"abc\"");
+    assert_eq!(format!("{:.3}", EntityTag::weak_unchecked("abc")), "W/\"");
+}
+
+#[test]
+fn test_etag_fmt_single_write() {
+    use core::fmt::Write;
+
+    struct Counter(usize, String);
+
+    impl Write for Counter {
+        fn write_str(&mut self, text: &str) -> core::fmt::Result {
+            self.0 += 1;
+            self.1.push_str(text);
+            Ok(())
+        }
+    }
+
+    for (tag, expected) in [(EntityTag::weak_unchecked("tag"), "W/tag"), (EntityTag::strong_unchecked("tag"), "tag")].iter() {
+        let mut counter = Counter(0, String::new());
+        write!(counter, "{}", tag).unwrap();
+        assert_eq!(counter.0, 1);
+        assert_eq!(counter.1, tag.as_header_str());
+
+        let mut counter = Counter(0, String::new());
+        write!(counter, "{:#}", tag).unwrap();
+        assert_eq!(counter.0, 1);
+        assert_eq!(counter.1, *expected);
+    }
 }
 
 #[test]
 fn test_etag_parse_success() {
-    assert_eq!("\"foobar\"".parse::<EntityTag>().unwrap(), EntityTag::strong("foobar"));
-    assert_eq!("\"\"".parse::<EntityTag>().unwrap(), EntityTag::strong(""));
-    assert_eq!("W/\"weaktag\"".parse::<EntityTag>().unwrap(), EntityTag::weak("weaktag"));
-    assert_eq!("W/\"\x65\x62\"".parse::<EntityTag>().unwrap(), EntityTag::weak("\x65\x62"));
-    assert_eq!("W/\"\"".parse::<EntityTag>().unwrap(), EntityTag::weak(""));
+    assert_eq!("\"foobar\"".parse::<EntityTag>().unwrap(), EntityTag::strong_unchecked("foobar"));
+    assert_eq!("\"\"".parse::<EntityTag>().unwrap(), EntityTag::strong_unchecked(""));
+    assert_eq!("W/\"weaktag\"".parse::<EntityTag>().unwrap(), EntityTag::weak_unchecked("weaktag"));
+    assert_eq!("W/\"\x65\x62\"".parse::<EntityTag>().unwrap(), EntityTag::weak_unchecked("\x65\x62"));
+    assert_eq!("W/\"\"".parse::<EntityTag>().unwrap(), EntityTag::weak_unchecked(""));
+}
+
+#[test]
+fn test_etag_parse_trimmed() {
+    assert_eq!(EntityTag::parse_trimmed(" \"abc\"").unwrap(), EntityTag::strong_unchecked("abc"));
+    assert_eq!(EntityTag::parse_trimmed("\tW/\"abc\" \t").unwrap(), EntityTag::weak_unchecked("abc"));
+    assert_eq!(EntityTag::parse_trimmed(" * ").unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!(EntityTag::parse_trimmed("\" abc\"").unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!(EntityTag::parse_trimmed("  ").unwrap_err(), etag::ParseError::InvalidFormat);
+    assert!(" \"abc\"".parse::<EntityTag>().is_err());
 }
 
 #[test]
@@ -127,4 +553,620 @@ fn test_etag_parse_failures() {
     assert!("\"unmatched-dquotes1".parse::<EntityTag>().is_err());
     assert!("unmatched-dquotes2\"".parse::<EntityTag>().is_err());
     assert!("matched-\"dquotes\"".parse::<EntityTag>().is_err());
+    assert_eq!("\"inner\"dquote\"".parse::<EntityTag>().unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!("\"with space\"".parse::<EntityTag>().unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!("W/\"control\tcharacter\"".parse::<EntityTag>().unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!("W/\"long-tag-with-delete-\x7F-character\"".parse::<EntityTag>().unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!("\"long-tag-with-non-ascii-character-ろ\"".parse::<EntityTag>().unwrap_err(), etag::ParseError::NotAscii);
+    assert!("\"!#$%&'()*+,-./0123456789:;<=>?@[\\]^_`{|}~\"".parse::<EntityTag>().is_ok());
+}
+
+#[test]
+fn test_etag_parse_grammar() {
+    use etag::ParseError::{InvalidFormat, NotAscii, Overflow};
+
+    let max = "a".repeat(EntityTag::MAX_TAG_LEN);
+    let above_max = format!("W/\"{}a\"", max);
+    let max = format!("W/\"{}\"", max);
+    let cases: &[(&str, Result<EntityTag, etag::ParseError>)] = &[
+        ("*", Err(InvalidFormat)),
+        ("\"*\"", Ok(EntityTag::strong_unchecked("*"))),
+        ("W/\"*\"", Ok(EntityTag::weak_unchecked("*"))),
+        ("\"\"", Ok(EntityTag::strong_unchecked(""))),
+        ("W/\"\"", Ok(EntityTag::weak_unchecked(""))),
+        ("\"W/\"", Ok(EntityTag::strong_unchecked("W/"))),
+        ("\"a\"", Ok(EntityTag::strong_unchecked("a"))),
+        ("W/\"a\"", Ok(EntityTag::weak_unchecked("a"))),
+        (max.as_str(), Ok(EntityTag::weak_unchecked(&max[3..max.len() - 1]))),
+        ("", Err(InvalidFormat)),
+        ("\"", Err(InvalidFormat)),
+        ("W", Err(InvalidFormat)),
+        ("W/", Err(InvalidFormat)),
+        ("W/\"", Err(InvalidFormat)),
+        ("W\"a\"", Err(InvalidFormat)),
+        ("/\"a\"", Err(InvalidFormat)),
+        ("w/\"a\"", Err(InvalidFormat)),
+        ("W//\"a\"", Err(InvalidFormat)),
+        ("**", Err(InvalidFormat)),
+        ("*\"a\"", Err(InvalidFormat)),
+        ("\"a\"*", Err(InvalidFormat)),
+        ("\"a\"\"", Err(InvalidFormat)),
+        ("\"a\"\"b\"", Err(InvalidFormat)),
+        ("\"a\", \"b\"", Err(InvalidFormat)),
+        ("W/\"a\"\"", Err(InvalidFormat)),
+        ("\"a\" ", Err(InvalidFormat)),
+        (" \"a\"", Err(InvalidFormat)),
+        ("\"a\0\"", Err(InvalidFormat)),
+        ("\"\r\n\"", Err(InvalidFormat)),
+        ("\"ろ\"", Err(NotAscii)),
+        ("\"ろ \"", Err(NotAscii)),
+        ("ろ\"a\"", Err(InvalidFormat)),
+        ("\"a\"ろ", Err(InvalidFormat)),
+        (above_max.as_str(), Err(Overflow)),
+    ];
+
+    for (text, expected) in cases {
+        assert_eq!(text.parse::<EntityTag>(), *expected, "{:?}", text);
+    }
+
+    for byte in 0..=0x7Fu8 {
+        let text = format!("\"a{}b\"", byte as char);
+        let expected = match byte {
+            0x21 | 0x23..=0x7E => Ok(EntityTag::strong_unchecked(&text[1..4])),
+            _ => Err(InvalidFormat),
+        };
+        assert_eq!(text.parse::<EntityTag>(), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_matches_if_none_match() {
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    assert!(strong.matches_if_none_match("*"));
+    assert!(strong.matches_if_none_match(" * "));
+    assert!(strong.matches_if_none_match("\"xyzzy\""));
+    assert!(weak.matches_if_none_match("\"xyzzy\""));
+    assert!(strong.matches_if_none_match("W/\"xyzzy\""));
+    assert!(strong.matches_if_none_match("\"r2d2xxxx\", \"c3piozzzz\", \"xyzzy\""));
+    assert!(strong.matches_if_none_match("\"r2d2xxxx\",W/\"xyzzy\""));
+    assert!(EntityTag::strong_unchecked("a,b").matches_if_none_match("\"c\", \"a,b\""));
+
+    assert!(!strong.matches_if_none_match(""));
+    assert!(!strong.matches_if_none_match("\"r2d2xxxx\", \"c3piozzzz\""));
+    assert!(!strong.matches_if_none_match("xyzzy"));
+    assert!(!strong.matches_if_none_match("w/\"xyzzy\""));
+    assert!(!EntityTag::strong_unchecked("b").matches_if_none_match("\"a,b\""));
+}
+
+#[test]
+fn test_matches_if_match() {
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    assert!(strong.matches_if_match("*"));
+    assert!(weak.matches_if_match("*"));
+    assert!(strong.matches_if_match("\"xyzzy\""));
+    assert!(strong.matches_if_match("\"r2d2xxxx\", \"c3piozzzz\", \"xyzzy\""));
+
+    assert!(!weak.matches_if_match("\"xyzzy\""));
+    assert!(!weak.matches_if_match("W/\"xyzzy\""));
+    assert!(!strong.matches_if_match("W/\"xyzzy\""));
+    assert!(!strong.matches_if_match(""));
+    assert!(!strong.matches_if_match("\"r2d2xxxx\", \"c3piozzzz\""));
+}
+
+#[test]
+fn test_etag_write_header() {
+    let mut buf = [0u8; 8];
+
+    assert_eq!(EntityTag::strong_unchecked("abc").write_header(&mut buf), Ok(5));
+    assert_eq!(&buf[..5], b"\"abc\"");
+    assert_eq!(EntityTag::weak_unchecked("abcd").write_header(&mut buf), Ok(8));
+    assert_eq!(&buf, b"W/\"abcd\"");
+
+    let mut buf = [0u8; 4];
+    assert_eq!(EntityTag::strong_unchecked("abc").write_header(&mut buf), Err(etag::ParseError::Overflow));
+    assert_eq!(buf, [0u8; 4]);
+}
+
+#[test]
+fn test_etag_write_to() {
+    let mut out = String::new();
+    EntityTag::strong_unchecked("abc").write_to(&mut out).unwrap();
+    EntityTag::weak_unchecked("d").write_to(&mut out).unwrap();
+    EntityTag::ANY.write_to(&mut out).unwrap();
+    assert_eq!(out, "\"abc\"W/\"d\"*");
+
+    let mut buf = etag::StrBuf::<4>::new();
+    assert!(EntityTag::strong_unchecked("abcd").write_to(&mut buf).is_err());
+    assert!(buf.is_empty());
+
+    let out: &mut dyn core::fmt::Write = &mut String::new();
+    assert!(EntityTag::strong_unchecked("").write_to(out).is_ok());
+}
+
+#[test]
+fn test_etag_display_buffer() {
+    assert_eq!(EntityTag::strong_unchecked("foobar").display_buffer().as_str(), "\"foobar\"");
+    assert_eq!(EntityTag::weak_unchecked("weak-etag").display_buffer().as_str(), "W/\"weak-etag\"");
+
+    let tag = EntityTag::checked_weak(&"1".repeat(EntityTag::MAX_TAG_LEN)).unwrap();
+    assert_eq!(tag.display_buffer().as_str(), tag.to_string());
+}
+
+#[test]
+fn test_not_modified() {
+    let tag = EntityTag::strong_unchecked("xyzzy");
+
+    assert!(etag::not_modified(&tag, Some("*")));
+    assert!(etag::not_modified(&tag, Some("W/\"xyzzy\"")));
+    assert!(etag::not_modified(&EntityTag::weak_unchecked("xyzzy"), Some("\"a\", \"xyzzy\"")));
+
+    assert!(!etag::not_modified(&tag, None));
+    assert!(!etag::not_modified(&tag, Some("")));
+    assert!(!etag::not_modified(&tag, Some("  ")));
+    assert!(!etag::not_modified(&tag, Some("\"other\"")));
+}
+
+#[test]
+fn test_validate_range_request() {
+    use etag::{validate_range_request, RangeDecision};
+
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    assert_eq!(validate_range_request(&strong, None), RangeDecision::Partial);
+    assert_eq!(validate_range_request(&strong, Some(" \"xyzzy\" ")), RangeDecision::Partial);
+
+    assert_eq!(validate_range_request(&strong, Some("W/\"xyzzy\"")), RangeDecision::Full);
+    assert_eq!(validate_range_request(&strong, Some("\"other\"")), RangeDecision::Full);
+    assert_eq!(validate_range_request(&strong, Some("*")), RangeDecision::Full);
+    assert_eq!(validate_range_request(&strong, Some("Sun, 06 Nov 1994 08:49:37 GMT")), RangeDecision::Full);
+
+    assert_eq!(validate_range_request(&weak, None), RangeDecision::Full);
+    assert_eq!(validate_range_request(&weak, Some("W/\"xyzzy\"")), RangeDecision::Full);
+    assert_eq!(validate_range_request(&EntityTag::ANY, None), RangeDecision::Full);
+}
+
+#[test]
+fn test_comparison_keys() {
+    use std::collections::HashMap;
+    use etag::{StrongKey, WeakKey};
+
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    let mut map = HashMap::new();
+    map.insert(WeakKey(strong.clone()), 1);
+    map.insert(WeakKey(weak.clone()), 2);
+    map.insert(WeakKey(EntityTag::ANY), 3);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&WeakKey(strong.clone())), Some(&2));
+    assert_eq!(map.get(&WeakKey(EntityTag::strong_unchecked(""))), None);
+
+    let mut map = HashMap::new();
+    map.insert(StrongKey(strong.clone()), 1);
+    map.insert(StrongKey(weak.clone()), 2);
+    map.insert(StrongKey::from(EntityTag::strong_unchecked("xyzzy")), 3);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&StrongKey(strong)), Some(&3));
+    assert_eq!(map.get(&StrongKey(weak)), Some(&2));
+    assert_eq!(map.get(&StrongKey(EntityTag::weak_unchecked("other"))), None);
+    assert_ne!(StrongKey(EntityTag::ANY), StrongKey(EntityTag::strong_unchecked("")));
+    assert_eq!(StrongKey(EntityTag::ANY), StrongKey(EntityTag::ANY));
+}
+
+#[test]
+fn test_hash_hex() {
+    let data = b"content";
+    let (_, hash) = EntityTag::from_data(data).content_components().unwrap();
+    assert_eq!(EntityTag::from_data(data).hash_hex().unwrap().as_str(), format!("{:x}", hash));
+    assert_eq!(EntityTag::const_from_data(data).hash_hex_upper().unwrap().as_str(), format!("{:X}", hash));
+
+    let tag = EntityTag::const_from_data_fnv1a(b"a");
+    assert_eq!(tag.hash_hex().unwrap(), "af63dc4c8601ec8c");
+    assert_eq!(tag.hash_hex_upper().unwrap(), "AF63DC4C8601EC8C");
+    assert_eq!(EntityTag::weak_unchecked("0-0").hash_hex().unwrap(), "0");
+
+    assert_eq!(EntityTag::strong_unchecked("v1").hash_hex(), None);
+    assert_eq!(EntityTag::ANY.hash_hex_upper(), None);
+}
+
+#[test]
+fn test_percent_encoded() {
+    let tag = EntityTag::percent_encoded(true, "100% ключ").unwrap();
+    assert!(tag.is_weak());
+    assert_eq!(tag.tag(), "100%25%20%D0%BA%D0%BB%D1%8E%D1%87");
+    assert_eq!(tag.percent_decoded().unwrap(), "100% ключ");
+
+    let tag = EntityTag::percent_encoded(false, "plain-tag").unwrap();
+    assert_eq!(tag, EntityTag::strong_unchecked("plain-tag"));
+    assert_eq!(tag.percent_decoded().unwrap(), "plain-tag");
+    assert_eq!(EntityTag::percent_encoded(false, "").unwrap(), EntityTag::strong_unchecked(""));
+
+    let text = "я".repeat(EntityTag::MAX_TAG_LEN / 6 + 1);
+    assert_eq!(EntityTag::percent_encoded(false, &text), Err(etag::ParseError::Overflow));
+
+    assert_eq!(EntityTag::strong_unchecked("%2f%2F").percent_decoded().unwrap(), "//");
+    assert_eq!(EntityTag::strong_unchecked("%2").percent_decoded(), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::strong_unchecked("%zz").percent_decoded(), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::strong_unchecked("%FF").percent_decoded(), Err(etag::ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_binary_tag() {
+    let mut out = [0u8; EntityTag::MAX_BINARY_LEN];
+
+    let digest = [0xd4u8, 0x1d, 0x8c, 0xd9, 0x8f, 0x00, 0xb2, 0x04, 0xe9, 0x80, 0x09, 0x98, 0xec, 0xf8, 0x42, 0x7e];
+    let tag = EntityTag::from_binary_tag(&digest).unwrap();
+    assert!(!tag.is_weak());
+    assert_eq!(tag.tag(), "1B2M2Y8AsgTpgAmY7PhCfg");
+    assert_eq!(tag.binary_tag(&mut out), Ok(digest.len()));
+    assert_eq!(&out[..digest.len()], digest);
+
+    for len in 0..=EntityTag::MAX_BINARY_LEN {
+        let bytes: Vec<u8> = (0..len as u8).map(|byte| byte.wrapping_mul(97)).collect();
+        let tag = EntityTag::from_binary_tag(&bytes).unwrap();
+        assert_eq!(tag.binary_tag(&mut out), Ok(len));
+        assert_eq!(&out[..len], &bytes[..]);
+    }
+    assert_eq!(EntityTag::from_binary_tag(&[0; EntityTag::MAX_BINARY_LEN + 1]), Err(etag::ParseError::Overflow));
+
+    assert_eq!(EntityTag::strong_unchecked("AQI").binary_tag(&mut out[..1]), Err(etag::ParseError::Overflow));
+    assert_eq!(EntityTag::strong_unchecked("AQJ").binary_tag(&mut out), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::strong_unchecked("AQIDB").binary_tag(&mut out), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::strong_unchecked("AQ+/").binary_tag(&mut out), Err(etag::ParseError::InvalidFormat));
+    assert_eq!(EntityTag::ANY.binary_tag(&mut out), Err(etag::ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_promote_if_matches() {
+    let strong = EntityTag::from_data(b"content");
+    let mut weak = strong.clone();
+    weak.set_weak(true);
+
+    assert_eq!(weak.promote_if_matches(b"content"), Some(strong.clone()));
+    assert_eq!(strong.promote_if_matches(b"content"), Some(strong.clone()));
+    assert_eq!(weak.promote_if_matches(b"content!"), None);
+    assert_eq!(weak.promote_if_matches(b"Content"), None);
+
+    assert_eq!(EntityTag::const_from_data_crc32(b"content").promote_if_matches(b"content"), None);
+    assert_eq!(EntityTag::weak_unchecked("1.5-7").promote_if_matches(b"content"), None);
+    assert_eq!(EntityTag::ANY.promote_if_matches(b""), None);
+}
+
+#[test]
+fn test_as_number() {
+    assert_eq!(EntityTag::strong_unchecked("42").as_u64(), Some(42));
+    assert_eq!(EntityTag::weak_unchecked("007").as_u64(), Some(7));
+    assert_eq!(EntityTag::strong_unchecked("18446744073709551616").as_u64(), None);
+    assert_eq!(EntityTag::strong_unchecked("18446744073709551616").as_u128(), Some(u64::MAX as u128 + 1));
+    assert_eq!(EntityTag::strong_unchecked(&u128::MAX.to_string()).as_u128(), Some(u128::MAX));
+
+    assert_eq!(EntityTag::strong_unchecked("+1").as_u64(), None);
+    assert_eq!(EntityTag::strong_unchecked("1.0").as_u128(), None);
+    assert_eq!(EntityTag::strong_unchecked("").as_u64(), None);
+    assert_eq!(EntityTag::ANY.as_u128(), None);
+}
+
+#[test]
+fn test_bump() {
+    use etag::BumpError;
+
+    assert_eq!(EntityTag::strong_unchecked("0").bump(), Ok(EntityTag::strong_unchecked("1")));
+    assert_eq!(EntityTag::weak_unchecked("19").bump(), Ok(EntityTag::weak_unchecked("20")));
+    assert_eq!(EntityTag::strong_unchecked("9").bump(), Ok(EntityTag::strong_unchecked("10")));
+    assert_eq!(EntityTag::strong_unchecked("0009").bump(), Ok(EntityTag::strong_unchecked("0010")));
+    assert_eq!(EntityTag::strong_unchecked("18446744073709551615").bump(), Ok(EntityTag::strong_unchecked("18446744073709551616")));
+
+    let max = "9".repeat(EntityTag::MAX_TAG_LEN - 1);
+    let bumped = EntityTag::strong_unchecked(&max).bump().unwrap();
+    assert_eq!(bumped.tag(), format!("1{}", "0".repeat(EntityTag::MAX_TAG_LEN - 1)));
+    assert_eq!(EntityTag::strong_unchecked(&format!("{}8", max)).bump().unwrap().tag(), format!("{}9", max));
+    assert_eq!(EntityTag::strong_unchecked(&format!("{}9", max)).bump(), Err(BumpError::Overflow));
+    assert_eq!(EntityTag::weak_unchecked(&format!("{}9", max)).bump(), Err(BumpError::Overflow));
+    assert_eq!(BumpError::Overflow.to_string(), "EntityTag counter overflows buffer");
+
+    assert_eq!(EntityTag::strong_unchecked("").bump(), Err(BumpError::NotNumeric));
+    assert_eq!(EntityTag::strong_unchecked("v1").bump(), Err(BumpError::NotNumeric));
+    assert_eq!(EntityTag::strong_unchecked("-1").bump(), Err(BumpError::NotNumeric));
+    assert_eq!(EntityTag::ANY.bump(), Err(BumpError::NotNumeric));
+}
+
+#[test]
+fn test_unchecked_constructors() {
+    assert_eq!(EntityTag::strong_unchecked("v1"), EntityTag::strong_unchecked("v1"));
+    assert_eq!(EntityTag::weak_unchecked("v1"), EntityTag::weak_unchecked("v1"));
+    assert_eq!(EntityTag::new_unchecked(true, "v1"), EntityTag::new_unchecked(true, "v1"));
+}
+
+#[test]
+fn test_try_from_display() {
+    let tag = EntityTag::try_from_display(true, &format_args!("{}-{:x}", "rev", 255u32)).unwrap();
+    assert_eq!(tag, EntityTag::weak_unchecked("rev-ff"));
+
+    let long = "a".repeat(EntityTag::MAX_TAG_LEN);
+    assert_eq!(EntityTag::try_from_display(false, long.as_str()).unwrap().tag(), long);
+    let long = "a".repeat(EntityTag::MAX_TAG_LEN + 1);
+    assert_eq!(EntityTag::try_from_display(false, long.as_str()), Err(etag::ParseError::Overflow));
+    assert_eq!(EntityTag::try_from_display(false, "ろ"), Err(etag::ParseError::NotAscii));
+    assert_eq!(EntityTag::try_from_display(false, "\""), Err(etag::ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_str_buf_overflow() {
+    use core::fmt::Write;
+
+    let mut buf = etag::StrBuf::<4>::new();
+    assert!(buf.try_push_str("ab"));
+    assert!(!buf.try_push_str("cde"));
+    assert_eq!(buf.as_str(), "ab");
+    assert!(write!(buf, "{}", 123).is_err());
+    assert_eq!(buf.as_str(), "ab");
+    assert!(write!(buf, "{}", 12).is_ok());
+    assert_eq!(buf.as_str(), "ab12");
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn test_random_strong() {
+    let tag = EntityTag::random_strong(12).unwrap();
+    assert!(!tag.is_weak());
+    assert_eq!(tag.len(), 16);
+    let mut bytes = [0u8; EntityTag::MAX_BINARY_LEN];
+    assert_eq!(tag.binary_tag(&mut bytes), Ok(12));
+    assert_ne!(tag, EntityTag::random_strong(12).unwrap());
+
+    assert_eq!(EntityTag::random_strong(0).unwrap(), EntityTag::strong_unchecked(""));
+    let tag = EntityTag::random_strong(usize::MAX).unwrap();
+    assert_eq!(tag.binary_tag(&mut bytes), Ok(EntityTag::MAX_BINARY_LEN));
+}
+
+#[cfg(feature = "ulid")]
+#[test]
+fn test_ulid() {
+    let first = ulid::Ulid::from_parts(1_700_000_000_000, u128::MAX);
+    let second = ulid::Ulid::from_parts(1_700_000_000_001, 0);
+    let first_tag = EntityTag::from_ulid(&first);
+    let second_tag = EntityTag::from_ulid(&second);
+    assert!(!first_tag.is_weak());
+    assert_eq!(first_tag.len(), ulid::ULID_LEN);
+    assert!(first_tag.tag() < second_tag.tag());
+    assert_eq!(first_tag.to_ulid(), Some(first));
+    assert_eq!(EntityTag::weak_unchecked(second_tag.tag()).to_ulid(), Some(second));
+
+    assert_eq!(EntityTag::strong_unchecked("v1").to_ulid(), None);
+    assert_eq!(EntityTag::ANY.to_ulid(), None);
+}
+
+#[cfg(all(feature = "ulid", feature = "std"))]
+#[test]
+fn test_new_ulid() {
+    let tag = EntityTag::new_ulid();
+    assert!(tag.to_ulid().is_some());
+    assert_ne!(tag, EntityTag::new_ulid());
+}
+
+#[cfg(feature = "signed")]
+#[test]
+fn test_signed() {
+    const KEY: &[u8] = b"key";
+
+    let tag = EntityTag::signed("row.42", KEY).unwrap();
+    assert!(!tag.is_weak());
+    assert_eq!(tag.len(), "row.42".len() + 17);
+    assert!(tag.tag().starts_with("row.42."));
+    assert_eq!(tag.verify_signature(KEY), Some("row.42"));
+    assert_eq!(EntityTag::weak_unchecked(tag.tag()).verify_signature(KEY), Some("row.42"));
+    assert_eq!(tag, EntityTag::signed("row.42", KEY).unwrap());
+
+    assert_eq!(tag.verify_signature(b"other"), None);
+    assert_eq!(EntityTag::strong_unchecked(&tag.tag().replace("42", "43")).verify_signature(KEY), None);
+    assert_eq!(EntityTag::strong_unchecked(&tag.tag()[..tag.len() - 1]).verify_signature(KEY), None);
+    assert_eq!(EntityTag::strong_unchecked(&format!("{}A", tag.tag())).verify_signature(KEY), None);
+    assert_eq!(EntityTag::strong_unchecked("row.42").verify_signature(KEY), None);
+    assert_eq!(EntityTag::ANY.verify_signature(KEY), None);
+
+    let empty = EntityTag::signed("", KEY).unwrap();
+    assert_eq!(empty.verify_signature(KEY), Some(""));
+
+    let max = "a".repeat(EntityTag::MAX_TAG_LEN - 17);
+    assert_eq!(EntityTag::signed(&max, KEY).unwrap().verify_signature(KEY), Some(max.as_str()));
+    assert_eq!(EntityTag::signed(&format!("{}a", max), KEY), Err(etag::ParseError::Overflow));
+    assert_eq!(EntityTag::signed("a b", KEY), Err(etag::ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_if_none_match() {
+    use etag::IfNoneMatch;
+
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    let any = IfNoneMatch::new(" * ");
+    assert!(any.is_any());
+    assert!(any.contains_weak(&weak));
+    assert!(any.contains_strong(&weak));
+
+    let list = IfNoneMatch::new("\"r2d2xxxx\", W/\"xyzzy\", \"c3piozzzz\"");
+    assert!(!list.is_any());
+    assert!(list.contains_weak(&strong));
+    assert!(list.contains_weak(&weak));
+    assert!(!list.contains_strong(&strong));
+    assert!(!list.contains_strong(&weak));
+    assert!(list.contains_strong(&EntityTag::strong_unchecked("c3piozzzz")));
+    assert!(!list.contains_weak(&EntityTag::strong_unchecked("xyz")));
+
+    let list = IfNoneMatch::new("\"xyzzy\"");
+    assert!(list.contains_strong(&strong));
+    assert!(!list.contains_strong(&weak));
+    assert!(!IfNoneMatch::new("").contains_weak(&strong));
+}
+
+#[test]
+fn test_parse_list() {
+    use etag::ParseError;
+
+    let header = "\"first\", W/\"second\", third, \"ろり\", \"a,b\"";
+    let mut tags = etag::parse_list(header);
+    assert_eq!(tags.next(), Some(Ok(EntityTag::strong_unchecked("first"))));
+    assert_eq!(tags.next(), Some(Ok(EntityTag::weak_unchecked("second"))));
+    assert_eq!(tags.next(), Some(Err(ParseError::InvalidFormat)));
+    assert_eq!(tags.next(), Some(Err(ParseError::NotAscii)));
+    assert_eq!(tags.next(), Some(Ok(EntityTag::strong_unchecked("a,b"))));
+    assert_eq!(tags.next(), None);
+
+    let invalid = etag::parse_list(header).enumerate().filter(|(_, tag)| tag.is_err()).map(|(idx, _)| idx).collect::<Vec<_>>();
+    assert_eq!(invalid, [2, 3]);
+
+    let valid = etag::parse_list(header).skip_invalid().collect::<Vec<_>>();
+    assert_eq!(valid, [EntityTag::strong_unchecked("first"), EntityTag::weak_unchecked("second"), EntityTag::strong_unchecked("a,b")]);
+
+    assert_eq!(etag::parse_list("").next(), None);
+}
+
+#[test]
+fn test_parse_many() {
+    let (tags, len) = etag::parse_many::<3>(" \"a\" ,, W/\"b\",").unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(tags, [Some(EntityTag::strong_unchecked("a")), Some(EntityTag::weak_unchecked("b")), None]);
+
+    let (tags, len) = etag::parse_many::<2>("\"a\", *").unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(tags, [Some(EntityTag::strong_unchecked("a")), Some(EntityTag::ANY)]);
+
+    assert_eq!(etag::parse_many::<2>("\"a\", \"b\", \"c\"").unwrap_err(), etag::ParseError::Overflow);
+    assert_eq!(etag::parse_many::<2>("\"a\", b").unwrap_err(), etag::ParseError::InvalidFormat);
+    assert_eq!(etag::parse_many::<0>("").unwrap().1, 0);
+    assert_eq!(etag::parse_many::<0>("\"a\"").unwrap_err(), etag::ParseError::Overflow);
+}
+
+#[test]
+fn test_parse_list_ows() {
+    let expected = [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b")];
+
+    for header in ["\"a\",W/\"b\"", "\"a\" , W/\"b\"", "\t\"a\"\t,\tW/\"b\"\t", ", ,\"a\",, W/\"b\" ,", ",\"a\",,,W/\"b\","].iter() {
+        let tags = etag::parse_list(header).collect::<Result<Vec<_>, _>>().expect("To parse");
+        assert_eq!(tags, expected, "header={}", header);
+    }
+
+    assert_eq!(etag::parse_list(" , ,\t, ").next(), None);
+    assert!(EntityTag::strong_unchecked("b").matches_if_match(", , \"a\" ,, \"b\""));
+    //Whitespace within DQUOTEs is not trimmed, making the tag invalid
+    assert_eq!(etag::parse_list("\" a \"").next(), Some(Err(etag::ParseError::InvalidFormat)));
+}
+
+#[test]
+fn test_entity_tag_list() {
+    let mut list = etag::EntityTagList::<2>::new();
+    assert!(list.is_empty());
+    assert_eq!(list.capacity(), 2);
+
+    assert_eq!(list.push(EntityTag::strong_unchecked("a")), Ok(()));
+    assert_eq!(list.push(EntityTag::weak_unchecked("b")), Ok(()));
+    assert!(list.is_full());
+    assert_eq!(list.push(EntityTag::strong_unchecked("c")), Err(EntityTag::strong_unchecked("c")));
+    assert_eq!(list.as_slice(), [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b")]);
+    assert!(list.contains(&EntityTag::weak_unchecked("a")));
+    assert!(list.contains(&EntityTag::strong_unchecked("b")));
+    assert!(!list.contains(&EntityTag::strong_unchecked("c")));
+
+    let parsed = etag::EntityTagList::<2>::parse(" \"a\" ,W/\"b\",").unwrap();
+    assert_eq!(parsed, list);
+    assert_eq!(parsed.iter().count(), 2);
+    assert_eq!(etag::EntityTagList::<2>::parse("\"a\", \"b\", \"c\"").unwrap_err(), etag::ParseError::Overflow);
+    assert_eq!(etag::EntityTagList::<2>::parse("\"a\", b").unwrap_err(), etag::ParseError::InvalidFormat);
+
+    let any = etag::EntityTagList::<1>::parse("*").unwrap();
+    assert!(any.contains(&EntityTag::strong_unchecked("anything")));
+
+    list.clear();
+    assert!(list.is_empty());
+    assert!(etag::EntityTagList::<0>::parse("").unwrap().is_empty());
+}
+
+#[test]
+fn test_entity_tag_list_set_operations() {
+    use etag::{Comparison, EntityTagList};
+
+    let mut list = EntityTagList::<5>::parse("\"a\", W/\"b\", \"a\", \"c\", W/\"b\"").unwrap();
+    list.dedup();
+    assert_eq!(list.as_slice(), [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b"), EntityTag::strong_unchecked("c")]);
+
+    assert!(list.contains_with(&EntityTag::strong_unchecked("a"), Comparison::Strong));
+    assert!(!list.contains_with(&EntityTag::strong_unchecked("b"), Comparison::Strong));
+    assert!(list.contains_with(&EntityTag::strong_unchecked("b"), Comparison::Weak));
+
+    let other = EntityTagList::<2>::parse("\"b\", \"c\"").unwrap();
+    assert_eq!(list.intersection(&other, Comparison::Weak).as_slice(), [EntityTag::weak_unchecked("b"), EntityTag::strong_unchecked("c")]);
+    assert_eq!(list.intersection(&other, Comparison::Strong).as_slice(), [EntityTag::strong_unchecked("c")]);
+    assert_eq!(list.difference(&other, Comparison::Weak).as_slice(), [EntityTag::strong_unchecked("a")]);
+    assert_eq!(list.difference(&other, Comparison::Strong).as_slice(), [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b")]);
+
+    let any = EntityTagList::<1>::parse("*").unwrap();
+    assert_eq!(list.intersection(&any, Comparison::Strong), list);
+    assert!(list.difference(&any, Comparison::Strong).is_empty());
+
+    let header = etag::IfNoneMatch::new("W/\"a\"");
+    assert!(header.contains_with(&EntityTag::strong_unchecked("a"), Comparison::Weak));
+    assert!(!header.contains_with(&EntityTag::strong_unchecked("a"), Comparison::Strong));
+}
+
+#[test]
+fn test_display_list() {
+    let tags = [EntityTag::strong_unchecked("a"), EntityTag::weak_unchecked("b")];
+    assert_eq!(etag::display_list(&tags).to_string(), "\"a\", W/\"b\"");
+    assert_eq!(etag::display_list(&tags[..1]).to_string(), "\"a\"");
+    assert_eq!(etag::display_list(&[]).to_string(), "");
+    assert_eq!(etag::display_list(&[EntityTag::weak_unchecked("a"), EntityTag::ANY]).to_string(), "*");
+
+    let list = etag::EntityTagList::<2>::parse("W/\"b\",\"a\"").unwrap();
+    assert_eq!(list.to_string(), "W/\"b\", \"a\"");
+
+    let mut buf = [0u8; 10];
+    assert_eq!(etag::write_list(&tags, &mut buf), Ok(10));
+    assert_eq!(&buf, b"\"a\", W/\"b\"");
+    assert_eq!(etag::write_list(&tags, &mut buf[..9]), Err(etag::ParseError::Overflow));
+    assert_eq!(etag::write_list(&list, &mut buf), Ok(10));
+}
+
+#[test]
+fn test_cache_key() {
+    let tag = EntityTag::strong_unchecked("v1");
+    let key = etag::cache_key("/index.html", None, &tag);
+    //Key must not change between releases
+    assert_eq!(key, [99, 43, 133, 194, 60, 244, 112, 55, 231, 162, 76, 22, 55, 27, 220, 136]);
+
+    assert_eq!(key, etag::cache_key("/index.html", None, &EntityTag::strong_unchecked("v1")));
+    assert_ne!(key, etag::cache_key("/index.html", None, &EntityTag::weak_unchecked("v1")));
+    assert_ne!(key, etag::cache_key("/index.html", None, &EntityTag::strong_unchecked("v2")));
+    assert_ne!(key, etag::cache_key("/index.html", Some(""), &tag));
+    assert_ne!(key, etag::cache_key("/index.htm", None, &tag));
+    assert_ne!(etag::cache_key("/a", Some("b"), &tag), etag::cache_key("/ab", Some(""), &tag));
+    assert_ne!(etag::cache_key("/", None, &EntityTag::ANY), etag::cache_key("/", None, &EntityTag::strong_unchecked("")));
+}
+
+#[test]
+fn test_etag_from_fields() {
+    let tag = etag::etag_from_fields!(1, "a");
+    assert!(!tag.is_weak());
+    assert_eq!(tag.tag(), "b9a7934866dcf9d74c48e72d427d701d");
+    assert_eq!(tag, etag::etag_from_fields!("1", 'a',));
+
+    assert_ne!(tag, etag::etag_from_fields!("1a"));
+    assert_ne!(tag, etag::etag_from_fields!("1a", ""));
+    assert_ne!(tag, etag::etag_from_fields!("", "1a"));
+    assert_ne!(tag, etag::etag_from_fields!("a", 1));
+    assert_ne!(etag::etag_from_fields!(""), etag::etag_from_fields!("", ""));
+}
+
+#[test]
+fn test_static_etag() {
+    const STRONG: EntityTag = etag::static_etag!("static-v1");
+
+    assert_eq!(STRONG, EntityTag::strong_unchecked("static-v1"));
+    assert_eq!(etag::static_etag!(W/"static-v1"), EntityTag::weak_unchecked("static-v1"));
+    assert_eq!(etag::static_etag!(""), EntityTag::strong_unchecked(""));
 }
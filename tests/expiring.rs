@@ -0,0 +1,56 @@
+use etag::{EntityTag, ParseError};
+use etag::expiring::ExpiringTag;
+
+const HASH: &str = "0123456789abcdef0123456789abcdef";
+
+#[test]
+fn test_expiring_new() {
+    let tag = ExpiringTag::new(1_700_000_000, b"body");
+    assert_eq!(tag.issued(), 1_700_000_000);
+    assert_eq!(tag.hash(), format!("{:032x}", xxhash_rust::xxh3::xxh3_128(b"body")));
+    assert_eq!(tag.to_string(), format!("1700000000-{}", tag.hash()));
+    assert!(!tag.as_etag().is_weak());
+
+    let max = ExpiringTag::new(u64::MAX, b"body");
+    assert_eq!(max.issued(), u64::MAX);
+    assert!(tag.same_content(&max));
+    assert!(!tag.same_content(&ExpiringTag::new(1_700_000_000, b"other")));
+}
+
+#[test]
+fn test_expiring_parse() {
+    let tag: ExpiringTag = format!("42-{}", HASH).parse().unwrap();
+    assert_eq!(tag.issued(), 42);
+    assert_eq!(tag.hash(), HASH);
+
+    assert_eq!(ExpiringTag::parse(HASH), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("-{}", HASH)), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("+1-{}", HASH)), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("1-{}", &HASH[1..])), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("1-{}0", HASH)), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("1-{}", HASH.to_uppercase())), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::parse(&format!("99999999999999999999-{}", HASH)), Err(ParseError::Overflow));
+}
+
+#[test]
+fn test_expiring_etag() {
+    let tag = ExpiringTag::new(10, b"body");
+    let etag = EntityTag::from(&tag);
+    assert_eq!(ExpiringTag::from_etag(&etag), Ok(tag.clone()));
+    assert_eq!(std::convert::TryFrom::try_from(&etag), Ok(tag.clone()));
+    assert_eq!(EntityTag::from(tag.clone()), etag);
+
+    assert_eq!(ExpiringTag::from_etag(&EntityTag::weak_unchecked(etag.tag())), Err(ParseError::InvalidFormat));
+    assert_eq!(ExpiringTag::from_etag(&EntityTag::ANY), Err(ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_is_expired() {
+    let tag = ExpiringTag::new(100, b"body");
+    assert!(!tag.is_expired(100, 10));
+    assert!(!tag.is_expired(109, 10));
+    assert!(tag.is_expired(110, 10));
+    assert!(!tag.is_expired(0, 10));
+    assert!(tag.is_expired(100, 0));
+    assert!(!tag.is_expired(u64::MAX, u64::MAX));
+}
@@ -0,0 +1,48 @@
+use etag::{EntityTag, EtagFilter};
+
+#[test]
+fn test_etag_filter() {
+    let mut filter = EtagFilter::<64>::new(4);
+    assert_eq!(filter.hashes(), 4);
+    assert!(!filter.contains(&EntityTag::strong_unchecked("v1")));
+
+    let tags: Vec<EntityTag> = (0..50).map(|idx| EntityTag::strong_unchecked(&idx.to_string())).collect();
+    for tag in tags.iter() {
+        filter.insert(tag);
+    }
+    filter.insert(&EntityTag::ANY);
+
+    for tag in tags.iter() {
+        assert!(filter.contains(tag));
+        let mut weak = tag.clone();
+        weak.set_weak(true);
+        assert!(filter.contains(&weak));
+    }
+    assert!(!filter.contains(&EntityTag::ANY));
+
+    let false_positives = (50..1050).filter(|idx| filter.contains(&EntityTag::strong_unchecked(&idx.to_string()))).count();
+    assert!(false_positives < 100, "{}", false_positives);
+
+    let received = EtagFilter::from_bytes(*filter.as_bytes(), filter.hashes());
+    assert!(received == filter);
+    assert!(tags.iter().all(|tag| received.contains(tag)));
+
+    filter.clear();
+    assert_eq!(filter.as_bytes(), &[0; 64]);
+    assert!(!filter.contains(&tags[0]));
+}
+
+#[test]
+fn test_etag_filter_hashes() {
+    assert_eq!(EtagFilter::<128>::optimal_hashes(100), 7);
+    assert_eq!(EtagFilter::<128>::optimal_hashes(0), 32);
+    assert_eq!(EtagFilter::<1>::optimal_hashes(100), 1);
+    assert_eq!(EtagFilter::<1>::new(0).hashes(), 1);
+}
+
+#[test]
+fn test_etag_filter_bytes_are_stable() {
+    let mut filter = EtagFilter::<8>::new(2);
+    filter.insert(&EntityTag::strong_unchecked("v1"));
+    assert_eq!(filter.as_bytes(), &[0, 32, 0, 64, 0, 0, 0, 0]);
+}
@@ -0,0 +1,139 @@
+use etag::EntityTag;
+use etag::generator::TimeBucketed;
+
+#[test]
+fn test_time_bucketed() {
+    let generator = TimeBucketed::new(60);
+    assert_eq!(generator.window_secs(), 60);
+    assert_eq!(generator.bucket(119), 1);
+
+    let tag = generator.generate(b"content", 60);
+    assert!(tag.is_weak());
+    assert_eq!(tag.content_components().map(|(len, _)| len), Some(7));
+    assert_eq!(tag, generator.generate(b"content", 119));
+    assert_ne!(tag, generator.generate(b"content", 120));
+    assert_ne!(tag, generator.generate(b"other", 60));
+    assert!(tag.weak_ne(&EntityTag::from_data(b"content")));
+
+    assert_eq!(TimeBucketed::new(0).window_secs(), 1);
+}
+
+#[test]
+fn test_namespace() {
+    use etag::generator::Namespace;
+
+    const TENANT: Namespace = Namespace::new("tenant-1");
+    let other = Namespace::new("tenant-2");
+    assert_ne!(TENANT.seed(), other.seed());
+
+    let tag = TENANT.from_data(b"content");
+    assert!(!tag.is_weak());
+    assert_eq!(tag.content_components().map(|(len, _)| len), Some(7));
+    assert_eq!(tag, Namespace::new("tenant-1").from_data(b"content"));
+    assert_ne!(tag, other.from_data(b"content"));
+    assert_ne!(tag, EntityTag::from_data(b"content"));
+
+    let weak = EntityTag::weak_unchecked("1.5-7");
+    let applied = TENANT.apply(&weak);
+    assert!(applied.is_weak());
+    assert_eq!(applied, TENANT.apply(&weak));
+    assert!(applied.weak_ne(&other.apply(&weak)));
+    assert!(!TENANT.apply(&EntityTag::strong_unchecked("1.5-7")).is_weak());
+    assert_eq!(TENANT.apply(&EntityTag::ANY), EntityTag::ANY);
+}
+
+#[test]
+fn test_etag_generator() {
+    use etag::generator::{EtagGenerator, GeneratorError, Storage};
+
+    //Storage of single counter, failing to store when disconnected
+    struct Flash {
+        counter: Option<u64>,
+        connected: bool,
+    }
+
+    impl Storage for Flash {
+        type Error = &'static str;
+
+        fn load(&mut self, _: &str) -> Result<Option<u64>, Self::Error> {
+            Ok(self.counter)
+        }
+
+        fn store(&mut self, _: &str, counter: u64) -> Result<(), Self::Error> {
+            match self.connected {
+                true => {
+                    self.counter = Some(counter);
+                    Ok(())
+                },
+                false => Err("disconnected"),
+            }
+        }
+    }
+
+    let mut flash = Flash { counter: None, connected: true };
+    let mut generator = EtagGenerator::new(&mut flash);
+    assert_eq!(generator.current("resource"), Ok(None));
+    assert_eq!(generator.next("resource"), Ok(EntityTag::strong_unchecked("1")));
+    assert_eq!(generator.next("resource"), Ok(EntityTag::strong_unchecked("2")));
+    assert_eq!(generator.current("resource"), Ok(Some(EntityTag::strong_unchecked("2"))));
+
+    //Restarted generator continues from persisted counter
+    let mut generator = EtagGenerator::new(&mut flash);
+    assert_eq!(generator.next("resource"), Ok(EntityTag::strong_unchecked("3")));
+    generator.into_storage().connected = false;
+
+    let mut generator = EtagGenerator::new(&mut flash);
+    assert_eq!(generator.next("resource"), Err(GeneratorError::Storage("disconnected")));
+    assert_eq!(generator.storage().counter, Some(3));
+
+    let mut flash = Flash { counter: Some(u64::MAX), connected: true };
+    let mut generator = EtagGenerator::new(&mut flash);
+    assert_eq!(generator.next("resource"), Err(GeneratorError::Exhausted));
+    assert_eq!(generator.current("resource"), Ok(Some(EntityTag::strong_unchecked("18446744073709551615"))));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_etag_generator_in_memory() {
+    use std::collections::HashMap;
+    use etag::generator::EtagGenerator;
+
+    let mut generator = EtagGenerator::new(HashMap::new());
+    assert_eq!(generator.next("a").unwrap(), EntityTag::strong_unchecked("1"));
+    assert_eq!(generator.next("b").unwrap(), EntityTag::strong_unchecked("1"));
+    assert_eq!(generator.next("a").unwrap(), EntityTag::strong_unchecked("2"));
+    assert_eq!(generator.storage().get("a"), Some(&2));
+}
+
+#[test]
+fn test_generation() {
+    use etag::generator::Generation;
+
+    let generation = Generation::new(7);
+    assert_eq!(generation.get(), 7);
+
+    let tag = generation.from_data(b"content");
+    assert!(!tag.is_weak());
+    assert_eq!(tag.content_components().map(|(len, _)| len), Some(7));
+    assert_eq!(tag, Generation::new(7).from_data(b"content"));
+    assert_ne!(tag, generation.from_data(b"other"));
+
+    let weak = EntityTag::weak_unchecked("1.5-7");
+    let applied = generation.apply(&weak);
+    assert!(applied.is_weak());
+    assert_eq!(generation.apply(&EntityTag::ANY), EntityTag::ANY);
+
+    assert_eq!(generation.bump_all(), Ok(8));
+    assert_eq!(generation.get(), 8);
+    assert_ne!(tag, generation.from_data(b"content"));
+    assert!(applied.weak_ne(&generation.apply(&weak)));
+
+    //Hash follows hash-* features, and zero seed doesn't alter it.
+    assert_eq!(Generation::new(0).from_data(b"content"), EntityTag::from_data(b"content"));
+
+    let generation = Generation::new(u32::MAX - 1);
+    assert_eq!(generation.bump_all(), Ok(u32::MAX));
+    assert_eq!(generation.bump_all(), Err(etag::generator::GeneratorError::Exhausted));
+    assert_eq!(generation.get(), u32::MAX);
+    assert_eq!(format!("{:?}", generation), format!("Generation({})", u32::MAX));
+}
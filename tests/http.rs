@@ -0,0 +1,76 @@
+#![cfg(feature = "http")]
+
+use etag::EntityTag;
+use http::HeaderMap;
+use http::header::{HeaderValue, ETAG, IF_MATCH, IF_NONE_MATCH};
+
+#[test]
+fn test_http_etag() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(etag::http::get_etag(&headers), None);
+
+    etag::http::set_etag(&mut headers, &EntityTag::strong_unchecked("v1")).unwrap();
+    etag::http::set_etag(&mut headers, &EntityTag::weak_unchecked("v2")).unwrap();
+    assert_eq!(headers.get_all(ETAG).iter().count(), 1);
+    assert_eq!(headers[ETAG], "W/\"v2\"");
+    assert_eq!(etag::http::get_etag(&headers), Some(Ok(EntityTag::weak_unchecked("v2"))));
+
+    headers.insert(ETAG, HeaderValue::from_static("v3"));
+    assert_eq!(etag::http::get_etag(&headers), Some(Err(etag::ParseError::InvalidFormat)));
+    headers.insert(ETAG, HeaderValue::from_bytes(b"\"\xff\"").unwrap());
+    assert_eq!(etag::http::get_etag(&headers), Some(Err(etag::ParseError::NotAscii)));
+}
+
+#[test]
+fn test_http_conditionals() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(etag::http::get_if_none_match(&headers), None);
+    assert_eq!(etag::http::get_if_match(&headers), None);
+
+    headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"a\", W/\"b\""));
+    headers.insert(IF_MATCH, HeaderValue::from_static("*"));
+
+    let if_none_match = etag::http::get_if_none_match(&headers).unwrap();
+    assert!(if_none_match.matches_if_none_match(Some(&EntityTag::strong_unchecked("b"))));
+    let if_match = etag::http::get_if_match(&headers).unwrap();
+    assert!(if_match.is_any());
+    assert_eq!(if_match.lines(), ["*"]);
+}
+
+#[test]
+fn test_http_conditionals_field_lines() {
+    let current = EntityTag::strong_unchecked("b");
+
+    let mut headers = HeaderMap::new();
+    headers.append(IF_MATCH, HeaderValue::from_static("\"a\""));
+    headers.append(IF_MATCH, HeaderValue::from_static("\"b\""));
+    let if_match = etag::http::get_if_match(&headers).unwrap();
+    assert_eq!(if_match.lines(), ["\"a\"", "\"b\""]);
+    assert!(if_match.matches_if_match(Some(&current)));
+
+    //Non-ASCII value is present, but never matches
+    headers.append(IF_MATCH, HeaderValue::from_bytes(b"\"b\xff\"").unwrap());
+    let if_match = etag::http::get_if_match(&headers).unwrap();
+    assert!(!if_match.is_valid());
+    assert!(!if_match.matches_if_match(Some(&current)));
+
+    headers.insert(IF_NONE_MATCH, HeaderValue::from_bytes(b"\xff").unwrap());
+    assert!(!etag::http::get_if_none_match(&headers).unwrap().matches_if_none_match(Some(&current)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_http_check_write() {
+    use etag::preconditions::{check_write_lines, WriteDecision};
+
+    let current = EntityTag::strong_unchecked("b");
+    let mut headers = HeaderMap::new();
+    assert_eq!(check_write_lines(&current, etag::http::get_if_match(&headers), None, true), WriteDecision::PreconditionRequired);
+
+    headers.append(IF_MATCH, HeaderValue::from_static("\"a\""));
+    headers.append(IF_MATCH, HeaderValue::from_static("\"b\""));
+    assert_eq!(check_write_lines(&current, etag::http::get_if_match(&headers), None, true), WriteDecision::Proceed);
+
+    headers.insert(IF_MATCH, HeaderValue::from_bytes(b"\"b\xff\"").unwrap());
+    assert_eq!(check_write_lines(&current, etag::http::get_if_match(&headers), None, false), WriteDecision::PreconditionFailed);
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "std")]
+
+use etag::EntityTag;
+use etag::intern::TagPool;
+
+#[test]
+fn test_tag_pool() {
+    let mut pool = TagPool::new();
+    assert!(pool.is_empty());
+
+    let first = pool.intern(&EntityTag::strong_unchecked("a"));
+    let weak = pool.intern(&EntityTag::weak_unchecked("a"));
+    assert_ne!(first, weak);
+    assert_eq!(pool.intern(&EntityTag::strong_unchecked("a")), first);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.refs(first), 2);
+    assert_eq!(pool.get(first), Some(&EntityTag::strong_unchecked("a")));
+    assert_eq!(pool.get(weak), Some(&EntityTag::weak_unchecked("a")));
+
+    assert!(pool.retain(first));
+    assert_eq!(pool.refs(first), 3);
+    assert_eq!(pool.release(first), None);
+    assert_eq!(pool.release(first), None);
+    assert_eq!(pool.release(first), Some(EntityTag::strong_unchecked("a")));
+    assert_eq!(pool.get(first), None);
+    assert_eq!(pool.refs(first), 0);
+    assert!(!pool.retain(first));
+    assert_eq!(pool.release(first), None);
+    assert_eq!(pool.len(), 1);
+
+    //Released slot is reused
+    assert_eq!(pool.intern(&EntityTag::strong_unchecked("b")), first);
+    assert_eq!(pool.get(first), Some(&EntityTag::strong_unchecked("b")));
+    assert_eq!(pool.len(), 2);
+}
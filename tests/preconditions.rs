@@ -0,0 +1,96 @@
+#![cfg(feature = "std")]
+
+use std::time::SystemTime;
+
+use etag::EntityTag;
+use etag::preconditions::{check_write, WriteDecision};
+
+#[test]
+fn test_check_write() {
+    let strong = EntityTag::strong_unchecked("xyzzy");
+    let weak = EntityTag::weak_unchecked("xyzzy");
+
+    assert_eq!(check_write(&strong, None, None, false), WriteDecision::Proceed);
+    assert_eq!(check_write(&strong, None, None, true), WriteDecision::PreconditionRequired);
+    assert_eq!(check_write(&strong, None, Some(SystemTime::UNIX_EPOCH), true), WriteDecision::Proceed);
+
+    assert_eq!(check_write(&strong, Some("\"xyzzy\""), None, true), WriteDecision::Proceed);
+    assert_eq!(check_write(&strong, Some("\"a\", \"xyzzy\""), None, false), WriteDecision::Proceed);
+    assert_eq!(check_write(&strong, Some("*"), None, true), WriteDecision::Proceed);
+    assert_eq!(check_write(&strong, Some("\"other\""), None, false), WriteDecision::PreconditionFailed);
+    assert_eq!(check_write(&strong, Some("W/\"xyzzy\""), None, false), WriteDecision::PreconditionFailed);
+    assert_eq!(check_write(&weak, Some("\"xyzzy\""), None, false), WriteDecision::PreconditionFailed);
+    assert_eq!(check_write(&weak, Some("*"), None, false), WriteDecision::Proceed);
+    assert_eq!(check_write(&strong, Some("\"other\""), Some(SystemTime::now()), false), WriteDecision::PreconditionFailed);
+}
+
+#[test]
+fn test_resource_validator() {
+    use std::time::Duration;
+    use etag::preconditions::{Decision, RequestConditionals, ResourceValidator};
+
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_millis(1_000_500);
+    let before = SystemTime::UNIX_EPOCH + Duration::from_secs(999);
+    let same = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let validator = ResourceValidator {
+        etag: Some(EntityTag::strong_unchecked("xyzzy")),
+        last_modified: Some(modified),
+    };
+
+    let get = RequestConditionals {
+        is_get_or_head: true,
+        ..Default::default()
+    };
+    let put = RequestConditionals::default();
+
+    assert_eq!(validator.evaluate(&get), Decision::Proceed);
+    assert_eq!(validator.evaluate(&put), Decision::Proceed);
+
+    //If-None-Match takes precedence over If-Modified-Since
+    assert_eq!(validator.evaluate(&RequestConditionals { if_none_match: Some("W/\"xyzzy\""), ..get }), Decision::NotModified);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_none_match: Some("\"other\""), if_modified_since: Some(same), ..get }), Decision::Proceed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_none_match: Some("*"), ..put }), Decision::PreconditionFailed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_modified_since: Some(same), ..get }), Decision::NotModified);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_modified_since: Some(before), ..get }), Decision::Proceed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_modified_since: Some(same), ..put }), Decision::Proceed);
+
+    //If-Match takes precedence over If-Unmodified-Since
+    assert_eq!(validator.evaluate(&RequestConditionals { if_match: Some("\"xyzzy\""), if_unmodified_since: Some(before), ..put }), Decision::Proceed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_match: Some("W/\"xyzzy\""), ..put }), Decision::PreconditionFailed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_unmodified_since: Some(before), ..put }), Decision::PreconditionFailed);
+    assert_eq!(validator.evaluate(&RequestConditionals { if_unmodified_since: Some(same), ..put }), Decision::Proceed);
+
+    //Failed If-Match is not overridden by If-None-Match
+    assert_eq!(validator.evaluate(&RequestConditionals { if_match: Some("\"other\""), if_none_match: Some("\"other\""), ..get }), Decision::PreconditionFailed);
+
+    let no_validators = ResourceValidator::default();
+    assert_eq!(no_validators.evaluate(&RequestConditionals { if_match: Some("*"), ..put }), Decision::Proceed);
+    assert_eq!(no_validators.evaluate(&RequestConditionals { if_match: Some("\"xyzzy\""), ..put }), Decision::PreconditionFailed);
+    assert_eq!(no_validators.evaluate(&RequestConditionals { if_modified_since: Some(same), ..get }), Decision::Proceed);
+}
+
+#[cfg(feature = "httpdate")]
+#[test]
+fn test_http_date() {
+    use std::time::Duration;
+    use etag::preconditions::{format_http_date, parse_http_date, Decision, RequestConditionals, ResourceValidator};
+
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(784111777);
+    assert_eq!(format_http_date(time).to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(parse_http_date(" Sun, 06 Nov 1994 08:49:37 GMT"), Some(time));
+    assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), Some(time));
+    assert_eq!(parse_http_date("yesterday"), None);
+
+    let validator = ResourceValidator {
+        etag: None,
+        last_modified: Some(time),
+    };
+    let get = RequestConditionals {
+        is_get_or_head: true,
+        ..Default::default()
+    };
+    assert_eq!(validator.evaluate(&get.with_if_modified_since("Sun, 06 Nov 1994 08:49:37 GMT")), Decision::NotModified);
+    assert_eq!(validator.evaluate(&get.with_if_modified_since("Sun, 06 Nov 1994 08:49:36 GMT")), Decision::Proceed);
+    assert_eq!(validator.evaluate(&get.with_if_modified_since("invalid")), Decision::Proceed);
+    assert_eq!(validator.evaluate(&RequestConditionals::default().with_if_unmodified_since("Sun, 06 Nov 1994 08:49:36 GMT")), Decision::PreconditionFailed);
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "proptest")]
+
+use etag::EntityTag;
+use proptest::proptest;
+
+proptest! {
+    #[test]
+    fn test_proptest_tag_roundtrip(tag in etag::proptest::entity_tag()) {
+        assert_eq!(tag.to_string().parse::<EntityTag>().unwrap(), tag);
+    }
+
+    #[test]
+    fn test_proptest_header_valid(header in etag::proptest::header()) {
+        assert_eq!(header.parse::<EntityTag>().unwrap().to_string(), header);
+    }
+
+    #[test]
+    fn test_proptest_malformed_header(header in etag::proptest::malformed_header()) {
+        assert!(header.parse::<EntityTag>().is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use etag::EntityTag;
+use etag::raw::find_etag;
+
+#[test]
+fn test_find_etag() {
+    let head = b"HTTP/1.1 304 Not Modified\r\nDate: Thu, 01 Jan 1970 00:00:00 GMT\r\nETag:  \"v1\"\t\r\n\r\n";
+    assert_eq!(find_etag(head), Some(EntityTag::strong_unchecked("v1")));
+
+    let head = b"HTTP/1.0 200 OK\nEtag: W/\"v2\"\n\n";
+    assert_eq!(find_etag(head), Some(EntityTag::weak_unchecked("v2")));
+
+    let head = b"HTTP/1.1 200 OK\r\nX-ETag: \"v1\"\r\n\r\nETag: \"v2\"\r\n";
+    assert_eq!(find_etag(head), None);
+
+    let head = b"HTTP/1.1 200 OK\r\nETag: \"v1\"";
+    assert_eq!(find_etag(head), None);
+}
+
+#[test]
+fn test_find_etag_invalid() {
+    assert_eq!(find_etag(b""), None);
+    assert_eq!(find_etag(b"ETag: \"v1\"\r\n\r\n"), None);
+    assert_eq!(find_etag(b"HTTP/1.1 200 OK\r\nETag: v1\r\n\r\n"), None);
+    assert_eq!(find_etag(b"HTTP/1.1 200 OK\r\nETag : \"v1\"\r\n\r\n"), None);
+    assert_eq!(find_etag(b"HTTP/1.1 200 OK\r\nETag: \"\xff\"\r\n\r\n"), None);
+    assert_eq!(find_etag(b"HTTP/1.1 200 OK\r\nETag: *\r\n\r\n"), None);
+}
@@ -0,0 +1,30 @@
+use etag::EntityTag;
+use etag::resource::{guard_update, PreconditionFailed, VersionedResource};
+
+struct Row {
+    version: &'static str,
+}
+
+impl VersionedResource for Row {
+    fn current_etag(&self) -> EntityTag {
+        EntityTag::strong_unchecked(self.version)
+    }
+}
+
+#[test]
+fn test_guard_update() {
+    let row = Row { version: "v2" };
+
+    assert_eq!(guard_update(&row, None), Ok(()));
+    assert_eq!(guard_update(&row, Some("*")), Ok(()));
+    assert_eq!(guard_update(&row, Some("\"v1\", \"v2\"")), Ok(()));
+    assert_eq!(guard_update(&&row, Some("\"v2\"")), Ok(()));
+
+    let error = guard_update(&row, Some("W/\"v2\"")).unwrap_err();
+    assert_eq!(error, PreconditionFailed { current: EntityTag::strong_unchecked("v2") });
+    assert_eq!(error.to_string(), "Precondition failed, current tag is \"v2\"");
+    assert!(guard_update(&row, Some("")).is_err());
+
+    assert!(guard_update(&EntityTag::weak_unchecked("v2"), Some("W/\"v2\"")).is_err());
+    assert_eq!(guard_update(&EntityTag::weak_unchecked("v2"), Some("*")), Ok(()));
+}
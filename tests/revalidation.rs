@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+
+use std::time::{Duration, SystemTime};
+
+use etag::EntityTag;
+use etag::revalidation::{CacheAction, RevalidationStore, StoredResponse, Validators};
+
+#[test]
+fn test_revalidation_action() {
+    let etag = EntityTag::weak_unchecked("v1");
+    let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    let later = date + Duration::from_secs(100);
+    let mut stored = StoredResponse {
+        etag: Some(&etag),
+        date,
+        cache_control: Some("public, MAX-AGE=\"100\""),
+    };
+
+    assert_eq!(stored.action(date), CacheAction::Fresh);
+    assert_eq!(stored.action(later - Duration::from_secs(1)), CacheAction::Fresh);
+    assert_eq!(stored.action(later), CacheAction::Revalidate);
+    //Clock skew doesn't make response stale
+    assert_eq!(stored.action(date - Duration::from_secs(10)), CacheAction::Fresh);
+    assert_eq!(stored.if_none_match(), Some("W/\"v1\""));
+
+    stored.cache_control = Some("max-age=100, no-cache");
+    assert_eq!(stored.action(date), CacheAction::Revalidate);
+    stored.cache_control = Some("max-age=100,no-store");
+    assert_eq!(stored.action(date), CacheAction::Refetch);
+    stored.cache_control = Some("max-age=abc");
+    assert_eq!(stored.action(date), CacheAction::Revalidate);
+    stored.cache_control = None;
+    assert_eq!(stored.action(date), CacheAction::Revalidate);
+
+    stored.etag = None;
+    assert_eq!(stored.action(date), CacheAction::Refetch);
+    assert_eq!(stored.if_none_match(), None);
+    stored.cache_control = Some("max-age=100");
+    assert_eq!(stored.action(date), CacheAction::Fresh);
+    assert_eq!(stored.action(later), CacheAction::Refetch);
+}
+
+#[test]
+fn test_revalidation_store() {
+    let path = std::env::temp_dir().join(format!("etag-revalidation-{}.tsv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+    let mut store = RevalidationStore::load(&path).unwrap();
+    assert!(store.is_empty());
+
+    let feed = Validators {
+        etag: Some(EntityTag::weak_unchecked("v,1")),
+        last_modified: Some(time),
+        fetched_at: time + Duration::from_secs(5),
+    };
+    let page = Validators {
+        etag: None,
+        last_modified: None,
+        fetched_at: time,
+    };
+    assert_eq!(store.update("https://example.com/feed", feed.clone()).unwrap(), None);
+    assert_eq!(store.update("https://example.com/page", page.clone()).unwrap(), None);
+    assert_eq!(store.update("https://example.com/\tpage", page.clone()).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(store.update("https://example.com/\npage", page.clone()).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(store.update("https://example.com/\r\npage", page.clone()).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(store.len(), 2);
+    store.save(&path).unwrap();
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(text, "https://example.com/feed\tW/\"v,1\"\t1700000000\t1700000005\nhttps://example.com/page\t\t\t1700000000\n");
+
+    let mut loaded = RevalidationStore::load(&path).unwrap();
+    assert_eq!(loaded, store);
+    assert_eq!(loaded.get("https://example.com/feed").unwrap().if_none_match(), Some("W/\"v,1\""));
+    assert_eq!(loaded.get("https://example.com/page").unwrap().if_none_match(), None);
+    assert_eq!(loaded.get("https://example.com/other"), None);
+
+    assert_eq!(loaded.remove("https://example.com/page"), Some(page.clone()));
+    assert_eq!(loaded.update("https://example.com/feed", Validators { etag: Some(EntityTag::weak_unchecked("v2")), ..feed.clone() }).unwrap(), Some(feed));
+    loaded.save(&path).unwrap();
+    assert_eq!(RevalidationStore::load(&path).unwrap(), loaded);
+
+    let any = Validators {
+        etag: Some(EntityTag::ANY),
+        ..page.clone()
+    };
+    loaded.update("https://example.com/any", any).unwrap();
+    loaded.save(&path).unwrap();
+    let reloaded = RevalidationStore::load(&path).unwrap();
+    assert_eq!(reloaded.get("https://example.com/any"), Some(&page));
+    assert_eq!(reloaded.get("https://example.com/feed"), loaded.get("https://example.com/feed"));
+
+    std::fs::write(&path, "https://example.com/\tinvalid\t\t1\n").unwrap();
+    assert_eq!(RevalidationStore::load(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::write(&path, "https://example.com/\t\t\n").unwrap();
+    assert_eq!(RevalidationStore::load(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::write(&path, "https://example.com/\t\tyesterday\t1\n").unwrap();
+    assert_eq!(RevalidationStore::load(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::write(&path, format!("https://example.com/\t\t\t{}\n", u64::MAX)).unwrap();
+    assert_eq!(RevalidationStore::load(&path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,48 @@
+use etag::{EntityTag, ParseError};
+use etag::revision::Revision;
+
+#[test]
+fn test_revision_parse() {
+    let revision: Revision = "3-a1b2c3".parse().unwrap();
+    assert_eq!(revision.generation(), 3);
+    assert_eq!(revision.hash(), "a1b2c3");
+    assert_eq!(revision.to_string(), "3-a1b2c3");
+    assert_eq!(revision.as_etag(), &EntityTag::strong_unchecked("3-a1b2c3"));
+
+    assert_eq!(Revision::parse("0-a1"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("a1"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("-a1"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("1-"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("1-xyz"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("+1-a1"), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::parse("99999999999999999999-a1"), Err(ParseError::Overflow));
+    assert_eq!(Revision::parse(&format!("1-{}", "a".repeat(EntityTag::MAX_TAG_LEN))), Err(ParseError::Overflow));
+}
+
+#[test]
+fn test_revision_etag() {
+    let revision = Revision::parse("12-deadbeef").unwrap();
+    let tag = EntityTag::from(&revision);
+    assert_eq!(tag.as_header_str(), "\"12-deadbeef\"");
+    assert_eq!(Revision::from_etag(&tag), Ok(revision.clone()));
+    assert_eq!(std::convert::TryFrom::try_from(&tag), Ok(revision.clone()));
+    assert_eq!(EntityTag::from(revision), tag);
+
+    assert_eq!(Revision::from_etag(&EntityTag::weak_unchecked("12-deadbeef")), Err(ParseError::InvalidFormat));
+    assert_eq!(Revision::from_etag(&EntityTag::ANY), Err(ParseError::InvalidFormat));
+}
+
+#[test]
+fn test_next_revision() {
+    let first = Revision::first(b"body");
+    assert_eq!(first.generation(), 1);
+    assert_eq!(first.hash().len(), 32);
+    assert_eq!(first, Revision::first(b"body"));
+    assert_ne!(first, Revision::first(b"other"));
+
+    let second = first.next_revision(b"body");
+    assert_eq!(second.generation(), 2);
+    assert_ne!(second.hash(), first.hash());
+    assert_eq!(Revision::parse(second.as_str()), Ok(second.clone()));
+    assert_ne!(second.next_revision(b"body"), Revision::parse("2-0").unwrap().next_revision(b"body"));
+}
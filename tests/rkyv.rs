@@ -0,0 +1,52 @@
+#![cfg(feature = "rkyv")]
+
+use etag::{ArchivedEntityTag, EntityTag};
+use rkyv::rancor;
+
+#[test]
+fn test_rkyv_roundtrip() {
+    for tag in [EntityTag::strong_unchecked("foobar"), EntityTag::weak_unchecked("weak-tag"), EntityTag::strong_unchecked(""), EntityTag::from_data(b"12")].iter() {
+        let bytes = rkyv::to_bytes::<rancor::Error>(tag).expect("To serialize");
+        let archived = rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).expect("To access");
+        assert_eq!(*archived, *tag);
+        assert_eq!(archived.weak(), tag.is_weak());
+        assert_eq!(archived.tag(), tag.tag());
+
+        let result = rkyv::deserialize::<EntityTag, rancor::Error>(archived).expect("To deserialize");
+        assert_eq!(result, *tag);
+    }
+}
+
+#[test]
+fn test_rkyv_any() {
+    let bytes = rkyv::to_bytes::<rancor::Error>(&EntityTag::ANY).expect("To serialize");
+    let archived = rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).expect("To access");
+    assert!(archived.is_any());
+    assert_eq!(archived.tag(), "");
+    assert_eq!(*archived, EntityTag::ANY);
+    assert_ne!(*archived, EntityTag::strong_unchecked(""));
+
+    let result = rkyv::deserialize::<EntityTag, rancor::Error>(archived).expect("To deserialize");
+    assert!(result.is_any());
+}
+
+#[test]
+fn test_rkyv_validation() {
+    let tag = EntityTag::strong_unchecked("foobar");
+    let mut bytes = rkyv::to_bytes::<rancor::Error>(&tag).expect("To serialize");
+    assert_eq!(bytes.len(), EntityTag::MAX_TAG_LEN + 2);
+
+    bytes[2] = 0xff;
+    assert!(rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).is_err());
+    bytes[2] = b'f';
+    bytes[1] = EntityTag::MAX_TAG_LEN as u8 + 1;
+    assert!(rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).is_err());
+
+    bytes[1] = tag.len() as u8;
+    bytes[3] = b'"';
+    assert!(rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).is_err());
+    bytes[3] = b'\n';
+    assert!(rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).is_err());
+    bytes[3] = b'o';
+    assert!(rkyv::access::<ArchivedEntityTag, rancor::Error>(&bytes).is_ok());
+}
@@ -0,0 +1,110 @@
+use etag::{scan_headers, EntityTag, ParseError};
+
+#[test]
+fn test_scan_headers() {
+    let headers: [(&str, &[u8]); 8] = [
+        ("Content-Type", b"text/html"),
+        ("ETAG", b" W/\"v1\" "),
+        ("if-match", b"\"a\", \"b\""),
+        ("If-Match", b"\"c\""),
+        ("if-none-match", b"*"),
+        ("If-Modified-Since", b"Sun, 06 Nov 1994 08:49:37 GMT"),
+        ("If-Range", b""),
+        ("If-Unmodified-Since", b"\xff"),
+    ];
+    let scanned = scan_headers(headers.iter().copied());
+
+    assert_eq!(scanned.etag(), Some(Ok(EntityTag::weak_unchecked("v1"))));
+    let if_match = scanned.if_match.unwrap();
+    assert_eq!(if_match.lines(), ["\"a\", \"b\"", "\"c\""]);
+    assert!(if_match.is_valid());
+    assert!(if_match.matches_if_match(Some(&EntityTag::strong_unchecked("b"))));
+    assert!(if_match.matches_if_match(Some(&EntityTag::strong_unchecked("c"))));
+    assert!(!if_match.matches_if_match(Some(&EntityTag::weak_unchecked("c"))));
+    assert!(!if_match.matches_if_match(None));
+    assert!(scanned.if_none_match.unwrap().is_any());
+    assert!(scanned.if_none_match.unwrap().matches_if_none_match(None));
+    assert_eq!(scanned.if_range, Some(""));
+    assert_eq!(scanned.if_modified_since, Some("Sun, 06 Nov 1994 08:49:37 GMT"));
+    //Invalid UTF-8 is still present
+    assert_eq!(scanned.if_unmodified_since, Some(""));
+    assert!(scanned.has_preconditions());
+
+    let scanned = scan_headers([("etag", "v1".as_bytes()), ("if-range", b"\"v1\"")]);
+    assert_eq!(scanned.etag(), Some(Err(ParseError::InvalidFormat)));
+    assert_eq!(scan_headers([("etag", "*".as_bytes())]).etag(), Some(Err(ParseError::InvalidFormat)));
+    assert!(!scanned.has_preconditions());
+    assert_eq!(scan_headers(core::iter::empty()), Default::default());
+}
+
+#[test]
+fn test_scan_headers_invalid_field_lines() {
+    let tag = EntityTag::strong_unchecked("v1");
+
+    //Line, which is not valid UTF-8, makes header present, but matching nothing
+    let scanned = scan_headers([("If-Match", "\"v1\"".as_bytes()), ("If-Match", b"\xff")]);
+    let if_match = scanned.if_match.unwrap();
+    assert!(!if_match.is_valid());
+    assert_eq!(if_match.lines(), ["\"v1\""]);
+    assert!(!if_match.matches_if_match(Some(&tag)));
+
+    let scanned = scan_headers([("If-None-Match", &b"\xff"[..])]);
+    assert!(scanned.has_preconditions());
+    assert!(!scanned.if_none_match.unwrap().matches_if_none_match(Some(&tag)));
+
+    //Wildcard is valid only as sole value
+    let scanned = scan_headers([("If-None-Match", "*".as_bytes()), ("If-None-Match", b"\"v0\"")]);
+    let if_none_match = scanned.if_none_match.unwrap();
+    assert!(!if_none_match.is_any());
+    assert!(!if_none_match.matches_if_none_match(Some(&tag)));
+
+    //Lines beyond limit make header invalid
+    let scanned = scan_headers([("If-None-Match", "\"v1\"".as_bytes()); 5]);
+    assert!(!scanned.if_none_match.unwrap().is_valid());
+    assert_eq!(scanned.if_none_match.unwrap().lines().len(), 4);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_scan_headers_to_request() {
+    use etag::preconditions::{Decision, ResourceValidator};
+
+    let headers: [(&str, &[u8]); 2] = [("If-None-Match", b"\"v1\""), ("If-Unmodified-Since", b"Sun, 06 Nov 1994 08:49:37 GMT")];
+    let request = scan_headers(headers.iter().copied()).to_request(true).unwrap();
+    assert!(request.is_get_or_head);
+    assert_eq!(request.if_none_match, Some("\"v1\""));
+    assert_eq!(request.if_match, None);
+    #[cfg(feature = "httpdate")]
+    assert!(request.if_unmodified_since.is_some());
+    #[cfg(not(feature = "httpdate"))]
+    assert!(request.if_unmodified_since.is_none());
+
+    let validator = ResourceValidator {
+        etag: Some(EntityTag::strong_unchecked("v1")),
+        last_modified: None,
+    };
+    assert_eq!(validator.evaluate(&request), Decision::NotModified);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_scan_headers_evaluate() {
+    use etag::preconditions::{Decision, ResourceValidator};
+
+    let validator = ResourceValidator {
+        etag: Some(EntityTag::strong_unchecked("v1")),
+        last_modified: None,
+    };
+
+    let scanned = scan_headers([("If-None-Match", "\"v0\"".as_bytes()), ("If-None-Match", b"W/\"v1\"")]);
+    assert_eq!(scanned.to_request(true), None);
+    assert_eq!(scanned.evaluate(&validator, true), Decision::NotModified);
+    assert_eq!(scanned.evaluate(&validator, false), Decision::PreconditionFailed);
+
+    let scanned = scan_headers([("If-Match", "\"v0\"".as_bytes()), ("If-Match", b"\"v1\"")]);
+    assert_eq!(scanned.evaluate(&validator, false), Decision::Proceed);
+    let scanned = scan_headers([("If-Match", &b"\xff"[..])]);
+    assert_eq!(scanned.to_request(false), None);
+    assert_eq!(scanned.evaluate(&validator, false), Decision::PreconditionFailed);
+    assert_eq!(scan_headers([("If-Match", "*".as_bytes())]).evaluate(&ResourceValidator::default(), false), Decision::Proceed);
+}
@@ -0,0 +1,15 @@
+#![cfg(feature = "schemars")]
+
+use etag::EntityTag;
+
+#[test]
+fn test_schemars_schema() {
+    let schema = schemars::schema_for!(EntityTag);
+    let schema = schema.as_value();
+
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["maxLength"], EntityTag::MAX_TAG_LEN + 4);
+
+    let pattern = schema["pattern"].as_str().expect("To have pattern");
+    assert_eq!(pattern, "^(W/)?\"[\\x21\\x23-\\x7E]*\"$");
+}
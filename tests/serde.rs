@@ -0,0 +1,64 @@
+#![cfg(feature = "serde")]
+
+use etag::EntityTag;
+use serde_test::{Token, Configure, assert_tokens, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens_error};
+
+#[test]
+fn test_serde_readable() {
+    assert_tokens(&EntityTag::strong_unchecked("foobar").readable(), &[Token::Str("\"foobar\"")]);
+    assert_tokens(&EntityTag::weak_unchecked("foobar").readable(), &[Token::Str("W/\"foobar\"")]);
+    assert_de_tokens_error::<serde_test::Readable<EntityTag>>(&[Token::Str("foobar")], "EntityTag uses invalid format");
+    assert_de_tokens_error::<serde_test::Readable<EntityTag>>(&[Token::Str("*")], "EntityTag uses invalid format");
+    assert_ser_tokens_error(&EntityTag::ANY.readable(), &[], "EntityTag wildcard cannot be serialized");
+}
+
+#[test]
+fn test_serde_compact() {
+    assert_tokens(&EntityTag::strong_unchecked("foobar").compact(), &[
+        Token::Tuple { len: 2 },
+        Token::Bool(false),
+        Token::Str("foobar"),
+        Token::TupleEnd,
+    ]);
+    assert_tokens(&EntityTag::weak_unchecked("foobar").compact(), &[
+        Token::Tuple { len: 2 },
+        Token::Bool(true),
+        Token::Str("foobar"),
+        Token::TupleEnd,
+    ]);
+    assert_de_tokens_error::<serde_test::Compact<EntityTag>>(&[
+        Token::Tuple { len: 2 },
+        Token::Bool(true),
+        Token::Str("ろり"),
+        Token::TupleEnd,
+    ], "EntityTag uses non-ASCII characters");
+}
+
+#[test]
+fn test_serde_compact_any() {
+    assert_tokens(&EntityTag::ANY.compact(), &[
+        Token::Tuple { len: 2 },
+        Token::Bool(false),
+        Token::Str("\""),
+        Token::TupleEnd,
+    ]);
+    assert_de_tokens_error::<serde_test::Compact<EntityTag>>(&[
+        Token::Tuple { len: 2 },
+        Token::Bool(true),
+        Token::Str("\""),
+        Token::TupleEnd,
+    ], "EntityTag uses invalid format");
+    //Opaque tag `*` is regular tag
+    assert_tokens(&EntityTag::strong_unchecked("*").compact(), &[
+        Token::Tuple { len: 2 },
+        Token::Bool(false),
+        Token::Str("*"),
+        Token::TupleEnd,
+    ]);
+}
+
+#[test]
+fn test_serde_compact_accepts_header() {
+    assert_de_tokens(&EntityTag::weak_unchecked("foobar").compact(), &[Token::Str("W/\"foobar\"")]);
+    assert_de_tokens_error::<serde_test::Compact<EntityTag>>(&[Token::Str("*")], "EntityTag uses invalid format");
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use etag::EntityTag;
+use etag::preconditions::RequestConditionals;
+use etag::serve::{serve_file, serve_file_with, ServeDecision};
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("etag-serve-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("To create dir");
+    let path = dir.join(name);
+    fs::write(&path, content).expect("To write");
+    path
+}
+
+fn get<'a>(if_none_match: Option<&'a str>) -> RequestConditionals<'a> {
+    RequestConditionals {
+        is_get_or_head: true,
+        if_none_match,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_serve_file() {
+    let path = temp_file("index.html", "<html></html>");
+    let expected = EntityTag::try_from_file_meta(&fs::metadata(&path).unwrap()).unwrap();
+
+    let etag = match serve_file(&path, &get(None)).unwrap() {
+        ServeDecision::Full { mut file, etag, len } => {
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            assert_eq!(content, "<html></html>");
+            assert_eq!(len, 13);
+            etag
+        },
+        decision => panic!("Unexpected decision: {:?}", decision),
+    };
+    assert_eq!(etag, expected);
+
+    let header = etag.to_string();
+    match serve_file(&path, &get(Some(&header))).unwrap() {
+        ServeDecision::NotModified { etag } => assert_eq!(etag, expected),
+        decision => panic!("Unexpected decision: {:?}", decision),
+    }
+    assert!(matches!(serve_file(&path, &get(Some("\"other\""))).unwrap(), ServeDecision::Full { .. }));
+
+    let put = RequestConditionals {
+        if_match: Some("\"other\""),
+        ..Default::default()
+    };
+    assert!(matches!(serve_file(&path, &put).unwrap(), ServeDecision::PreconditionFailed));
+}
+
+#[test]
+fn test_serve_file_modified_since() {
+    let path = temp_file("style.css", "a{}");
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+    let request = RequestConditionals {
+        is_get_or_head: true,
+        if_modified_since: Some(modified + Duration::from_secs(1)),
+        ..Default::default()
+    };
+    assert!(matches!(serve_file(&path, &request).unwrap(), ServeDecision::NotModified { .. }));
+
+    let request = RequestConditionals {
+        is_get_or_head: true,
+        if_modified_since: Some(SystemTime::UNIX_EPOCH),
+        ..Default::default()
+    };
+    assert!(matches!(serve_file(&path, &request).unwrap(), ServeDecision::Full { .. }));
+}
+
+#[test]
+fn test_serve_file_with() {
+    let path = temp_file("app.js", "main()");
+    let content_tag = EntityTag::from_data(b"main()");
+
+    let header = content_tag.to_string();
+    let decision = serve_file_with(&path, &get(Some(&header)), |_| Ok(content_tag.clone())).unwrap();
+    assert!(matches!(decision, ServeDecision::NotModified { etag } if etag == content_tag));
+
+    let error = serve_file_with(&path, &get(None), |_| Err(std::io::Error::other("lookup"))).unwrap_err();
+    assert_eq!(error.to_string(), "lookup");
+}
+
+#[test]
+fn test_serve_file_errors() {
+    let path = temp_file("exists", "");
+    assert_eq!(serve_file(path.with_file_name("missing"), &get(None)).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    assert!(serve_file(path.parent().unwrap(), &get(None)).is_err());
+}
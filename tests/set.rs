@@ -0,0 +1,84 @@
+use etag::{Comparison, EntityTag, FixedEtagSet};
+
+#[test]
+fn test_fixed_etag_set() {
+    let mut set = FixedEtagSet::<4>::new();
+    assert!(set.is_empty());
+    assert_eq!(set.capacity(), 4);
+
+    assert_eq!(set.insert(EntityTag::strong_unchecked("a")), Ok(true));
+    assert_eq!(set.insert(EntityTag::strong_unchecked("a")), Ok(false));
+    assert_eq!(set.insert(EntityTag::weak_unchecked("a")), Ok(true));
+    assert_eq!(set.insert(EntityTag::ANY), Ok(false));
+    assert_eq!(set.insert(EntityTag::strong_unchecked("b")), Ok(true));
+    assert_eq!(set.insert(EntityTag::strong_unchecked("c")), Ok(true));
+    assert_eq!(set.insert(EntityTag::strong_unchecked("d")), Err(EntityTag::strong_unchecked("d")));
+    assert_eq!(set.insert(EntityTag::strong_unchecked("c")), Ok(false));
+    assert_eq!(set.len(), 4);
+
+    assert!(set.contains(&EntityTag::weak_unchecked("b")));
+    assert!(!set.contains_with(&EntityTag::weak_unchecked("b"), Comparison::Strong));
+    assert!(!set.contains(&EntityTag::strong_unchecked("d")));
+    assert!(!set.contains(&EntityTag::ANY));
+
+    assert!(set.remove(&EntityTag::strong_unchecked("a")));
+    assert!(!set.remove(&EntityTag::strong_unchecked("a")));
+    assert!(!set.remove(&EntityTag::ANY));
+    assert_eq!(set.len(), 3);
+    assert!(set.contains_with(&EntityTag::weak_unchecked("a"), Comparison::Weak));
+    assert!(!set.contains_with(&EntityTag::strong_unchecked("a"), Comparison::Strong));
+    assert_eq!(set.iter().count(), 3);
+
+    set.clear();
+    assert!(set.is_empty());
+    assert!(!set.contains(&EntityTag::weak_unchecked("a")));
+    assert!(!FixedEtagSet::<0>::new().contains(&EntityTag::weak_unchecked("a")));
+}
+
+#[test]
+fn test_fixed_etag_set_remove_keeps_probe_sequences() {
+    let mut set = FixedEtagSet::<32>::new();
+    let tags: Vec<EntityTag> = (0..32).map(|idx| EntityTag::strong_unchecked(&idx.to_string())).collect();
+    for tag in tags.iter() {
+        assert_eq!(set.insert(tag.clone()), Ok(true));
+    }
+
+    for (idx, tag) in tags.iter().enumerate().filter(|(idx, _)| idx % 3 == 0) {
+        assert!(set.remove(tag), "{}", idx);
+    }
+
+    for (idx, tag) in tags.iter().enumerate() {
+        assert_eq!(set.contains(tag), idx % 3 != 0, "{}", idx);
+    }
+    assert_eq!(set.len(), 21);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_etag_set() {
+    use etag::EtagSet;
+
+    let mut set = EtagSet::new();
+    assert_eq!(set.capacity(), 0);
+    assert!(!set.contains(&EntityTag::strong_unchecked("0")));
+    assert!(!set.remove(&EntityTag::strong_unchecked("0")));
+
+    for idx in 0..1000 {
+        assert!(set.insert(EntityTag::strong_unchecked(&idx.to_string())));
+    }
+    assert!(!set.insert(EntityTag::strong_unchecked("0")));
+    assert!(!set.insert(EntityTag::ANY));
+    assert_eq!(set.len(), 1000);
+    assert!(set.capacity() >= 1000);
+
+    for idx in (0..1000).step_by(2) {
+        assert!(set.remove(&EntityTag::strong_unchecked(&idx.to_string())));
+    }
+    for idx in 0..1000 {
+        assert_eq!(set.contains(&EntityTag::weak_unchecked(&idx.to_string())), idx % 2 == 1);
+    }
+    assert_eq!(set.iter().count(), 500);
+
+    let set = EtagSet::with_capacity(100);
+    assert!(set.capacity() >= 100);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "sqlx")]
+
+use etag::{EntityTag, ParseError};
+use etag::sqlx::{push_if_match, RowVersion};
+use sqlx::{Postgres, QueryBuilder};
+
+#[test]
+fn test_row_version() {
+    let version = RowVersion(42);
+    assert_eq!(version.etag(), EntityTag::strong_unchecked("42"));
+    assert_eq!(EntityTag::from(RowVersion(-1)), EntityTag::strong_unchecked("-1"));
+    assert_eq!(RowVersion::from_etag(&version.etag()), Ok(version));
+    assert_eq!(RowVersion::from_etag(&EntityTag::weak_unchecked("42")), Err(ParseError::InvalidFormat));
+    assert_eq!(RowVersion::from_etag(&EntityTag::strong_unchecked("v42")), Err(ParseError::InvalidFormat));
+    assert_eq!(RowVersion::from_etag(&EntityTag::ANY), Err(ParseError::InvalidFormat));
+    assert_eq!(RowVersion::from_etag(&EntityTag::strong_unchecked("-1")), Ok(RowVersion(-1)));
+    for tag in ["007", "+7", "-0", " 7", ""].iter() {
+        assert_eq!(RowVersion::from_etag(&EntityTag::strong_unchecked(tag)), Err(ParseError::InvalidFormat), "{}", tag);
+    }
+}
+
+#[test]
+fn test_push_if_match() {
+    fn guard(if_match: &str) -> String {
+        let mut query = QueryBuilder::<Postgres>::new("WHERE ");
+        push_if_match(&mut query, "version", if_match);
+        query.sql().to_owned()
+    }
+
+    assert_eq!(guard("*"), "WHERE TRUE");
+    assert_eq!(guard("\"1\""), "WHERE version IN ($1)");
+    assert_eq!(guard("\"1\", W/\"2\", \"v3\", \"4\""), "WHERE version IN ($1, $2)");
+    assert_eq!(guard("W/\"1\""), "WHERE FALSE");
+    assert_eq!(guard("\"007\", \"+7\""), "WHERE FALSE");
+    assert_eq!(guard(""), "WHERE FALSE");
+}
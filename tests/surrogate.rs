@@ -0,0 +1,33 @@
+use etag::EntityTag;
+use etag::surrogate::{write_keys, KeyFormat};
+
+#[test]
+fn test_surrogate_keys() {
+    let tags = [EntityTag::strong_unchecked("a,b"), EntityTag::ANY, EntityTag::weak_unchecked("c d"), EntityTag::strong_unchecked("")];
+
+    let mut header = String::new();
+    assert_eq!(write_keys(&KeyFormat::CLOUDFLARE, "", tags.iter(), &mut header), Ok(2));
+    assert_eq!(header, "a_b,c_d");
+
+    let mut header = String::new();
+    assert_eq!(write_keys(&KeyFormat::FASTLY, "ключ/", tags.iter(), &mut header), Ok(3));
+    assert_eq!(header, "_________a_b _________c_d _________");
+}
+
+#[test]
+fn test_surrogate_keys_limits() {
+    let format = KeyFormat {
+        separator: ' ',
+        max_key_len: 5,
+        max_header_len: 11,
+    };
+    let tags = [EntityTag::strong_unchecked("abcdef"), EntityTag::strong_unchecked("ghijkl"), EntityTag::strong_unchecked("mnopqr")];
+
+    let mut header = String::new();
+    assert_eq!(write_keys(&format, "k-", tags.iter(), &mut header), Ok(2));
+    assert_eq!(header, "k-abc k-ghi");
+
+    let mut header = String::new();
+    assert_eq!(write_keys(&format, "ключ", tags.iter(), &mut header), Ok(2));
+    assert_eq!(header, "_____ _____");
+}
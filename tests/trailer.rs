@@ -0,0 +1,45 @@
+use etag::EntityTag;
+use etag::trailer::StreamingTag;
+
+#[test]
+fn test_streaming_tag() {
+    let mut tag = StreamingTag::new();
+    assert!(tag.is_empty());
+    assert_eq!(tag.etag(), EntityTag::from_data(b""));
+
+    tag.update(b"streamed ");
+    tag.update(b"");
+    tag.update(b"body");
+    assert_eq!(tag.len(), 13);
+    assert!(!tag.etag().is_weak());
+    assert_eq!(tag.etag(), EntityTag::from_data(b"streamed body"));
+}
+
+#[test]
+fn test_streaming_tag_uneven_frames() {
+    let body = (0..300u32).map(|idx| (idx * 37) as u8).collect::<Vec<_>>();
+
+    for frame_len in [1, 7, 31, 32, 33, 64, 100] {
+        let mut tag = StreamingTag::new();
+        for frame in body.chunks(frame_len) {
+            tag.update(frame);
+        }
+        assert_eq!(tag.etag(), EntityTag::from_data(&body), "frame_len={}", frame_len);
+    }
+}
+
+#[test]
+fn test_streaming_tag_chunked() {
+    let frame = [b'a'; 300];
+    let mut tag = StreamingTag::default();
+
+    assert_eq!(tag.chunk_header(&frame), "12c\r\n");
+    assert_eq!(tag.chunk_header(b""), "");
+    assert_eq!(tag.chunk_header(b"b"), "1\r\n");
+    assert_eq!(tag.len(), 301);
+
+    let mut last_chunk = String::new();
+    tag.write_last_chunk(&mut last_chunk).unwrap();
+    assert_eq!(last_chunk, format!("0\r\nETag: {}\r\n\r\n", tag.etag()));
+    assert_eq!(etag::trailer::TRAILER, ("Trailer", "ETag"));
+}
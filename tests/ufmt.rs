@@ -0,0 +1,29 @@
+#![cfg(feature = "ufmt")]
+
+use etag::{EntityTag, ParseError};
+
+struct Out(String);
+
+impl ufmt::uWrite for Out {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.0.push_str(text);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_ufmt_display_debug() {
+    let mut out = Out(String::new());
+    ufmt::uwrite!(out, "{} {}", EntityTag::strong_unchecked("xyzzy"), EntityTag::weak_unchecked("xyzzy")).unwrap();
+    assert_eq!(out.0, "\"xyzzy\" W/\"xyzzy\"");
+
+    let mut out = Out(String::new());
+    ufmt::uwrite!(out, "{:?}", EntityTag::weak_unchecked("xyzzy")).unwrap();
+    assert_eq!(out.0, format!("{:?}", EntityTag::weak_unchecked("xyzzy")));
+
+    let mut out = Out(String::new());
+    ufmt::uwrite!(out, "{:?}: {}", ParseError::NotAscii, ParseError::NotAscii).unwrap();
+    assert_eq!(out.0, format!("{:?}: {}", ParseError::NotAscii, ParseError::NotAscii));
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "utoipa")]
+
+use etag::EntityTag;
+use utoipa::{IntoParams, PartialSchema, ToSchema};
+use utoipa::openapi::{RefOr, Required};
+use utoipa::openapi::path::ParameterIn;
+use utoipa::openapi::schema::Schema;
+
+#[test]
+fn test_utoipa_schema() {
+    assert_eq!(EntityTag::name(), "EntityTag");
+    match EntityTag::schema() {
+        RefOr::T(Schema::Object(object)) => {
+            assert_eq!(object.pattern.as_deref(), Some("^(W/)?\"[\\x21\\x23-\\x7E]*\"$"));
+            assert_eq!(object.max_length, Some(EntityTag::MAX_TAG_LEN + 4));
+        },
+        _ => panic!("Unexpected schema"),
+    }
+}
+
+#[test]
+fn test_utoipa_params() {
+    let params = etag::utoipa::IfMatch::into_params(|| None);
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].name, "If-Match");
+    assert!(params[0].parameter_in == ParameterIn::Header);
+    assert!(params[0].required == Required::False);
+
+    let params = etag::utoipa::IfNoneMatch::into_params(|| None);
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].name, "If-None-Match");
+
+    assert!(etag::utoipa::etag_header().description.is_some());
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "wasm")]
+
+use etag::EntityTag;
+use etag::wasm::WasmEntityTag;
+
+#[test]
+fn test_wasm_tag() {
+    let strong = WasmEntityTag::parse("\"foobar\"").unwrap_or_else(|_| panic!("To parse"));
+    let weak = WasmEntityTag::parse("W/\"foobar\"").unwrap_or_else(|_| panic!("To parse"));
+
+    assert!(!strong.weak());
+    assert!(weak.weak());
+    assert_eq!(strong.tag(), "foobar");
+    assert_eq!(weak.to_string(), "W/\"foobar\"");
+    assert!(strong.weak_eq(&weak));
+    assert!(!strong.strong_eq(&weak));
+    assert!(strong.strong_eq(&strong));
+
+    let data = WasmEntityTag::from_data(b"12");
+    assert_eq!(EntityTag::from(data), EntityTag::from_data(b"12"));
+}
@@ -0,0 +1,107 @@
+use etag::EntityTag;
+use etag::zip::{parse_central_header, parse_local_header};
+
+fn central_header(name: &[u8], crc32: u32, size: u32, extra: &[u8], comment: &[u8]) -> Vec<u8> {
+    let mut header = Vec::new();
+    header.extend_from_slice(&0x02014b50u32.to_le_bytes());
+    header.extend_from_slice(&[20, 0, 20, 0, 0, 0, 8, 0, 0, 0, 0, 0]);
+    header.extend_from_slice(&crc32.to_le_bytes());
+    header.extend_from_slice(&1u32.to_le_bytes());
+    header.extend_from_slice(&size.to_le_bytes());
+    header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(comment.len() as u16).to_le_bytes());
+    header.extend_from_slice(&[0; 12]);
+    header.extend_from_slice(name);
+    header.extend_from_slice(extra);
+    header.extend_from_slice(comment);
+    header
+}
+
+fn local_header(name: &[u8], flags: u16, crc32: u32, size: u32, extra: &[u8]) -> Vec<u8> {
+    let mut header = Vec::new();
+    header.extend_from_slice(&0x04034b50u32.to_le_bytes());
+    header.extend_from_slice(&[20, 0]);
+    header.extend_from_slice(&flags.to_le_bytes());
+    header.extend_from_slice(&[8, 0, 0, 0, 0, 0]);
+    header.extend_from_slice(&crc32.to_le_bytes());
+    header.extend_from_slice(&1u32.to_le_bytes());
+    header.extend_from_slice(&size.to_le_bytes());
+    header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+    header.extend_from_slice(name);
+    header.extend_from_slice(extra);
+    header
+}
+
+const CONTENT: &[u8] = b"body{margin:0}";
+const CONTENT_CRC32: u32 = 0x7da78e48;
+
+#[test]
+fn test_from_crc32() {
+    assert_eq!(EntityTag::from_crc32(CONTENT_CRC32, CONTENT.len() as u64), EntityTag::const_from_data_crc32(CONTENT));
+}
+
+#[test]
+fn test_central_header() {
+    let mut directory = central_header(b"style.css", CONTENT_CRC32, CONTENT.len() as u32, &[0x55, 0x54, 1, 0, 0], b"comment");
+    let first_len = directory.len();
+    directory.extend_from_slice(&central_header(b"empty", 0, 0, &[], &[]));
+
+    let entry = parse_central_header(&directory).expect("To parse");
+    assert_eq!(entry.name, b"style.css");
+    assert_eq!(entry.crc32, CONTENT_CRC32);
+    assert_eq!(entry.size, CONTENT.len() as u64);
+    assert_eq!(entry.header_len, first_len);
+    assert_eq!(entry.etag(), EntityTag::const_from_data_crc32(CONTENT));
+
+    let entry = parse_central_header(&directory[entry.header_len..]).expect("To parse");
+    assert_eq!(entry.name, b"empty");
+    assert_eq!(entry.etag(), EntityTag::const_from_data_crc32(b""));
+}
+
+#[test]
+fn test_zip64_size() {
+    let size = 5u64 << 32;
+    let mut extra = vec![0x55, 0x54, 1, 0, 0];
+    extra.extend_from_slice(&[1, 0, 16, 0]);
+    extra.extend_from_slice(&size.to_le_bytes());
+    extra.extend_from_slice(&1u64.to_le_bytes());
+
+    let header = central_header(b"big.bin", 1, u32::MAX, &extra, &[]);
+    assert_eq!(parse_central_header(&header).unwrap().size, size);
+    assert_eq!(parse_central_header(&header).unwrap().etag(), EntityTag::from_crc32(1, size));
+
+    let header = local_header(b"big.bin", 0, 1, u32::MAX, &extra);
+    assert_eq!(parse_local_header(&header).unwrap().size, size);
+
+    let header = central_header(b"big.bin", 1, u32::MAX, &[], &[]);
+    assert_eq!(parse_central_header(&header), None);
+}
+
+#[test]
+fn test_local_header() {
+    let header = local_header(b"style.css", 0, CONTENT_CRC32, CONTENT.len() as u32, &[]);
+    let entry = parse_local_header(&header).expect("To parse");
+    assert_eq!(entry.name, b"style.css");
+    assert_eq!(entry.header_len, header.len());
+    assert_eq!(entry.etag(), EntityTag::const_from_data_crc32(CONTENT));
+
+    let header = local_header(b"style.css", 1 << 3, 0, 0, &[]);
+    assert_eq!(parse_local_header(&header), None);
+}
+
+#[test]
+fn test_malformed_header() {
+    let header = central_header(b"style.css", CONTENT_CRC32, CONTENT.len() as u32, &[], b"comment");
+    for len in 0..header.len() {
+        assert_eq!(parse_central_header(&header[..len]), None);
+    }
+    assert_eq!(parse_local_header(&header), None);
+
+    let header = local_header(b"style.css", 0, CONTENT_CRC32, CONTENT.len() as u32, &[]);
+    for len in 0..header.len() {
+        assert_eq!(parse_local_header(&header[..len]), None);
+    }
+    assert_eq!(parse_central_header(&header), None);
+}